@@ -0,0 +1,128 @@
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+use rusty_rain::{
+    ai::pathfinding::{self, flow_field::FlowField, hierarchical::HierarchicalGraph},
+    math::Pos,
+    world::{
+        building::entrance::Entrance,
+        world_map::{WorldMap, resources::ResourceType},
+    },
+};
+
+/// Square map with bushes all over the place, and a wall of trees every 25 tiles with a few gaps
+fn bench_map(size: usize) -> WorldMap {
+    let mut map = WorldMap::new(size, size);
+    for y in 0..size {
+        for x in 0..size {
+            if (x * 7 + y * 13) % 11 == 0 {
+                *map.get_mut(&Pos::new(x, y)) = ResourceType::tile_berry();
+            }
+        }
+    }
+    for wall_x in (20..size).step_by(25) {
+        for y in (0..size).filter(|y| (y + wall_x) % 37 > 2) {
+            *map.get_mut(&Pos::new(wall_x, y)) = ResourceType::tile_tree();
+        }
+    }
+    map
+}
+
+fn routes(size: usize) -> Vec<(Pos, Pos)> {
+    vec![
+        (Pos::new(2, 2), Pos::new(size - 3, size - 3)),
+        (Pos::new(size - 3, 2), Pos::new(2, size - 3)),
+        (Pos::new(size / 4, size / 2), Pos::new(size - 10, size / 3)),
+    ]
+}
+
+fn compare(c: &mut Criterion) {
+    let mut group = c.benchmark_group("find path");
+    for size in [64, 128, 256] {
+        let map = bench_map(size);
+        let routes = routes(size);
+
+        group.bench_with_input(BenchmarkId::new("a_star", size), &routes, |b, routes| {
+            b.iter(|| {
+                for (start, end) in routes {
+                    pathfinding::a_star_uncached(&map, *start, *end);
+                }
+            })
+        });
+
+        let mut graph = HierarchicalGraph::new(&map);
+        group.bench_with_input(BenchmarkId::new("hierarchical", size), &routes, |b, routes| {
+            b.iter(|| {
+                for (start, end) in routes {
+                    graph.find_path(&map, *start, *end);
+                }
+            })
+        });
+
+        //one field per destination - as if there was a store at the end of every route
+        let fields: Vec<FlowField> = routes.iter().map(|(_, end)| flow_field_to(&map, *end)).collect();
+        group.bench_with_input(BenchmarkId::new("flow_field", size), &routes, |b, routes| {
+            b.iter(|| {
+                for ((start, _), field) in routes.iter().zip(&fields) {
+                    field.path_from(&map, *start);
+                }
+            })
+        });
+    }
+    group.finish();
+}
+
+fn flow_field_to(
+    map: &WorldMap,
+    end: Pos,
+) -> FlowField {
+    let entrance = Entrance {
+        door: Pos::new(end.x + 1, end.y),
+        doorstep: end,
+    };
+    FlowField::new(map, entrance.door, vec![entrance])
+}
+
+fn maintenance(c: &mut Criterion) {
+    let mut group = c.benchmark_group("hierarchical graph");
+    for size in [128, 256] {
+        let map = bench_map(size);
+        group.bench_with_input(BenchmarkId::new("build", size), &map, |b, map| {
+            b.iter(|| HierarchicalGraph::new(map))
+        });
+
+        //a single tile changed, e.g. a new road
+        let mut graph = HierarchicalGraph::new(&map);
+        let changed = Pos::new(size / 2, size / 2);
+        group.bench_with_input(BenchmarkId::new("update", size), &map, |b, map| {
+            b.iter(|| {
+                graph.mark_dirty(&changed);
+                graph.update(map);
+            })
+        });
+    }
+    group.finish();
+}
+
+fn flow_field_maintenance(c: &mut Criterion) {
+    let mut group = c.benchmark_group("flow field");
+    for size in [128, 256] {
+        let map = bench_map(size);
+        let end = Pos::new(2, 2);
+        group.bench_with_input(BenchmarkId::new("build", size), &map, |b, map| {
+            b.iter(|| flow_field_to(map, end))
+        });
+
+        //a single tile changed, e.g. a new road
+        let mut field = flow_field_to(&map, end);
+        let changed = Pos::new(size / 2, size / 2);
+        group.bench_with_input(BenchmarkId::new("update", size), &map, |b, map| {
+            b.iter(|| {
+                field.mark_dirty(&changed);
+                field.update(map);
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, compare, maintenance, flow_field_maintenance);
+criterion_main!(benches);
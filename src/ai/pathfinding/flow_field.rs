@@ -0,0 +1,326 @@
+use std::collections::BinaryHeap;
+
+use super::get_neighbours;
+use crate::{
+    config::buildings::Buildings,
+    data_helpers::with_priority::WithPriority,
+    math::Pos,
+    world::{
+        building::{BuildingBehaviourDiscriminants, entrance::Entrance},
+        world_map::{Movement, WorldMap},
+    },
+};
+
+/// Cost of getting into a single building from every tile of the map, and the next step on the
+/// cheapest way there. Workers keep coming back to the same few buildings - with the field, finding
+/// the way is just following the arrows. Only the tiles count, not the crowds on them - the field
+/// would have to be updated with every step of every worker.
+pub struct FlowField {
+    pub building_pos: Pos,
+    entrances: Vec<Entrance>,
+    ///the field is useless after the movement changes - see WorldMap::set_movement
+    movement: Movement,
+    width: usize,
+    height: usize,
+    ///infinity where there is no way into the building
+    cost: Vec<f32>,
+    ///the door for the doorsteps
+    next: Vec<Option<Pos>>,
+    ///tiles changed since the last update
+    dirty: Vec<Pos>,
+    ///number of tiles recalculated so far - for the tests
+    pub updated_tiles: usize,
+}
+
+/// Hearths and stores - every worker keeps coming back to them
+pub fn wants_flow_field(building: Buildings) -> bool {
+    matches!(
+        building.get_data().building_behaviour,
+        BuildingBehaviourDiscriminants::Hearth | BuildingBehaviourDiscriminants::Store
+    )
+}
+
+impl FlowField {
+    pub fn new(
+        map: &WorldMap,
+        building_pos: Pos,
+        entrances: Vec<Entrance>,
+    ) -> Self {
+        let mut field = Self {
+            building_pos,
+            entrances,
+            movement: map.movement(),
+            width: map.width(),
+            height: map.height(),
+            cost: Vec::new(),
+            next: Vec::new(),
+            dirty: Vec::new(),
+            updated_tiles: 0,
+        };
+        field.rebuild(map);
+        field
+    }
+
+    pub fn mark_dirty(
+        &mut self,
+        pos: &Pos,
+    ) {
+        if !self.dirty.contains(pos) {
+            self.dirty.push(*pos);
+        }
+    }
+
+    /// Cost of getting into the building from the tile, None if there is no way
+    pub fn cost_at(
+        &self,
+        pos: &Pos,
+    ) -> Option<f32> {
+        let cost = self.cost[self.index(pos)];
+        if cost.is_finite() { Some(cost) } else { None }
+    }
+
+    /// The whole field, row by row - for the heat map overlays
+    pub fn rows(&self) -> Vec<Vec<Option<f32>>> {
+        (0..self.height)
+            .map(|y| (0..self.width).map(|x| self.cost_at(&Pos::new(x, y))).collect())
+            .collect()
+    }
+
+    /// Cost of getting into the building for a worker standing at start. The worker does not have to
+    /// stand on a walkable tile - they may just be leaving another building
+    pub fn cost_from(
+        &self,
+        map: &WorldMap,
+        start: &Pos,
+    ) -> Option<f32> {
+        self.first_step(map, start).map(|(cost, _)| cost)
+    }
+
+    /// Follows the arrows from start, all the way through the door - O(path length)
+    pub fn path_from(
+        &self,
+        map: &WorldMap,
+        start: Pos,
+    ) -> Option<Vec<Pos>> {
+        if self.entrances.iter().any(|e| e.door == start) {
+            return Some(vec![start]); //already inside
+        }
+
+        let (_, mut current) = self.first_step(map, &start)?;
+        let mut path = vec![start, current];
+
+        //the doors are the only tiles of the building on the way
+        while !self.entrances.iter().any(|e| e.door == current) {
+            current = self.next[self.index(&current)]?;
+            path.push(current);
+
+            if path.len() > self.cost.len() {
+                return None; //should never happen, but I'd rather not hang the game if it does
+            }
+        }
+        Some(path)
+    }
+
+    fn first_step(
+        &self,
+        map: &WorldMap,
+        start: &Pos,
+    ) -> Option<(f32, Pos)> {
+        let index = self.index(start);
+        if map.get(start).is_traversible() {
+            return self.cost_at(start).map(|cost| (cost, self.next[index].unwrap()));
+        }
+
+        //the first step out of a building has to be picked by hand
+        get_neighbours(map, start)
+            .into_iter()
+            .filter_map(|n| self.cost_at(&n).map(|cost| (cost + map.step_cost(start, &n), n)))
+            .min_by(|l, r| l.0.total_cmp(&r.0))
+    }
+
+    /// Only the part of the field that went through the changed tiles is recalculated, and the
+    /// tiles that may have gotten cheaper
+    pub fn update(
+        &mut self,
+        map: &WorldMap,
+    ) {
+        if self.dirty.is_empty() && self.movement == map.movement() {
+            return;
+        }
+
+        let dirty = std::mem::take(&mut self.dirty);
+        let touches_entrance = dirty
+            .iter()
+            .any(|p| self.entrances.iter().any(|e| e.door == *p || e.doorstep == *p));
+        if touches_entrance || self.movement != map.movement() {
+            self.rebuild(map);
+            return;
+        }
+
+        //every tile whose way led through a changed tile (or squeezed past it diagonally) has to
+        //find a new way
+        let mut roots: Vec<usize> = Vec::new();
+        for pos in &dirty {
+            roots.push(self.index(pos));
+            for neighbour in self.surrounding(pos) {
+                let Some(next) = self.next[self.index(&neighbour)] else {
+                    continue;
+                };
+                let still_allowed = map.get(&neighbour).is_traversible()
+                    && (self.entrances.iter().any(|e| e.door == next)
+                        || get_neighbours(map, &neighbour).contains(&next));
+                if !still_allowed {
+                    roots.push(self.index(&neighbour));
+                }
+            }
+        }
+        let invalid = self.downstream_of(roots);
+
+        for &index in &invalid {
+            self.cost[index] = f32::INFINITY;
+            self.next[index] = None;
+        }
+
+        //the invalidated tiles and the changed ones pick the best of their valid neighbours, and
+        //the search goes on from there
+        let mut frontier: BinaryHeap<WithPriority<Pos>> = BinaryHeap::new();
+        let seeds: Vec<Pos> = invalid
+            .iter()
+            .map(|i| self.pos(*i))
+            .chain(dirty.iter().copied())
+            .collect();
+        for pos in seeds {
+            if !map.get(&pos).is_traversible() {
+                continue;
+            }
+
+            let best = get_neighbours(map, &pos)
+                .into_iter()
+                .filter_map(|n| self.cost_at(&n).map(|cost| (cost + map.step_cost(&pos, &n), n)))
+                .min_by(|l, r| l.0.total_cmp(&r.0));
+            let index = self.index(&pos);
+            if let Some((cost, next)) = best
+                && cost < self.cost[index]
+            {
+                self.cost[index] = cost;
+                self.next[index] = Some(next);
+                frontier.push(WithPriority::new(pos, -cost));
+            }
+        }
+
+        self.propagate(map, frontier);
+    }
+
+    fn rebuild(
+        &mut self,
+        map: &WorldMap,
+    ) {
+        self.movement = map.movement();
+        self.cost = vec![f32::INFINITY; self.width * self.height];
+        self.next = vec![None; self.width * self.height];
+
+        let mut frontier: BinaryHeap<WithPriority<Pos>> = BinaryHeap::new();
+        for entrance in &self.entrances {
+            if !map.get(&entrance.doorstep).is_traversible() {
+                continue;
+            }
+            let cost = map.step_cost(&entrance.doorstep, &entrance.door);
+            let index = self.index(&entrance.doorstep);
+            if cost < self.cost[index] {
+                self.cost[index] = cost;
+                self.next[index] = Some(entrance.door);
+                frontier.push(WithPriority::new(entrance.doorstep, -cost));
+            }
+        }
+
+        self.propagate(map, frontier);
+    }
+
+    /// Dijkstra, backwards - from the tiles with a known cost to the tiles that lead to them
+    fn propagate(
+        &mut self,
+        map: &WorldMap,
+        mut frontier: BinaryHeap<WithPriority<Pos>>,
+    ) {
+        while let Some(current) = frontier.pop() {
+            let current_cost = -current.priority;
+            let current = current.unpack();
+            if current_cost > self.cost[self.index(&current)] {
+                continue; //already found a cheaper way from here
+            }
+            self.updated_tiles += 1;
+
+            //corner cutting rules work both ways, so the neighbours are the tiles one can step here from
+            for previous in get_neighbours(map, &current) {
+                let cost = current_cost + map.step_cost(&previous, &current);
+                let index = self.index(&previous);
+                if cost < self.cost[index] {
+                    self.cost[index] = cost;
+                    self.next[index] = Some(current);
+                    frontier.push(WithPriority::new(previous, -cost));
+                }
+            }
+        }
+    }
+
+    /// The given tiles, and all the tiles whose way goes through them
+    fn downstream_of(
+        &self,
+        roots: Vec<usize>,
+    ) -> Vec<usize> {
+        let roots: Vec<usize> = roots.into_iter().filter(|i| self.cost[*i].is_finite()).collect();
+        if roots.is_empty() {
+            return roots;
+        }
+
+        let mut previous: Vec<Vec<usize>> = vec![Vec::new(); self.cost.len()];
+        for (index, next) in self.next.iter().enumerate() {
+            if let Some(next) = next {
+                previous[self.index(next)].push(index);
+            }
+        }
+
+        let mut visited = vec![false; self.cost.len()];
+        let mut stack = roots;
+        let mut ret = Vec::new();
+        while let Some(index) = stack.pop() {
+            if visited[index] {
+                continue;
+            }
+            visited[index] = true;
+            ret.push(index);
+            stack.extend(previous[index].iter().copied());
+        }
+        ret
+    }
+
+    /// The 8 tiles around, that are still on the map
+    fn surrounding(
+        &self,
+        pos: &Pos,
+    ) -> Vec<Pos> {
+        let mut ret = Vec::new();
+        for y in pos.y.saturating_sub(1)..=(pos.y + 1).min(self.height - 1) {
+            for x in pos.x.saturating_sub(1)..=(pos.x + 1).min(self.width - 1) {
+                if x != pos.x || y != pos.y {
+                    ret.push(Pos::new(x, y));
+                }
+            }
+        }
+        ret
+    }
+
+    fn index(
+        &self,
+        pos: &Pos,
+    ) -> usize {
+        pos.y * self.width + pos.x
+    }
+
+    fn pos(
+        &self,
+        index: usize,
+    ) -> Pos {
+        Pos::new(index % self.width, index / self.width)
+    }
+}
@@ -0,0 +1,613 @@
+use std::collections::{BinaryHeap, HashMap};
+
+use crate::{
+    data_helpers::with_priority::WithPriority,
+    math::Pos,
+    world::world_map::{Movement, WorldMap},
+};
+
+use super::{a_star_uncached, get_adjacent, heuristic};
+
+//HPA* - the map is cut into square clusters. Wherever two neighbouring clusters can be crossed, I
+//place a transition (a pair of tiles, one on each side). Transitions of the same cluster are
+//connected by the cheapest way inside the cluster. Searching this small graph is much cheaper than
+//searching the whole grid, and the real path is only filled in for the few clusters on the way.
+//With 8-way movement, the corners of diagonal neighbours can be crossed too - otherwise the way
+//between them would always lead through a third cluster.
+//The paths are not always the cheapest possible - good enough for walking around the settlement
+
+pub const CLUSTER_SIZE: usize = 16;
+///smaller maps are searched directly - building the graph would not pay off
+pub const MIN_TILES: usize = 64 * 64;
+///border openings this long get a transition at each end, shorter ones only in the middle
+const LONG_OPENING: usize = 6;
+
+#[derive(Clone, Copy)]
+struct Area {
+    x0: usize,
+    y0: usize,
+    ///exclusive
+    x1: usize,
+    y1: usize,
+}
+
+impl Area {
+    fn contains(
+        &self,
+        pos: &Pos,
+    ) -> bool {
+        pos.x >= self.x0 && pos.x < self.x1 && pos.y >= self.y0 && pos.y < self.y1
+    }
+
+    fn index(
+        &self,
+        pos: &Pos,
+    ) -> usize {
+        (pos.y - self.y0) * (self.x1 - self.x0) + (pos.x - self.x0)
+    }
+
+    fn len(&self) -> usize {
+        (self.x1 - self.x0) * (self.y1 - self.y0)
+    }
+
+    /// One tile bigger on each side - so that the tiles right behind the border are included
+    fn grown(
+        &self,
+        map: &WorldMap,
+    ) -> Area {
+        Area {
+            x0: self.x0.saturating_sub(1),
+            y0: self.y0.saturating_sub(1),
+            x1: (self.x1 + 1).min(map.width()),
+            y1: (self.y1 + 1).min(map.height()),
+        }
+    }
+}
+
+/// Dijkstra limited to a small area. Tables are plain Vecs over the area, not HashMaps over the
+/// whole map. A reverse search finds the cost of getting from every tile TO the source. Only the
+/// searches around the start and the goal of a single path see the crowds - for the worker standing
+/// on `own` - the graph itself would be out of date with every step of every worker
+struct AreaSearch {
+    area: Area,
+    source: Pos,
+    cost: Vec<f32>,
+    came_from: Vec<Option<Pos>>,
+}
+
+impl AreaSearch {
+    fn run(
+        map: &WorldMap,
+        source: Pos,
+        area: Area,
+        reverse: bool,
+        own: Option<&Pos>,
+    ) -> Self {
+        let mut search = Self {
+            area,
+            source,
+            cost: vec![f32::INFINITY; area.len()],
+            came_from: vec![None; area.len()],
+        };
+
+        let mut frontier: BinaryHeap<WithPriority<Pos>> = BinaryHeap::new();
+        search.cost[area.index(&source)] = 0.0;
+        frontier.push(WithPriority::default(source));
+
+        while let Some(current) = frontier.pop() {
+            let current_cost = -current.priority;
+            let current = current.unpack();
+            if current_cost > search.cost[area.index(&current)] {
+                continue; //already found a cheaper way here
+            }
+
+            for next in get_adjacent(map, &current) {
+                if !area.contains(&next) || !map.get(&next).is_traversible() {
+                    continue;
+                }
+
+                //walking costs as much as the tile the worker enters
+                let (from, to) = if reverse { (&next, &current) } else { (&current, &next) };
+                let step = match own {
+                    Some(own) => map.route_step_cost(from, to, own),
+                    None => map.step_cost(from, to),
+                };
+
+                let cost = current_cost + step;
+                let index = area.index(&next);
+                if cost < search.cost[index] {
+                    search.cost[index] = cost;
+                    search.came_from[index] = Some(current);
+                    frontier.push(WithPriority::new(next, -cost));
+                }
+            }
+        }
+
+        search
+    }
+
+    fn cost(
+        &self,
+        pos: &Pos,
+    ) -> Option<f32> {
+        if !self.area.contains(pos) {
+            return None;
+        }
+        let cost = self.cost[self.area.index(pos)];
+        cost.is_finite().then_some(cost)
+    }
+
+    /// Chain of tiles from `pos` back to the source - for a forward search it has to be reversed
+    fn chain(
+        &self,
+        pos: Pos,
+    ) -> Vec<Pos> {
+        let mut chain = vec![pos];
+        let mut current = pos;
+        while current != self.source {
+            current = self.came_from[self.area.index(&current)].unwrap();
+            chain.push(current);
+        }
+        chain
+    }
+}
+
+pub struct HierarchicalGraph {
+    clusters_x: usize,
+    clusters_y: usize,
+    ///transitions to the cluster on the right and to the cluster below, by cluster
+    right_borders: Vec<Vec<(Pos, Pos)>>,
+    down_borders: Vec<Vec<(Pos, Pos)>>,
+    ///transitions to the clusters diagonally below, by cluster. Only with 8-way movement
+    corner_borders: Vec<Vec<(Pos, Pos)>>,
+    ///transition tiles of each cluster, always in the same order
+    nodes: Vec<Vec<Pos>>,
+    ///cheapest ways between the transitions of the same cluster
+    intra_edges: HashMap<Pos, Vec<(Pos, f32)>>,
+    ///crossings to the neighbouring clusters
+    inter_edges: HashMap<Pos, Vec<(Pos, f32)>>,
+    ///clusters with changed tiles, to be updated before the next search
+    dirty: Vec<usize>,
+    ///number of clusters recalculated so far - for the tests and benchmarks
+    pub updated_clusters: usize,
+}
+
+impl HierarchicalGraph {
+    pub fn new(map: &WorldMap) -> Self {
+        let clusters_x = map.width().div_ceil(CLUSTER_SIZE);
+        let clusters_y = map.height().div_ceil(CLUSTER_SIZE);
+        let count = clusters_x * clusters_y;
+
+        let mut graph = Self {
+            clusters_x,
+            clusters_y,
+            right_borders: vec![Vec::new(); count],
+            down_borders: vec![Vec::new(); count],
+            corner_borders: vec![Vec::new(); count],
+            nodes: vec![Vec::new(); count],
+            intra_edges: HashMap::new(),
+            inter_edges: HashMap::new(),
+            dirty: (0..count).collect(),
+            updated_clusters: 0,
+        };
+        graph.update(map);
+        graph
+    }
+
+    pub fn mark_dirty(
+        &mut self,
+        pos: &Pos,
+    ) {
+        let cluster = self.cluster_of(pos);
+        if !self.dirty.contains(&cluster) {
+            self.dirty.push(cluster);
+        }
+    }
+
+    /// Only the clusters with changed tiles and their neighbours are recalculated - the borders
+    /// they share may have gained or lost transitions
+    pub fn update(
+        &mut self,
+        map: &WorldMap,
+    ) {
+        if self.dirty.is_empty() {
+            return;
+        }
+
+        let mut dirty = std::mem::take(&mut self.dirty);
+        dirty.sort();
+
+        for &cluster in &dirty {
+            self.right_borders[cluster] = self.find_transitions(map, cluster, true);
+            self.down_borders[cluster] = self.find_transitions(map, cluster, false);
+            if let Some(left) = self.left_of(cluster) {
+                self.right_borders[left] = self.find_transitions(map, left, true);
+            }
+            if let Some(up) = self.above(cluster) {
+                self.down_borders[up] = self.find_transitions(map, up, false);
+            }
+        }
+
+        //a corner crossing depends on the tiles of all four clusters around the corner
+        let diagonal = map.movement() == Movement::EightWay;
+        let mut affected: Vec<usize> = if diagonal {
+            dirty.iter().flat_map(|c| self.with_all_neighbours(*c)).collect()
+        } else {
+            dirty.iter().flat_map(|c| self.with_neighbours(*c)).collect()
+        };
+        affected.sort();
+        affected.dedup();
+
+        if diagonal {
+            for &cluster in &affected {
+                self.corner_borders[cluster] = self.find_corner_transitions(map, cluster);
+            }
+        }
+
+        for &cluster in &affected {
+            for node in std::mem::take(&mut self.nodes[cluster]) {
+                self.intra_edges.remove(&node);
+            }
+            self.nodes[cluster] = self.find_nodes(cluster);
+        }
+        for &cluster in &affected {
+            self.connect_nodes(map, cluster);
+        }
+        self.updated_clusters += affected.len();
+
+        //there are only a few crossings per border, rebuilding all of them is cheap
+        self.inter_edges.clear();
+        for cluster in 0..self.right_borders.len() {
+            for &(a, b) in self.right_borders[cluster]
+                .iter()
+                .chain(self.down_borders[cluster].iter())
+                .chain(self.corner_borders[cluster].iter())
+            {
+                self.inter_edges.entry(a).or_default().push((b, map.step_cost(&a, &b)));
+                self.inter_edges.entry(b).or_default().push((a, map.step_cost(&b, &a)));
+            }
+        }
+    }
+
+    pub fn find_path(
+        &mut self,
+        map: &WorldMap,
+        start: Pos,
+        end: Pos,
+    ) -> Option<Vec<Pos>> {
+        self.update(map);
+
+        //nothing to gain in the same cluster
+        if self.cluster_of(&start) == self.cluster_of(&end) {
+            return a_star_uncached(map, start, end);
+        }
+
+        let start_area = self.area_of(map, self.cluster_of(&start)).grown(map);
+        let start_search = AreaSearch::run(map, start, start_area, false, Some(&start));
+        let goal_area = self.area_of(map, self.cluster_of(&end)).grown(map);
+        let goal_search = AreaSearch::run(map, end, goal_area, true, Some(&start));
+
+        let start_links: Vec<(Pos, f32)> = self
+            .nodes_in(start_area)
+            .into_iter()
+            .filter_map(|n| start_search.cost(&n).map(|c| (n, c)))
+            .collect();
+
+        //the abstract search - None stands for the goal
+        let mut frontier: BinaryHeap<WithPriority<Option<Pos>>> = BinaryHeap::new();
+        frontier.push(WithPriority::default(Some(start)));
+        let mut cost_so_far: HashMap<Option<Pos>, f32> = HashMap::from([(Some(start), 0.0)]);
+        let mut came_from: HashMap<Option<Pos>, Option<Pos>> = HashMap::new();
+
+        while let Some(current) = frontier.pop() {
+            let Some(current) = current.unpack() else {
+                return Some(self.refine(map, &came_from, &start_search, &goal_search));
+            };
+
+            let mut links: Vec<(Option<Pos>, f32)> = Vec::new();
+            if current == start {
+                links.extend(start_links.iter().map(|(n, c)| (Some(*n), *c)));
+            }
+            for edges in [self.intra_edges.get(&current), self.inter_edges.get(&current)]
+                .into_iter()
+                .flatten()
+            {
+                links.extend(edges.iter().map(|(n, c)| (Some(*n), *c)));
+            }
+            if let Some(cost) = goal_search.cost(&current) {
+                links.push((None, cost));
+            }
+
+            for (next, step) in links {
+                let cost = cost_so_far[&Some(current)] + step;
+                if cost_so_far.get(&next).is_none_or(|c| cost < *c) {
+                    cost_so_far.insert(next, cost);
+                    came_from.insert(next, Some(current));
+                    let priority = cost + next.map(|n| heuristic(&end, &n, map.movement())).unwrap_or(0.0);
+                    frontier.push(WithPriority::new(next, -priority));
+                }
+            }
+        }
+
+        //the transitions are placed so, that this should only happen when there is no way at all -
+        //but the tiles around the start and the goal can be tricky, so better check properly
+        a_star_uncached(map, start, end)
+    }
+
+    /// Turns the chain of transitions into tiles
+    fn refine(
+        &self,
+        map: &WorldMap,
+        came_from: &HashMap<Option<Pos>, Option<Pos>>,
+        start_search: &AreaSearch,
+        goal_search: &AreaSearch,
+    ) -> Vec<Pos> {
+        let mut nodes = Vec::new();
+        let mut current = came_from[&None];
+        while let Some(node) = current {
+            nodes.push(node);
+            current = came_from.get(&Some(node)).copied().flatten();
+        }
+        nodes.reverse();
+
+        //nodes[0] is the start, the first transition is reached by the start search
+        let mut path: Vec<Pos> = if nodes.len() > 1 {
+            start_search.chain(nodes[1]).into_iter().rev().collect()
+        } else {
+            vec![nodes[0]]
+        };
+
+        for pair in nodes[1..].windows(2) {
+            let (from, to) = (pair[0], pair[1]);
+            if self.cluster_of(&from) != self.cluster_of(&to) {
+                path.push(to);
+                continue;
+            }
+
+            let area = self.area_of(map, self.cluster_of(&from));
+            let search = AreaSearch::run(map, from, area, false, None);
+            path.extend(search.chain(to).into_iter().rev().skip(1));
+        }
+
+        let last = *nodes.last().unwrap();
+        path.extend(goal_search.chain(last).into_iter().skip(1));
+        path
+    }
+
+    fn cluster_of(
+        &self,
+        pos: &Pos,
+    ) -> usize {
+        (pos.y / CLUSTER_SIZE) * self.clusters_x + pos.x / CLUSTER_SIZE
+    }
+
+    fn area_of(
+        &self,
+        map: &WorldMap,
+        cluster: usize,
+    ) -> Area {
+        let x0 = (cluster % self.clusters_x) * CLUSTER_SIZE;
+        let y0 = (cluster / self.clusters_x) * CLUSTER_SIZE;
+        Area {
+            x0,
+            y0,
+            x1: (x0 + CLUSTER_SIZE).min(map.width()),
+            y1: (y0 + CLUSTER_SIZE).min(map.height()),
+        }
+    }
+
+    fn left_of(
+        &self,
+        cluster: usize,
+    ) -> Option<usize> {
+        (!cluster.is_multiple_of(self.clusters_x)).then(|| cluster - 1)
+    }
+
+    fn above(
+        &self,
+        cluster: usize,
+    ) -> Option<usize> {
+        (cluster >= self.clusters_x).then(|| cluster - self.clusters_x)
+    }
+
+    fn with_neighbours(
+        &self,
+        cluster: usize,
+    ) -> Vec<usize> {
+        let mut ret = vec![cluster];
+        ret.extend(self.left_of(cluster));
+        ret.extend(self.above(cluster));
+        if cluster % self.clusters_x + 1 < self.clusters_x {
+            ret.push(cluster + 1);
+        }
+        if cluster + self.clusters_x < self.clusters_x * self.clusters_y {
+            ret.push(cluster + self.clusters_x);
+        }
+        ret
+    }
+
+    /// The cluster `dx` columns and `dy` rows away, if it is on the map
+    fn offset(
+        &self,
+        cluster: usize,
+        dx: isize,
+        dy: isize,
+    ) -> Option<usize> {
+        let x = (cluster % self.clusters_x).checked_add_signed(dx)?;
+        let y = (cluster / self.clusters_x).checked_add_signed(dy)?;
+        (x < self.clusters_x && y < self.clusters_y).then_some(y * self.clusters_x + x)
+    }
+
+    fn with_all_neighbours(
+        &self,
+        cluster: usize,
+    ) -> Vec<usize> {
+        let mut ret = Vec::new();
+        for dy in -1..=1 {
+            for dx in -1..=1 {
+                ret.extend(self.offset(cluster, dx, dy));
+            }
+        }
+        ret
+    }
+
+    /// Transitions on the right (or bottom) border of the cluster. Every opening - a run of tiles
+    /// traversible on both sides - gets one transition in the middle, long ones one at each end
+    fn find_transitions(
+        &self,
+        map: &WorldMap,
+        cluster: usize,
+        right: bool,
+    ) -> Vec<(Pos, Pos)> {
+        let area = self.area_of(map, cluster);
+        let (border, other_side) = if right {
+            (area.x1 - 1, area.x1)
+        } else {
+            (area.y1 - 1, area.y1)
+        };
+        let limit = if right { map.width() } else { map.height() };
+        if other_side >= limit {
+            return Vec::new();
+        }
+
+        let along = if right { area.y0..area.y1 } else { area.x0..area.x1 };
+        let pair = |i: usize| {
+            if right {
+                (Pos::new(border, i), Pos::new(other_side, i))
+            } else {
+                (Pos::new(i, border), Pos::new(i, other_side))
+            }
+        };
+        let open = |i: usize| {
+            let (a, b) = pair(i);
+            map.get(&a).is_traversible() && map.get(&b).is_traversible()
+        };
+
+        let mut transitions = Vec::new();
+        let mut run_start: Option<usize> = None;
+        let end = along.end;
+        for i in along.chain([end]) {
+            match (run_start, i < end && open(i)) {
+                (None, true) => run_start = Some(i),
+                (Some(from), false) => {
+                    let length = i - from;
+                    if length >= LONG_OPENING {
+                        transitions.push(pair(from));
+                        transitions.push(pair(i - 1));
+                    } else {
+                        transitions.push(pair(from + length / 2));
+                    }
+                    run_start = None;
+                }
+                _ => {}
+            }
+        }
+        transitions
+    }
+
+    /// Transitions through the bottom corners of the cluster, to the clusters diagonally below. A
+    /// corner can only be crossed if both of the tiles next to the diagonal step are free too - no
+    /// cutting corners, same as in get_adjacent
+    fn find_corner_transitions(
+        &self,
+        map: &WorldMap,
+        cluster: usize,
+    ) -> Vec<(Pos, Pos)> {
+        let area = self.area_of(map, cluster);
+        if area.y1 >= map.height() {
+            return Vec::new();
+        }
+
+        let mut corners = Vec::new();
+        if area.x1 < map.width() {
+            corners.push((Pos::new(area.x1 - 1, area.y1 - 1), Pos::new(area.x1, area.y1)));
+        }
+        if area.x0 > 0 {
+            corners.push((Pos::new(area.x0, area.y1 - 1), Pos::new(area.x0 - 1, area.y1)));
+        }
+
+        let free = |p: Pos| map.get(&p).is_traversible();
+        corners
+            .into_iter()
+            .filter(|(a, b)| free(*a) && free(*b) && free(Pos::new(b.x, a.y)) && free(Pos::new(a.x, b.y)))
+            .collect()
+    }
+
+    fn find_nodes(
+        &self,
+        cluster: usize,
+    ) -> Vec<Pos> {
+        let mut nodes: Vec<Pos> = Vec::new();
+        nodes.extend(self.right_borders[cluster].iter().map(|t| t.0));
+        nodes.extend(self.down_borders[cluster].iter().map(|t| t.0));
+        nodes.extend(self.corner_borders[cluster].iter().map(|t| t.0));
+        for above in [self.offset(cluster, -1, -1), self.offset(cluster, 1, -1)]
+            .into_iter()
+            .flatten()
+        {
+            nodes.extend(
+                self.corner_borders[above]
+                    .iter()
+                    .map(|t| t.1)
+                    .filter(|n| self.cluster_of(n) == cluster),
+            );
+        }
+        if let Some(left) = self.left_of(cluster) {
+            nodes.extend(self.right_borders[left].iter().map(|t| t.1));
+        }
+        if let Some(up) = self.above(cluster) {
+            nodes.extend(self.down_borders[up].iter().map(|t| t.1));
+        }
+        nodes.sort_by_key(|p| (p.y, p.x));
+        nodes.dedup();
+        nodes
+    }
+
+    fn connect_nodes(
+        &mut self,
+        map: &WorldMap,
+        cluster: usize,
+    ) {
+        let area = self.area_of(map, cluster);
+        for &node in &self.nodes[cluster] {
+            let search = AreaSearch::run(map, node, area, false, None);
+            let edges = self.nodes[cluster]
+                .iter()
+                .filter(|n| **n != node)
+                .filter_map(|n| search.cost(n).map(|c| (*n, c)))
+                .collect();
+            self.intra_edges.insert(node, edges);
+        }
+    }
+
+    fn nodes_in(
+        &self,
+        area: Area,
+    ) -> Vec<Pos> {
+        let first = self.cluster_of(&Pos::new(area.x0, area.y0));
+        let last = self.cluster_of(&Pos::new(area.x1 - 1, area.y1 - 1));
+        let (first_x, first_y) = (first % self.clusters_x, first / self.clusters_x);
+        let (last_x, last_y) = (last % self.clusters_x, last / self.clusters_x);
+
+        let mut ret = Vec::new();
+        for y in first_y..=last_y {
+            for x in first_x..=last_x {
+                let cluster = y * self.clusters_x + x;
+                ret.extend(self.nodes[cluster].iter().filter(|n| area.contains(n)));
+            }
+        }
+        ret
+    }
+}
+
+/// Hierarchical search using the graph stored in the map - built on the first search, and kept up
+/// to date with the changes made through WorldMap::build and WorldMap::get_mut
+pub fn find_path(
+    map: &WorldMap,
+    start: Pos,
+    end: Pos,
+) -> Option<Vec<Pos>> {
+    let mut graph = map.hierarchy.borrow_mut();
+    let graph = graph.get_or_insert_with(|| HierarchicalGraph::new(map));
+    graph.find_path(map, start, end)
+}
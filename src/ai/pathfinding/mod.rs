@@ -1,13 +1,16 @@
 use std::collections::{BinaryHeap, HashMap};
 
 pub mod debug_path_drawer;
+pub mod flow_field;
+pub mod hierarchical;
+pub mod path_cache;
 pub mod pathfinding_helpers;
 
 use debug_path_drawer::PathDrawer;
-use log::info;
+use log::trace;
 
 use crate::data_helpers::with_priority::WithPriority;
-use crate::world::world_map::TileType;
+use crate::world::world_map::{Movement, TileType};
 use crate::{math::Pos, world::world_map::WorldMap};
 
 const HEURISTICS_INFLUENCE: f32 = 0.5;
@@ -47,7 +50,7 @@ where
 
         //continue search
         for next in get_neighbours(map, &current) {
-            let cost = cost_so_far[&current] + map.get(&next).cost();
+            let cost = cost_so_far[&current] + map.route_step_cost(&current, &next, &start);
 
             let cost_exists = cost_so_far.contains_key(&next);
 
@@ -69,10 +72,281 @@ where
     None
 }
 
+/// Single search for the cheapest of many targets - cheaper than running a_star to each of them
+/// and comparing. Targets do not have to be traversible (buildings are not), but the search never
+/// goes through them. Returns the index of the target reached, and the path to it
+pub fn dijkstra_closest_target(
+    map: &WorldMap,
+    start: Pos,
+    targets: &[Pos],
+) -> Option<(usize, Vec<Pos>)> {
+    if let Some(index) = targets.iter().position(|t| *t == start) {
+        return Some((index, Vec::from_iter([start])));
+    }
+
+    let start_cost = WithPriority::default(start);
+
+    let mut frontier: BinaryHeap<WithPriority<Pos>> = BinaryHeap::new();
+    frontier.push(start_cost);
+
+    let mut came_from: HashMap<Pos, Option<Pos>> = HashMap::new();
+    came_from.insert(start, None);
+
+    let mut cost_so_far: HashMap<Pos, f32> = HashMap::new();
+    cost_so_far.insert(start, 0.0);
+
+    while let Some(current) = frontier.pop() {
+        let current = current.unpack();
+
+        //the first target to leave the frontier is the cheapest one
+        if let Some(index) = targets.iter().position(|t| *t == current) {
+            return Some((index, build_path(&came_from, current, map)));
+        }
+
+        let neighbours = get_adjacent(map, &current)
+            .into_iter()
+            .filter(|p| map.get(p).is_traversible() || targets.contains(p));
+
+        for next in neighbours {
+            let cost = cost_so_far[&current] + map.route_step_cost(&current, &next, &start);
+
+            if cost_so_far.get(&next).is_none_or(|c| cost < *c) {
+                cost_so_far.insert(next, cost);
+                frontier.push(WithPriority::new(next, -cost));
+                came_from.insert(next, Some(current));
+            }
+        }
+    }
+
+    None
+}
+
+/// Like dijkstra_closest_target, but the targets are buildings - the worker walks up to the cheapest
+/// of all the doorsteps, and steps into the building. Returns the index of the building
+pub fn dijkstra_closest_building(
+    map: &WorldMap,
+    start: Pos,
+    buildings: &[Pos],
+) -> Option<(usize, Vec<Pos>)> {
+    for (index, building_pos) in buildings.iter().enumerate() {
+        if map.entrances(building_pos).iter().any(|e| e.door == start) {
+            return Some((index, vec![start])); //already inside
+        }
+    }
+
+    //hearths and stores have their flow fields - no need to search at all
+    let field_costs: Option<Vec<Option<f32>>> = buildings
+        .iter()
+        .map(|b| map.flow_field(b).map(|f| f.cost_from(map, &start)))
+        .collect();
+    if let Some(costs) = field_costs {
+        let (index, _) = costs
+            .iter()
+            .enumerate()
+            .filter_map(|(i, c)| c.map(|c| (i, c)))
+            .min_by(|l, r| l.1.total_cmp(&r.1))?;
+        let path = map.flow_field(&buildings[index])?.path_from(map, start)?;
+        return Some((index, path));
+    }
+
+    let mut targets = Vec::new();
+    //building index and the door behind each of the targets
+    let mut doors: Vec<(usize, Option<Pos>)> = Vec::new();
+
+    for (index, building_pos) in buildings.iter().enumerate() {
+        let entrances = map.entrances(building_pos);
+        if entrances.is_empty() {
+            targets.push(*building_pos);
+            doors.push((index, None));
+        }
+        for entrance in entrances.iter().filter(|e| map.get(&e.doorstep).is_traversible()) {
+            targets.push(entrance.doorstep);
+            doors.push((index, Some(entrance.door)));
+        }
+    }
+
+    let (target, mut path) = dijkstra_closest_target(map, start, &targets)?;
+    let (index, door) = doors[target];
+    if let Some(door) = door {
+        path.push(door);
+    }
+    Some((index, path))
+}
+
+/// Cost of the cheapest way to every one of the targets, in a single search - None for the ones
+/// that cannot be reached. Like with dijkstra_closest_target, the targets do not have to be
+/// traversible, but the search never goes through them
+pub fn dijkstra_costs(
+    map: &WorldMap,
+    start: Pos,
+    targets: &[Pos],
+) -> Vec<Option<f32>> {
+    let mut cost_so_far: HashMap<Pos, f32> = HashMap::new();
+    cost_so_far.insert(start, 0.0);
+
+    let mut frontier: BinaryHeap<WithPriority<Pos>> = BinaryHeap::new();
+    frontier.push(WithPriority::default(start));
+
+    let mut remaining = targets.len();
+    let mut reached: Vec<Option<f32>> = vec![None; targets.len()];
+
+    while let Some(current) = frontier.pop() {
+        let current_cost = -current.priority;
+        let current = current.unpack();
+        if current_cost > cost_so_far[&current] {
+            continue; //already found a cheaper way here
+        }
+
+        //the same tile might be listed more than once
+        for (index, _) in targets.iter().enumerate().filter(|(_, t)| **t == current) {
+            reached[index] = Some(current_cost);
+            remaining -= 1;
+        }
+        if remaining == 0 {
+            break;
+        }
+        if current != start && !map.get(&current).is_traversible() {
+            continue; //a building - not going through
+        }
+
+        let neighbours = get_adjacent(map, &current)
+            .into_iter()
+            .filter(|p| map.get(p).is_traversible() || targets.contains(p));
+
+        for next in neighbours {
+            let cost = current_cost + map.route_step_cost(&current, &next, &start);
+            if cost_so_far.get(&next).is_none_or(|c| cost < *c) {
+                cost_so_far.insert(next, cost);
+                frontier.push(WithPriority::new(next, -cost));
+            }
+        }
+    }
+
+    reached
+}
+
+/// Cost of getting from every tile of the map to the goal - a single search backwards from it, for
+/// when the way there has to be priced from many places. The goal is reached the same way as with
+/// a_star - by stepping next to it
+pub struct DistanceField {
+    goal: Pos,
+    cost: HashMap<Pos, f32>,
+}
+
+impl DistanceField {
+    pub fn new(
+        map: &WorldMap,
+        goal: Pos,
+    ) -> Self {
+        let mut cost: HashMap<Pos, f32> = HashMap::new();
+        let mut frontier: BinaryHeap<WithPriority<Pos>> = BinaryHeap::new();
+        for next in get_neighbours(map, &goal) {
+            let step = map.step_cost(&next, &goal);
+            cost.insert(next, step);
+            frontier.push(WithPriority::new(next, -step));
+        }
+
+        while let Some(current) = frontier.pop() {
+            let current_cost = -current.priority;
+            let current = current.unpack();
+            if current_cost > cost[&current] {
+                continue; //already found a cheaper way here
+            }
+
+            //corner cutting rules work both ways, so the neighbours are the tiles one can step here from
+            for previous in get_neighbours(map, &current) {
+                let total = current_cost + map.step_cost(&previous, &current);
+                if cost.get(&previous).is_none_or(|c| total < *c) {
+                    cost.insert(previous, total);
+                    frontier.push(WithPriority::new(previous, -total));
+                }
+            }
+        }
+
+        Self { goal, cost }
+    }
+
+    /// From the tiles outside of the field too - the first step out of a building is picked by hand
+    pub fn cost_from(
+        &self,
+        map: &WorldMap,
+        start: &Pos,
+    ) -> Option<f32> {
+        if *start == self.goal {
+            return Some(0.0);
+        }
+        if let Some(cost) = self.cost.get(start) {
+            return Some(*cost);
+        }
+        if is_nearby(map, start, &self.goal) {
+            return Some(map.step_cost(start, &self.goal));
+        }
+
+        get_neighbours(map, start)
+            .iter()
+            .filter_map(|n| self.cost.get(n).map(|cost| cost + map.step_cost(start, n)))
+            .min_by(|l, r| l.total_cmp(r))
+    }
+}
+
+/// Path into the building at building_pos, through the cheapest of its entrances. Buildings without
+/// entrances are walked up to from any side
+pub fn path_into_building(
+    map: &WorldMap,
+    start: Pos,
+    building_pos: Pos,
+) -> Option<Vec<Pos>> {
+    let entrances = map.entrances(&building_pos);
+    if entrances.is_empty() {
+        return a_star(map, start, building_pos);
+    }
+    if entrances.iter().any(|e| e.door == start) {
+        return Some(vec![start]); //already inside
+    }
+    if let Some(field) = map.flow_field(&building_pos) {
+        return field.path_from(map, start);
+    }
+
+    let path_cost = |path: &Vec<Pos>| map.path_to_cost(path).iter().sum::<f32>();
+    entrances
+        .iter()
+        .filter(|e| map.get(&e.doorstep).is_traversible())
+        .filter_map(|e| {
+            let mut path = if start == e.doorstep {
+                vec![start]
+            } else {
+                a_star(map, start, e.doorstep)?
+            };
+            path.push(e.door);
+            Some(path)
+        })
+        .min_by(|l, r| path_cost(l).total_cmp(&path_cost(r)))
+}
+
+/// Asks the map's path cache first. Big maps are searched hierarchically - see hierarchical.rs
 pub fn a_star(
     map: &WorldMap,
     start: Pos,
     end: Pos,
+) -> Option<Vec<Pos>> {
+    let mut cache = map.path_cache.borrow_mut();
+    if let Some(path) = cache.get(start, end, map.revision, map.congestion_revision) {
+        return path;
+    }
+
+    let path = if map.width() * map.height() >= hierarchical::MIN_TILES {
+        hierarchical::find_path(map, start, end)
+    } else {
+        a_star_uncached(map, start, end)
+    };
+    cache.insert(start, end, map.revision, map.congestion_revision, path.clone());
+    path
+}
+
+pub fn a_star_uncached(
+    map: &WorldMap,
+    start: Pos,
+    end: Pos,
 ) -> Option<Vec<Pos>> {
     if start == end {
         return Some(Vec::from_iter([start]));
@@ -101,7 +375,7 @@ pub fn a_star(
 
         //continue search
         for next in get_neighbours(map, &current) {
-            let cost = cost_so_far[&current] + map.get(&next).cost();
+            let cost = cost_so_far[&current] + map.route_step_cost(&current, &next, &start);
 
             let cost_exists = cost_so_far.contains_key(&next);
 
@@ -112,7 +386,7 @@ pub fn a_star(
             if !cost_exists || cost < cost_so_far[&next] {
                 cost_so_far.insert(next, cost);
 
-                let priority = cost + heuristic(&end, &next);
+                let priority = cost + heuristic(&end, &next, map.movement());
                 frontier.push(WithPriority::new(next, -priority));
 
                 came_from.insert(next, Some(current));
@@ -138,9 +412,10 @@ fn build_path(
     }
     let path = path.iter().copied().rev().collect();
 
+    //drawing the whole map on every search was the slowest part of the pathfinding - only when
+    //really needed
     let map_drawer = PathDrawer { map, path: &path };
-
-    info!("\n{}", map_drawer);
+    trace!("\n{}", map_drawer);
 
     path
 }
@@ -148,10 +423,16 @@ fn build_path(
 fn heuristic(
     a: &Pos,
     b: &Pos,
+    movement: Movement,
 ) -> f32 {
     //assumes square grid
-    //TODO: explore diagonal movement...
-    let base = (a.x as f32 - b.x as f32).abs() + (a.y as f32 - b.y as f32).abs();
+    let dx = (a.x as f32 - b.x as f32).abs();
+    let dy = (a.y as f32 - b.y as f32).abs();
+    let base = match movement {
+        Movement::FourWay => dx + dy,
+        //octile distance - every diagonal step replaces two straight ones
+        Movement::EightWay => dx + dy + (std::f32::consts::SQRT_2 - 2.0) * dx.min(dy),
+    };
     base * HEURISTICS_INFLUENCE
 }
 
@@ -159,34 +440,48 @@ fn get_neighbours(
     map: &WorldMap,
     pos: &Pos,
 ) -> Vec<Pos> {
-    let bottom = if pos.y > 0 {
-        Some(Pos::new(pos.x, pos.y - 1))
-    } else {
-        None
-    };
-    let top = if pos.y < map.height() - 1 {
-        Some(Pos::new(pos.x, pos.y + 1))
-    } else {
-        None
-    };
-    let left = if pos.x > 0 {
-        Some(Pos::new(pos.x - 1, pos.y))
-    } else {
-        None
-    };
-    let right = if pos.x < map.width() - 1 {
-        Some(Pos::new(pos.x + 1, pos.y))
-    } else {
-        None
+    get_adjacent(map, pos)
+        .into_iter()
+        .filter(|x| map.get(x).is_traversible())
+        .collect()
+}
+
+/// All the tiles next to the given one, that are still on the map. With 8-way movement diagonal
+/// tiles are included only when the worker would not have to squeeze between two obstacles
+fn get_adjacent(
+    map: &WorldMap,
+    pos: &Pos,
+) -> Vec<Pos> {
+    let offset = |dx: i32, dy: i32| {
+        let x = pos.x as i32 + dx;
+        let y = pos.y as i32 + dy;
+        if x < 0 || y < 0 || x >= map.width() as i32 || y >= map.height() as i32 {
+            None
+        } else {
+            Some(Pos::new(x as usize, y as usize))
+        }
     };
 
-    let ret = [bottom, left, top, right];
-    let valid = ret
+    let mut ret: Vec<Pos> = [(0, -1), (-1, 0), (0, 1), (1, 0)]
         .iter()
-        .filter_map(|p| p.as_ref())
-        .filter(|&x| (map.get(x).is_traversible()));
+        .filter_map(|(dx, dy)| offset(*dx, *dy))
+        .collect();
+
+    if map.movement() == Movement::EightWay {
+        let diagonals = [(-1, -1), (-1, 1), (1, 1), (1, -1)];
+        for (dx, dy) in diagonals {
+            let Some(diagonal) = offset(dx, dy) else {
+                continue;
+            };
+            //no cutting corners around trees and buildings
+            let free = |p: Option<Pos>| p.is_some_and(|p| map.get(&p).is_traversible());
+            if free(offset(dx, 0)) && free(offset(0, dy)) {
+                ret.push(diagonal);
+            }
+        }
+    }
 
-    valid.cloned().collect()
+    ret
 }
 
 fn get_nearby<F>(
@@ -197,65 +492,13 @@ fn get_nearby<F>(
 where
     F: Fn(&TileType) -> bool,
 {
-    let bottom = if pos.y > 0 {
-        Some(Pos::new(pos.x, pos.y - 1))
-    } else {
-        None
-    };
-    let top = if pos.y < map.height() - 1 {
-        Some(Pos::new(pos.x, pos.y + 1))
-    } else {
-        None
-    };
-    let left = if pos.x > 0 {
-        Some(Pos::new(pos.x - 1, pos.y))
-    } else {
-        None
-    };
-    let right = if pos.x < map.width() - 1 {
-        Some(Pos::new(pos.x + 1, pos.y))
-    } else {
-        None
-    };
-
-    let ret = [bottom, left, top, right];
-    ret.iter()
-        .filter_map(|p| p.as_ref())
-        .filter(|t| tile_type_check(map.get(*t)))
-        .nth(0)
-        .copied()
+    get_adjacent(map, pos).into_iter().find(|t| tile_type_check(map.get(t)))
 }
+
 fn is_nearby(
     map: &WorldMap,
     pos: &Pos,
     target: &Pos,
-) -> bool
-where
-{
-    let bottom = if pos.y > 0 {
-        Some(Pos::new(pos.x, pos.y - 1))
-    } else {
-        None
-    };
-    let top = if pos.y < map.height() - 1 {
-        Some(Pos::new(pos.x, pos.y + 1))
-    } else {
-        None
-    };
-    let left = if pos.x > 0 {
-        Some(Pos::new(pos.x - 1, pos.y))
-    } else {
-        None
-    };
-    let right = if pos.x < map.width() - 1 {
-        Some(Pos::new(pos.x + 1, pos.y))
-    } else {
-        None
-    };
-
-    let ret = [bottom, left, top, right];
-    ret.iter()
-        .filter_map(|p| p.as_ref())
-        .filter(|&p| p == target)
-        .any(|_| true)
+) -> bool {
+    get_adjacent(map, pos).contains(target)
 }
@@ -0,0 +1,68 @@
+use std::collections::HashMap;
+
+use crate::math::Pos;
+
+/// Paths found by a_star, remembered until the map changes. Workers keep walking between the same
+/// few buildings, so most searches were repeated over and over again.
+pub struct PathCache {
+    ///revision of the map the cached paths were found on - see WorldMap::revision
+    pub revision: usize,
+    ///failed searches are cached too - `None` means there is no way. The paths are found with the
+    ///crowds of WorldMap::congestion_revision - the ones found with older crowds are not the
+    ///cheapest anymore, but they are kept until the map changes, as the crowds come and go
+    pub paths: HashMap<(Pos, Pos, usize, usize), Option<Vec<Pos>>>,
+    pub hits: usize,
+    pub misses: usize,
+}
+
+impl Default for PathCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PathCache {
+    ///the cache is dropped as a whole once it grows too big - simpler than tracking what is old
+    pub const MAX_ENTRIES: usize = 4096;
+
+    pub fn new() -> Self {
+        Self {
+            revision: 0,
+            paths: HashMap::new(),
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    pub fn get(
+        &mut self,
+        start: Pos,
+        goal: Pos,
+        revision: usize,
+        congestion_revision: usize,
+    ) -> Option<Option<Vec<Pos>>> {
+        let cached = self.paths.get(&(start, goal, revision, congestion_revision)).cloned();
+        if cached.is_some() {
+            self.hits += 1;
+        } else {
+            self.misses += 1;
+        }
+        cached
+    }
+
+    pub fn insert(
+        &mut self,
+        start: Pos,
+        goal: Pos,
+        revision: usize,
+        congestion_revision: usize,
+        path: Option<Vec<Pos>>,
+    ) {
+        //paths found on older revisions will never be asked for again
+        if revision != self.revision || self.paths.len() >= Self::MAX_ENTRIES {
+            self.paths.clear();
+            self.revision = revision;
+        }
+        self.paths.insert((start, goal, revision, congestion_revision), path);
+    }
+}
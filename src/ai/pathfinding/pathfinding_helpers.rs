@@ -6,43 +6,32 @@ use crate::{
     world::{World, building::Building, world_map::WorldMap},
 };
 
-pub fn closest_shop_mut<'a, F>(
+pub fn closest_shop_mut<F>(
     start: Pos,
-    world: &'a mut World,
+    world: &mut World,
     f: F,
-) -> Option<(&'a mut Building, Vec<Pos>)>
+) -> Option<(&mut Building, Vec<Pos>)>
 where
     F: Fn(&Building) -> bool,
 {
     closest_shop_mut_2(start, &world.map, &mut world.shops, f)
 }
 
-pub fn closest_shop<'a, F>(
+/// Cheapest shop to get to, by the real cost of the tiles on the way - a road around the bog beats
+/// the short way through it. Workers go in through the cheapest of the entrances
+pub fn closest_shop<F>(
     start: Pos,
-    world: &'a World,
+    world: &World,
     f: F,
-) -> Option<(&'a Building, Vec<Pos>)>
+) -> Option<(&Building, Vec<Pos>)>
 where
     F: Fn(&Building) -> bool,
 {
-    let mut stores: Vec<(&Building, Vec<Pos>)> = world
-        .shops
-        .iter()
-        .filter_map(|s| {
-            if f(s) {
-                let path = pathfinding::a_star(&world.map, start, s.building_base.pos);
-                path.map(|path| (s, path))
-            } else {
-                None
-            }
-        })
-        .collect();
-
-    //TODO: compare path's cost, not length
-    stores.sort_by(|s1, s2| s2.1.len().cmp(&s1.1.len())); //note reversed comparison order - this
-    //is for descending sort
+    let candidates: Vec<&Building> = world.shops.iter().filter(|s| f(s)).collect();
+    let targets: Vec<Pos> = candidates.iter().map(|s| s.building_base.pos).collect();
 
-    stores.pop()
+    let (index, path) = pathfinding::dijkstra_closest_building(&world.map, start, &targets)?;
+    Some((candidates[index], path))
 }
 
 ///The variant of this funciton exists, to make invocation less restrictive on borrow rules.
@@ -57,20 +46,9 @@ pub fn closest_shop_mut_2<'a, F>(
 where
     F: Fn(&Building) -> bool,
 {
-    let mut stores: Vec<(&mut Building, Vec<Pos>)> = shops
-        .iter_mut()
-        .filter_map(|s| {
-            if f(s) {
-                let path = pathfinding::a_star(map, start, s.building_base.pos);
-                path.map(|path| (s, path))
-            } else {
-                None
-            }
-        })
-        .collect();
-
-    stores.sort_by(|s1, s2| s2.1.len().cmp(&s1.1.len())); //note reversed comparison order - this
-    //is for descending sort
+    let mut candidates: Vec<&mut Building> = shops.iter_mut().filter(|s| f(s)).collect();
+    let targets: Vec<Pos> = candidates.iter().map(|s| s.building_base.pos).collect();
 
-    stores.pop()
+    let (index, path) = pathfinding::dijkstra_closest_building(map, start, &targets)?;
+    Some((candidates.swap_remove(index), path))
 }
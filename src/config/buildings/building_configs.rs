@@ -1,22 +1,58 @@
 use std::sync::LazyLock;
 
 use crate::{
-    config::{inventory::InventoryItems, receipes::receipes_config},
+    config::{
+        inventory::{InventoryItems, ItemTag},
+        receipes::receipes_config,
+        species::Specialisation,
+    },
     world::building::BuildingBehaviourDiscriminants,
 };
 
-use super::BuildingConfig;
+use super::{BuildingConfig, DecorationConfig, EntranceConfig, HearthConfig, ToolsConfig};
 
 pub static WOODCUTTER: LazyLock<BuildingConfig> = LazyLock::new(|| BuildingConfig {
     name: "Woodcutter's Camp",
     max_workers: 3,
     production_receipes: Vec::new(),
     gathered_resource_types: vec![InventoryItems::Wood],
+    gathering_efficiency: 1.0,
     width: 2,
     height: 2,
     building_behaviour: BuildingBehaviourDiscriminants::Gatherer,
+    decoration: None,
+    specialisations: vec![Specialisation::Woodworking],
+    preserves: Vec::new(),
+    entrances: vec![EntranceConfig::new(1, 2)],
+    hearth: None,
     build_time: 15.0,
     build_materials: vec![(InventoryItems::Wood, 10.0), (InventoryItems::Parts, 2.0)],
+    tools: Some(ToolsConfig {
+        speed_bonus: 1.25,
+        yield_bonus: 1.5,
+        wear_per_second: 1.0 / 60.0,
+        supplying_threshold: 0.5,
+    }),
+});
+
+//gathers a bit of everything, but none of it as fast as a camp made for it would
+pub static FORAGER: LazyLock<BuildingConfig> = LazyLock::new(|| BuildingConfig {
+    name: "Forager's Camp",
+    max_workers: 3,
+    production_receipes: Vec::new(),
+    gathered_resource_types: vec![InventoryItems::Berries, InventoryItems::Mushrooms, InventoryItems::Eggs],
+    gathering_efficiency: 0.75,
+    width: 2,
+    height: 2,
+    building_behaviour: BuildingBehaviourDiscriminants::Gatherer,
+    decoration: None,
+    specialisations: Vec::new(),
+    preserves: Vec::new(),
+    entrances: vec![EntranceConfig::new(1, 2)],
+    hearth: None,
+    build_time: 15.0,
+    build_materials: vec![(InventoryItems::Wood, 8.0), (InventoryItems::Parts, 1.0)],
+    tools: None,
 });
 
 pub static LUMBERMILL: LazyLock<BuildingConfig> = LazyLock::new(|| BuildingConfig {
@@ -28,11 +64,43 @@ pub static LUMBERMILL: LazyLock<BuildingConfig> = LazyLock::new(|| BuildingConfi
         &receipes_config::SCROLLS_1,
     ],
     gathered_resource_types: Vec::new(),
+    gathering_efficiency: 1.0,
     width: 2,
     height: 3,
     building_behaviour: BuildingBehaviourDiscriminants::Producer,
+    decoration: None,
+    specialisations: vec![Specialisation::Woodworking],
+    preserves: Vec::new(),
+    entrances: vec![EntranceConfig::new(1, 3)],
+    hearth: None,
     build_time: 25.0,
     build_materials: vec![(InventoryItems::Bricks, 2.0), (InventoryItems::Fabric, 2.0)],
+    tools: Some(ToolsConfig {
+        speed_bonus: 1.5,
+        yield_bonus: 1.0,
+        wear_per_second: 1.0 / 90.0,
+        supplying_threshold: 0.5,
+    }),
+});
+
+pub static TOOLSHOP: LazyLock<BuildingConfig> = LazyLock::new(|| BuildingConfig {
+    name: "Toolshop",
+    max_workers: 2,
+    production_receipes: vec![&receipes_config::TOOLS_1],
+    gathered_resource_types: Vec::new(),
+    gathering_efficiency: 1.0,
+    width: 2,
+    height: 2,
+    building_behaviour: BuildingBehaviourDiscriminants::Producer,
+    decoration: None,
+    specialisations: Vec::new(),
+    preserves: Vec::new(),
+    entrances: vec![EntranceConfig::new(1, 2)],
+    hearth: None,
+    build_time: 25.0,
+    build_materials: vec![(InventoryItems::Plank, 4.0), (InventoryItems::Bricks, 2.0)],
+    //making tools with tools would be too much
+    tools: None,
 });
 
 pub static MAIN_STORE: LazyLock<BuildingConfig> = LazyLock::new(|| BuildingConfig {
@@ -40,12 +108,19 @@ pub static MAIN_STORE: LazyLock<BuildingConfig> = LazyLock::new(|| BuildingConfi
     max_workers: 0,
     production_receipes: Vec::new(),
     gathered_resource_types: Vec::new(),
+    gathering_efficiency: 1.0,
     width: 4,
     height: 3,
     building_behaviour: BuildingBehaviourDiscriminants::Store,
+    decoration: None,
+    specialisations: Vec::new(),
+    preserves: Vec::new(),
+    entrances: vec![EntranceConfig::new(2, 3), EntranceConfig::new(-1, 1)],
+    hearth: None,
     //this is free!
     build_time: 0.0,
     build_materials: Vec::new(),
+    tools: None,
 });
 
 pub static MAIN_HEARTH: LazyLock<BuildingConfig> = LazyLock::new(|| BuildingConfig {
@@ -53,10 +128,154 @@ pub static MAIN_HEARTH: LazyLock<BuildingConfig> = LazyLock::new(|| BuildingConf
     max_workers: 0,
     production_receipes: Vec::new(),
     gathered_resource_types: Vec::new(),
+    gathering_efficiency: 1.0,
     width: 4,
     height: 4,
     building_behaviour: BuildingBehaviourDiscriminants::Hearth,
+    decoration: None,
+    specialisations: Vec::new(),
+    preserves: Vec::new(),
+    entrances: vec![EntranceConfig::new(2, 4), EntranceConfig::new(1, -1)],
+    hearth: Some(HearthConfig {
+        fuel_burning_time: 20.0,
+        fuel_supplying_threshold: 10.0,
+    }),
     //this is free!
     build_time: 0.0,
     build_materials: Vec::new(),
+    tools: None,
+});
+
+//Granary and Cellar are just stores, that keep the food from rotting
+pub static GRANARY: LazyLock<BuildingConfig> = LazyLock::new(|| BuildingConfig {
+    name: "Granary",
+    max_workers: 0,
+    production_receipes: Vec::new(),
+    gathered_resource_types: Vec::new(),
+    gathering_efficiency: 1.0,
+    width: 3,
+    height: 2,
+    building_behaviour: BuildingBehaviourDiscriminants::Store,
+    decoration: None,
+    specialisations: Vec::new(),
+    preserves: vec![ItemTag::BasicFood],
+    entrances: vec![EntranceConfig::new(1, 2)],
+    hearth: None,
+    build_time: 20.0,
+    build_materials: vec![(InventoryItems::Plank, 5.0), (InventoryItems::Fabric, 2.0)],
+    tools: None,
 });
+
+pub static CELLAR: LazyLock<BuildingConfig> = LazyLock::new(|| BuildingConfig {
+    name: "Cellar",
+    max_workers: 0,
+    production_receipes: Vec::new(),
+    gathered_resource_types: Vec::new(),
+    gathering_efficiency: 1.0,
+    width: 2,
+    height: 2,
+    building_behaviour: BuildingBehaviourDiscriminants::Store,
+    decoration: None,
+    specialisations: Vec::new(),
+    preserves: vec![ItemTag::Food],
+    entrances: vec![EntranceConfig::new(1, 2)],
+    hearth: None,
+    build_time: 25.0,
+    build_materials: vec![(InventoryItems::Bricks, 4.0), (InventoryItems::Plank, 2.0)],
+    tools: None,
+});
+
+pub static SHELTER: LazyLock<BuildingConfig> = LazyLock::new(|| BuildingConfig {
+    name: "Shelter",
+    max_workers: 0,
+    production_receipes: Vec::new(),
+    gathered_resource_types: Vec::new(),
+    gathering_efficiency: 1.0,
+    width: 2,
+    height: 2,
+    building_behaviour: BuildingBehaviourDiscriminants::House,
+    decoration: None,
+    specialisations: Vec::new(),
+    preserves: Vec::new(),
+    entrances: vec![EntranceConfig::new(1, 2)],
+    hearth: None,
+    build_time: 10.0,
+    build_materials: vec![(InventoryItems::Wood, 4.0)],
+    tools: None,
+});
+
+pub static STATUE: LazyLock<BuildingConfig> = LazyLock::new(|| BuildingConfig {
+    name: "Statue",
+    max_workers: 0,
+    production_receipes: Vec::new(),
+    gathered_resource_types: Vec::new(),
+    gathering_efficiency: 1.0,
+    width: 1,
+    height: 1,
+    building_behaviour: BuildingBehaviourDiscriminants::Decoration,
+    build_time: 10.0,
+    build_materials: vec![(InventoryItems::Stone, 4.0)],
+    decoration: Some(DecorationConfig {
+        radius: 3,
+        resolve_bonus: 1.5,
+        buff_duration: 90.0,
+    }),
+    specialisations: Vec::new(),
+    preserves: Vec::new(),
+    entrances: Vec::new(),
+    hearth: None,
+    tools: None,
+});
+
+pub static PARK: LazyLock<BuildingConfig> = LazyLock::new(|| BuildingConfig {
+    name: "Park",
+    max_workers: 0,
+    production_receipes: Vec::new(),
+    gathered_resource_types: Vec::new(),
+    gathering_efficiency: 1.0,
+    width: 3,
+    height: 3,
+    building_behaviour: BuildingBehaviourDiscriminants::Decoration,
+    build_time: 20.0,
+    build_materials: vec![(InventoryItems::Wood, 6.0), (InventoryItems::Herbs, 4.0)],
+    decoration: Some(DecorationConfig {
+        radius: 4,
+        resolve_bonus: 2.5,
+        buff_duration: 60.0,
+    }),
+    specialisations: Vec::new(),
+    preserves: Vec::new(),
+    entrances: Vec::new(),
+    hearth: None,
+    tools: None,
+});
+
+pub static HUMAN_HOUSE: LazyLock<BuildingConfig> = LazyLock::new(|| house("Human House"));
+
+pub static BEAVER_HOUSE: LazyLock<BuildingConfig> = LazyLock::new(|| house("Beaver House"));
+
+pub static LIZARD_HOUSE: LazyLock<BuildingConfig> = LazyLock::new(|| house("Lizard House"));
+
+pub static HARPY_HOUSE: LazyLock<BuildingConfig> = LazyLock::new(|| house("Harpy House"));
+
+//the species houses only differ in who prefers to live in them - see SpeciesConfig::preferred_housing
+fn house(name: &'static str) -> BuildingConfig {
+    BuildingConfig {
+        name,
+        max_workers: 0,
+        production_receipes: Vec::new(),
+        gathered_resource_types: Vec::new(),
+        gathering_efficiency: 1.0,
+        width: 2,
+        height: 2,
+        building_behaviour: BuildingBehaviourDiscriminants::House,
+        build_time: 15.0,
+        build_materials: vec![(InventoryItems::Plank, 4.0), (InventoryItems::Fabric, 2.0)],
+        decoration: None,
+        specialisations: Vec::new(),
+        preserves: Vec::new(),
+        entrances: vec![EntranceConfig::new(1, 2)],
+        hearth: None,
+        tools: None,
+    }
+}
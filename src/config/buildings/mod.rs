@@ -1,6 +1,9 @@
 use strum_macros::Display;
 
-use super::inventory::InventoryItems;
+use super::{
+    inventory::{InventoryItems, ItemTag},
+    species::Specialisation,
+};
 use crate::world::building::BuildingBehaviourDiscriminants;
 
 use super::receipes::Receipe;
@@ -13,9 +16,64 @@ pub struct BuildingConfig {
     pub build_materials: Vec<(InventoryItems, f32)>,
     pub production_receipes: Vec<&'static Receipe>,
     pub gathered_resource_types: Vec<InventoryItems>,
+    ///multiplier of the gathering speed of the node - 1.0 for the buildings that do not gather
+    pub gathering_efficiency: f32,
     pub width: u8,
     pub height: u8,
     pub building_behaviour: BuildingBehaviourDiscriminants,
+    pub decoration: Option<DecorationConfig>,
+    pub specialisations: Vec<Specialisation>,
+    pub hearth: Option<HearthConfig>,
+    ///buildings that can use tools - None for the ones that can not
+    pub tools: Option<ToolsConfig>,
+    ///perishable items with any of these tags do not spoil in this building
+    pub preserves: Vec<ItemTag>,
+    ///no entrances - workers walk up to the building from any side, like to a statue
+    pub entrances: Vec<EntranceConfig>,
+}
+
+/// The tile in front of the door, relative to the top-left corner of the building. It lies just
+/// outside of the footprint - e.g. (1, height) is right below the second tile of the bottom row
+#[derive(Clone, Copy)]
+pub struct EntranceConfig {
+    pub x: i8,
+    pub y: i8,
+}
+
+impl EntranceConfig {
+    pub const fn new(
+        x: i8,
+        y: i8,
+    ) -> Self {
+        Self { x, y }
+    }
+}
+
+pub struct HearthConfig {
+    ///how long a single unit of fuel keeps the hearth burning
+    pub fuel_burning_time: f32,
+    ///below this amount of fuel in stock, the hearth tender goes for more
+    pub fuel_supplying_threshold: f32,
+}
+
+/// Tools make the work faster (and the gathering more plentiful) as long as there are any in the
+/// building. They wear out while the workers use them, and have to be brought from the stores again
+pub struct ToolsConfig {
+    pub speed_bonus: f32,
+    ///multiplier of the gathered amount - 1.0 for the producers
+    pub yield_bonus: f32,
+    ///tools used up by a single worker every second of the actual work
+    pub wear_per_second: f32,
+    ///below this amount of tools in stock, a worker goes for more
+    pub supplying_threshold: f32,
+}
+
+/// Decorations lift the spirits of the workers walking past them. The bonus to resolve fades away
+/// over the duration of the buff.
+pub struct DecorationConfig {
+    pub radius: u8,
+    pub resolve_bonus: f32,
+    pub buff_duration: f32,
 }
 
 #[derive(PartialEq, Eq, Clone, Copy, Display)]
@@ -34,6 +92,17 @@ pub enum Buildings {
     Hearth,
     Store,
 
+    //housing
+    Shelter,
+    HumanHouse,
+    BeaverHouse,
+    LizardHouse,
+    HarpyHouse,
+
+    //decorations
+    Statue,
+    Park,
+
     //Producer - materials
     CrudeWorkstation,
     MakeshiftPost,
@@ -92,7 +161,6 @@ pub enum Buildings {
     // +farms
     // +fertile soil buildings
     // +mine
-    // +housing
 
     // Tavern,
     // Temple,
@@ -114,7 +182,7 @@ impl Buildings {
             Buildings::Herbalist => todo!(),
             Buildings::Stonecutter => todo!(),
             Buildings::Harvester => todo!(),
-            Buildings::Forager => todo!(),
+            Buildings::Forager => &building_configs::FORAGER,
             Buildings::Trapper => todo!(),
             Buildings::MainHearth => &building_configs::MAIN_HEARTH,
             Buildings::MainStore => &building_configs::MAIN_STORE,
@@ -127,6 +195,13 @@ impl Buildings {
             Buildings::Butcher => todo!(),
             Buildings::Hearth => todo!(),
             Buildings::Store => todo!(),
+            Buildings::Shelter => &building_configs::SHELTER,
+            Buildings::HumanHouse => &building_configs::HUMAN_HOUSE,
+            Buildings::BeaverHouse => &building_configs::BEAVER_HOUSE,
+            Buildings::LizardHouse => &building_configs::LIZARD_HOUSE,
+            Buildings::HarpyHouse => &building_configs::HARPY_HOUSE,
+            Buildings::Statue => &building_configs::STATUE,
+            Buildings::Park => &building_configs::PARK,
             Buildings::Workshop => todo!(),
             Buildings::Cooperage => todo!(),
             Buildings::Carpenter => todo!(),
@@ -139,7 +214,7 @@ impl Buildings {
             Buildings::Provisioner => todo!(),
             Buildings::Smelter => todo!(),
             Buildings::Smithy => todo!(),
-            Buildings::Toolshop => todo!(),
+            Buildings::Toolshop => &building_configs::TOOLSHOP,
             Buildings::Artisan => todo!(),
             Buildings::AlchemistHut => todo!(),
             Buildings::Teahouse => todo!(),
@@ -155,9 +230,9 @@ impl Buildings {
             Buildings::Cookhouse => todo!(),
             Buildings::Smokehouse => todo!(),
             Buildings::Beanery => todo!(),
-            Buildings::Cellar => todo!(),
+            Buildings::Cellar => &building_configs::CELLAR,
             Buildings::Cannery => todo!(),
-            Buildings::Granary => todo!(),
+            Buildings::Granary => &building_configs::GRANARY,
             Buildings::Grill => todo!(),
             Buildings::Rainmill => todo!(),
             Buildings::Pantry => todo!(),
@@ -0,0 +1,133 @@
+use std::{collections::HashMap, sync::LazyLock};
+
+use strum::IntoEnumIterator;
+
+use super::{InventoryItemConfig, InventoryItems, ItemCategory, ItemTag};
+
+//there are too many items to write a static for each of them, like I do for buildings - the
+//configs are generated from the table below instead. The match is exhaustive on purpose, so that
+//adding a new item without a config does not compile
+
+fn base_config(item: InventoryItems) -> (&'static str, ItemCategory, f32) {
+    use InventoryItems as I;
+    use ItemCategory as C;
+
+    match item {
+        I::Wood => ("Wood", C::BuildingMaterial, 1.0),
+        I::Resin => ("Resin", C::BuildingMaterial, 0.5),
+        I::Stone => ("Stone", C::BuildingMaterial, 2.0),
+        I::Clay => ("Clay", C::BuildingMaterial, 1.5),
+        I::PlantFiber => ("Plant Fiber", C::BuildingMaterial, 0.5),
+        I::Reed => ("Reed", C::BuildingMaterial, 0.5),
+        I::Leather => ("Leather", C::BuildingMaterial, 0.5),
+        I::CopperOre => ("Copper Ore", C::BuildingMaterial, 2.0),
+        I::BoneMarrow => ("Bone Marrow", C::Fuel, 1.0),
+        I::Grain => ("Grain", C::BuildingMaterial, 0.5),
+        I::Herbs => ("Herbs", C::BuildingMaterial, 0.5),
+
+        I::Roots => ("Roots", C::BasicFood, 0.5),
+        I::Vegetables => ("Vegetables", C::BasicFood, 0.5),
+        I::Berries => ("Berries", C::BasicFood, 0.5),
+        I::Mushrooms => ("Mushrooms", C::BasicFood, 0.5),
+        I::Eggs => ("Eggs", C::BasicFood, 0.5),
+        I::Meat => ("Meat", C::BasicFood, 0.5),
+        I::Insects => ("Insects", C::BasicFood, 0.5),
+
+        I::Plank => ("Planks", C::BuildingMaterial, 1.0),
+        I::Bricks => ("Bricks", C::BuildingMaterial, 2.0),
+        I::Fabric => ("Fabric", C::BuildingMaterial, 0.5),
+        I::Pottery => ("Pottery", C::TradeGood, 1.0),
+        I::Oil => ("Oil", C::Fuel, 1.0),
+        I::Coal => ("Coal", C::Fuel, 1.0),
+        I::Waterskins => ("Waterskins", C::TradeGood, 0.5),
+        I::CopperBars => ("Copper Bars", C::BuildingMaterial, 2.0),
+        I::CrystalizedDew => ("Crystalized Dew", C::BuildingMaterial, 0.5),
+        I::Barrels => ("Barrels", C::TradeGood, 1.5),
+        I::Flour => ("Flour", C::BuildingMaterial, 0.5),
+
+        I::PackOfProvisions => ("Pack of Provisions", C::Pack, 2.0),
+        I::PackOfCrops => ("Pack of Crops", C::Pack, 2.0),
+        I::PackOfBuildingMaterials => ("Pack of Building Materials", C::Pack, 2.0),
+        I::PackOfTradeGoods => ("Pack of Trade Goods", C::Pack, 2.0),
+        I::PackOfLuxuryGoods => ("Pack of Luxury Goods", C::Pack, 2.0),
+
+        I::Skewers => ("Skewers", C::ComplexFood, 0.5),
+        I::Jerky => ("Jerky", C::ComplexFood, 0.5),
+        I::PickledGoods => ("Pickled Goods", C::ComplexFood, 0.5),
+        I::Paste => ("Paste", C::ComplexFood, 0.5),
+        I::Biscuits => ("Biscuits", C::ComplexFood, 0.5),
+        I::Pie => ("Pie", C::ComplexFood, 0.5),
+        I::Porridge => ("Porridge", C::ComplexFood, 0.5),
+
+        I::Coats => ("Coats", C::TradeGood, 1.0),
+        I::Boots => ("Boots", C::TradeGood, 1.0),
+        I::Scrolls => ("Scrolls", C::TradeGood, 0.5),
+        I::Dye => ("Dye", C::TradeGood, 0.5),
+        I::Incense => ("Incense", C::TradeGood, 0.5),
+        I::Wine => ("Wine", C::TradeGood, 1.0),
+        I::Ale => ("Ale", C::TradeGood, 1.0),
+        I::Tea => ("Tea", C::TradeGood, 0.5),
+        I::TrainingGear => ("Training Gear", C::TradeGood, 1.5),
+
+        I::Tools => ("Tools", C::Special, 1.0),
+        I::Parts => ("Parts", C::Special, 1.0),
+        I::WildfireEssence => ("Wildfire Essence", C::Special, 0.5),
+        I::Amber => ("Amber", C::Special, 0.1),
+    }
+}
+
+/// Tags coming from the category are added automatically - here go only the extra ones
+fn extra_tags(item: InventoryItems) -> Vec<ItemTag> {
+    match item {
+        //wood is first and foremost a building material, but it burns just fine
+        InventoryItems::Wood => vec![ItemTag::Fuel],
+        _ => Vec::new(),
+    }
+}
+
+/// In seconds - see InventoryItemConfig::shelf_life. Jerky, pickles and biscuits are made to last,
+/// that's the whole point of them
+fn shelf_life(item: InventoryItems) -> Option<f32> {
+    use InventoryItems as I;
+
+    match item {
+        I::Meat => Some(300.0),
+        I::Insects => Some(400.0),
+        I::Eggs => Some(600.0),
+        I::Berries => Some(900.0),
+        I::Mushrooms => Some(900.0),
+        I::Vegetables => Some(2400.0),
+        I::Roots => Some(3000.0),
+
+        I::Skewers => Some(600.0),
+        I::Pie => Some(900.0),
+        I::Porridge => Some(900.0),
+        I::Paste => Some(1200.0),
+        _ => None,
+    }
+}
+
+pub static INVENTORY_ITEM_CONFIGS: LazyLock<HashMap<InventoryItems, InventoryItemConfig>> = LazyLock::new(|| {
+    InventoryItems::iter()
+        .map(|item| {
+            let (name, category, weight) = base_config(item);
+
+            let mut tags = category.tags();
+            tags.append(&mut extra_tags(item));
+
+            let shelf_life = shelf_life(item);
+            if shelf_life.is_some() {
+                tags.push(ItemTag::Perishable);
+            }
+
+            let config = InventoryItemConfig {
+                name,
+                category,
+                weight,
+                tags,
+                shelf_life,
+            };
+            (item, config)
+        })
+        .collect()
+});
@@ -1,12 +1,70 @@
 use crate::data_helpers::to_string::ToString;
-use strum_macros::Display;
+use strum::IntoEnumIterator;
+use strum_macros::{Display, EnumIter};
+
+pub mod inventory_configs;
 
-#[derive(Eq, PartialEq, Hash)]
 pub struct InventoryItemConfig {
-    pub name: String,
-    pub is_fuel: bool,
-    pub is_basic_food: bool,
-    pub is_pack: bool,
+    ///display name
+    pub name: &'static str,
+    pub category: ItemCategory,
+    ///weight of a single unit
+    pub weight: f32,
+    pub tags: Vec<ItemTag>,
+    ///perishable items lose one unit every `shelf_life` seconds per unit in the stack, unless
+    ///kept in a building that preserves them
+    pub shelf_life: Option<f32>,
+}
+
+/// Every item belongs to exactly one category
+#[derive(PartialEq, Eq, Clone, Copy, Display)]
+pub enum ItemCategory {
+    BuildingMaterial,
+    BasicFood,
+    ComplexFood,
+    Fuel,
+    Pack,
+    TradeGood,
+    Special,
+}
+
+/// Items can have any number of tags. Code deciding what to do with an item should ask for a tag,
+/// rather than list the items by hand
+#[derive(PartialEq, Eq, Clone, Copy, Display)]
+pub enum ItemTag {
+    BuildingMaterial,
+    Food,
+    BasicFood,
+    ComplexFood,
+    Fuel,
+    Pack,
+    TradeGood,
+    Tradeable,
+    ///set automatically for items with a shelf life
+    Perishable,
+}
+
+impl ItemCategory {
+    pub fn tags(&self) -> Vec<ItemTag> {
+        match self {
+            ItemCategory::BuildingMaterial => vec![ItemTag::BuildingMaterial],
+            ItemCategory::BasicFood => vec![ItemTag::Food, ItemTag::BasicFood],
+            ItemCategory::ComplexFood => vec![ItemTag::Food, ItemTag::ComplexFood],
+            ItemCategory::Fuel => vec![ItemTag::Fuel],
+            ItemCategory::Pack => vec![ItemTag::Pack, ItemTag::Tradeable],
+            ItemCategory::TradeGood => vec![ItemTag::TradeGood, ItemTag::Tradeable],
+            ItemCategory::Special => Vec::new(),
+        }
+    }
+}
+
+impl InventoryItemConfig {
+    pub fn has_tag(
+        &self,
+        tag: ItemTag,
+    ) -> bool {
+        self.tags.contains(&tag)
+    }
 }
 
 impl ToString for (InventoryItems, f32) {
@@ -21,7 +79,7 @@ impl ToString for InventoryItems {
     }
 }
 
-#[derive(Hash, PartialEq, Eq, Copy, Clone, Display)]
+#[derive(Hash, PartialEq, Eq, Copy, Clone, Debug, Display, EnumIter)]
 pub enum InventoryItems {
     //basic materials
     Wood,
@@ -93,3 +151,21 @@ pub enum InventoryItems {
     WildfireEssence,
     Amber,
 }
+
+impl InventoryItems {
+    pub fn config(&self) -> &'static InventoryItemConfig {
+        &inventory_configs::INVENTORY_ITEM_CONFIGS[self]
+    }
+
+    pub fn has_tag(
+        &self,
+        tag: ItemTag,
+    ) -> bool {
+        self.config().has_tag(tag)
+    }
+
+    /// All the items with the given tag, always in the same order
+    pub fn with_tag(tag: ItemTag) -> Vec<InventoryItems> {
+        InventoryItems::iter().filter(|i| i.has_tag(tag)).collect()
+    }
+}
@@ -2,3 +2,4 @@ pub mod buildings;
 pub mod inventory;
 pub mod receipes;
 pub mod resources;
+pub mod species;
@@ -38,3 +38,13 @@ pub static SCROLLS_1: LazyLock<Receipe> = LazyLock::new(|| Receipe {
     time_requirement: 84.0,
     receipe_level: ReceipeLevel::Basic,
 });
+
+pub static TOOLS_1: LazyLock<Receipe> = LazyLock::new(|| Receipe {
+    input: vec![
+        vec![(InventoryItems::Plank, 2.0), (InventoryItems::Wood, 4.0)],
+        vec![(InventoryItems::CopperBars, 2.0), (InventoryItems::CrystalizedDew, 2.0)],
+    ],
+    output: vec![(InventoryItems::Tools, 1.0)],
+    time_requirement: 40.0,
+    receipe_level: ReceipeLevel::Basic,
+});
@@ -1,7 +1,10 @@
 use super::inventory::InventoryItems;
 pub mod resource_nodes_config;
 
+#[derive(PartialEq, Eq, Clone, Copy)]
 pub enum ResourceNodes {
+    Tree,
+    BerryBush,
     DewberryBush,
     DewberryBushLarge,
     DrizzlewingNest,
@@ -64,14 +67,21 @@ pub enum NodeSize {
 pub struct ResourceNodeConfig {
     pub name: &'static str,
     pub primary_resource: InventoryItems,
+    ///odds of finding the bonus resource with each gather
     pub bonus_resources: Vec<(InventoryItems, f32)>,
     pub total_charges: f32,
+    ///time a worker spends gathering a single charge
+    pub gathering_time: f32,
+    ///amount of the primary resource from a single charge
+    pub yield_per_gather: f32,
     pub size: NodeSize,
 }
 
 impl ResourceNodes {
     pub fn get_config(&self) -> &'static ResourceNodeConfig {
         match self {
+            ResourceNodes::Tree => &resource_nodes_config::TREE,
+            ResourceNodes::BerryBush => &resource_nodes_config::BERRY_BUSH,
             ResourceNodes::DewberryBush => &resource_nodes_config::DEWBERRY_BUSH,
             ResourceNodes::DewberryBushLarge => &resource_nodes_config::DEWBERRY_BUSH_LARGE,
             ResourceNodes::DrizzlewingNest => &resource_nodes_config::DRIZZLEWING_NEST,
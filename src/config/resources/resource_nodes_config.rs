@@ -4,11 +4,33 @@ use crate::config::inventory::InventoryItems;
 
 use super::{NodeSize, ResourceNodeConfig};
 
+pub static TREE: LazyLock<ResourceNodeConfig> = LazyLock::new(|| ResourceNodeConfig {
+    name: "Tree",
+    primary_resource: InventoryItems::Wood,
+    bonus_resources: vec![(InventoryItems::Resin, 0.2)],
+    total_charges: 10.0,
+    gathering_time: 10.0,
+    yield_per_gather: 1.0,
+    size: NodeSize::Small,
+});
+
+pub static BERRY_BUSH: LazyLock<ResourceNodeConfig> = LazyLock::new(|| ResourceNodeConfig {
+    name: "Berry Bush",
+    primary_resource: InventoryItems::Berries,
+    bonus_resources: vec![(InventoryItems::Herbs, 0.2)],
+    total_charges: 50.0,
+    gathering_time: 10.0,
+    yield_per_gather: 1.0,
+    size: NodeSize::Small,
+});
+
 pub static DEWBERRY_BUSH: LazyLock<ResourceNodeConfig> = LazyLock::new(|| ResourceNodeConfig {
     name: "Dewberry Bush",
     primary_resource: InventoryItems::Berries,
     bonus_resources: vec![(InventoryItems::Berries, 0.2)],
     total_charges: 15.0,
+    gathering_time: 8.0,
+    yield_per_gather: 1.0,
     size: NodeSize::Small,
 });
 
@@ -17,6 +39,8 @@ pub static DEWBERRY_BUSH_LARGE: LazyLock<ResourceNodeConfig> = LazyLock::new(||
     primary_resource: InventoryItems::Berries,
     bonus_resources: vec![(InventoryItems::Berries, 0.8)],
     total_charges: 70.0,
+    gathering_time: 8.0,
+    yield_per_gather: 1.0,
     size: NodeSize::Large,
 });
 
@@ -25,6 +49,8 @@ pub static CLAY_DEPOSIT: LazyLock<ResourceNodeConfig> = LazyLock::new(|| Resourc
     primary_resource: InventoryItems::Clay,
     bonus_resources: vec![(InventoryItems::CopperOre, 0.5), (InventoryItems::Roots, 0.2)],
     total_charges: 20.0,
+    gathering_time: 12.0,
+    yield_per_gather: 1.0,
     size: NodeSize::Small,
 });
 
@@ -33,6 +59,8 @@ pub static CLAY_DEPOSIT_LARGE: LazyLock<ResourceNodeConfig> = LazyLock::new(|| R
     primary_resource: InventoryItems::Clay,
     bonus_resources: vec![(InventoryItems::CopperOre, 0.75), (InventoryItems::Roots, 0.3)],
     total_charges: 60.0,
+    gathering_time: 12.0,
+    yield_per_gather: 1.0,
     size: NodeSize::Large,
 });
 
@@ -41,6 +69,8 @@ pub static BLEEDING_TOOTH: LazyLock<ResourceNodeConfig> = LazyLock::new(|| Resou
     primary_resource: InventoryItems::Mushrooms,
     bonus_resources: vec![(InventoryItems::Insects, 0.2)],
     total_charges: 20.0,
+    gathering_time: 8.0,
+    yield_per_gather: 1.0,
     size: NodeSize::Large,
 });
 
@@ -49,6 +79,8 @@ pub static BLEEDING_TOOTH_LARGE: LazyLock<ResourceNodeConfig> = LazyLock::new(||
     primary_resource: InventoryItems::Mushrooms,
     bonus_resources: vec![(InventoryItems::Insects, 0.4)],
     total_charges: 70.0,
+    gathering_time: 8.0,
+    yield_per_gather: 1.0,
     size: NodeSize::Small,
 });
 
@@ -57,6 +89,8 @@ pub static DRIZZLEWING_NEST: LazyLock<ResourceNodeConfig> = LazyLock::new(|| Res
     primary_resource: InventoryItems::Eggs,
     bonus_resources: vec![(InventoryItems::Meat, 0.2)],
     total_charges: 15.0,
+    gathering_time: 8.0,
+    yield_per_gather: 1.0,
     size: NodeSize::Large,
 });
 
@@ -65,6 +99,8 @@ pub static DRIZZLEWING_NEST_LARGE: LazyLock<ResourceNodeConfig> = LazyLock::new(
     primary_resource: InventoryItems::Eggs,
     bonus_resources: vec![(InventoryItems::Meat, 0.4)],
     total_charges: 70.0,
+    gathering_time: 8.0,
+    yield_per_gather: 1.0,
     size: NodeSize::Small,
 });
 
@@ -73,6 +109,8 @@ pub static FLAX_FIELD: LazyLock<ResourceNodeConfig> = LazyLock::new(|| ResourceN
     primary_resource: InventoryItems::PlantFiber,
     bonus_resources: vec![(InventoryItems::Clay, 0.25), (InventoryItems::Insects, 0.2)],
     total_charges: 20.0,
+    gathering_time: 10.0,
+    yield_per_gather: 1.0,
     size: NodeSize::Large,
 });
 
@@ -81,6 +119,8 @@ pub static FLAX_FIELD_LARGE: LazyLock<ResourceNodeConfig> = LazyLock::new(|| Res
     primary_resource: InventoryItems::PlantFiber,
     bonus_resources: vec![(InventoryItems::Clay, 0.5), (InventoryItems::Insects, 0.3)],
     total_charges: 60.0,
+    gathering_time: 10.0,
+    yield_per_gather: 1.0,
     size: NodeSize::Small,
 });
 
@@ -89,6 +129,8 @@ pub static GRASSCAP_MUSHROOMS: LazyLock<ResourceNodeConfig> = LazyLock::new(|| R
     primary_resource: InventoryItems::Mushrooms,
     bonus_resources: vec![(InventoryItems::Insects, 0.2)],
     total_charges: 20.0,
+    gathering_time: 8.0,
+    yield_per_gather: 1.0,
     size: NodeSize::Large,
 });
 
@@ -97,5 +139,7 @@ pub static GRASSCAP_MUSHROOMS_LARGE: LazyLock<ResourceNodeConfig> = LazyLock::ne
     primary_resource: InventoryItems::Mushrooms,
     bonus_resources: vec![(InventoryItems::Insects, 0.4)],
     total_charges: 70.0,
+    gathering_time: 8.0,
+    yield_per_gather: 1.0,
     size: NodeSize::Small,
 });
@@ -0,0 +1,59 @@
+use strum_macros::Display;
+
+use super::{buildings::Buildings, inventory::InventoryItems};
+
+pub mod species_configs;
+
+pub struct SpeciesConfig {
+    pub name: &'static str,
+    pub preferred_food: Vec<InventoryItems>,
+    pub preferred_housing: Buildings,
+    ///0.0 - feels the full penalty when the hearth goes out, 1.0 - does not care at all
+    pub hearth_resilience: f32,
+    pub specialisations: Vec<Specialisation>,
+}
+
+#[derive(PartialEq, Eq, Clone, Copy, Display, Debug)]
+pub enum Species {
+    Human,
+    Beaver,
+    Lizard,
+    Harpy,
+}
+
+/// Buildings are tagged with specialisations - workers of the species sharing the specialisation
+/// work faster in them
+#[derive(Hash, PartialEq, Eq, Clone, Copy, Display)]
+pub enum Specialisation {
+    Farming,
+    Woodworking,
+    Brewing,
+    Meat,
+    Cooking,
+    Cloth,
+    Alchemy,
+}
+
+impl Species {
+    pub const SPECIALISATION_SPEED_BONUS: f32 = 1.3;
+
+    pub fn get_config(&self) -> &'static SpeciesConfig {
+        match self {
+            Species::Human => &species_configs::HUMAN,
+            Species::Beaver => &species_configs::BEAVER,
+            Species::Lizard => &species_configs::LIZARD,
+            Species::Harpy => &species_configs::HARPY,
+        }
+    }
+
+    pub fn is_specialised_in(
+        &self,
+        building: Buildings,
+    ) -> bool {
+        let building_specialisations = &building.get_data().specialisations;
+        self.get_config()
+            .specialisations
+            .iter()
+            .any(|s| building_specialisations.contains(s))
+    }
+}
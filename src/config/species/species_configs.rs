@@ -0,0 +1,42 @@
+use std::sync::LazyLock;
+
+use crate::config::{buildings::Buildings, inventory::InventoryItems};
+
+use super::{Specialisation, SpeciesConfig};
+
+pub static HUMAN: LazyLock<SpeciesConfig> = LazyLock::new(|| SpeciesConfig {
+    name: "Human",
+    preferred_food: vec![
+        InventoryItems::Vegetables,
+        InventoryItems::Biscuits,
+        InventoryItems::Pie,
+    ],
+    preferred_housing: Buildings::HumanHouse,
+    hearth_resilience: 0.25,
+    specialisations: vec![Specialisation::Farming, Specialisation::Cooking],
+});
+
+pub static BEAVER: LazyLock<SpeciesConfig> = LazyLock::new(|| SpeciesConfig {
+    name: "Beaver",
+    preferred_food: vec![InventoryItems::Berries, InventoryItems::Biscuits, InventoryItems::Pie],
+    preferred_housing: Buildings::BeaverHouse,
+    hearth_resilience: 0.0,
+    specialisations: vec![Specialisation::Woodworking, Specialisation::Brewing],
+});
+
+pub static LIZARD: LazyLock<SpeciesConfig> = LazyLock::new(|| SpeciesConfig {
+    name: "Lizard",
+    preferred_food: vec![InventoryItems::Meat, InventoryItems::Insects, InventoryItems::Skewers],
+    preferred_housing: Buildings::LizardHouse,
+    //cold blooded - the hearth matters a lot
+    hearth_resilience: 0.0,
+    specialisations: vec![Specialisation::Meat, Specialisation::Alchemy],
+});
+
+pub static HARPY: LazyLock<SpeciesConfig> = LazyLock::new(|| SpeciesConfig {
+    name: "Harpy",
+    preferred_food: vec![InventoryItems::Insects, InventoryItems::Jerky, InventoryItems::Paste],
+    preferred_housing: Buildings::HarpyHouse,
+    hearth_resilience: 0.75,
+    specialisations: vec![Specialisation::Cloth, Specialisation::Alchemy],
+});
@@ -21,11 +21,7 @@ impl<T, F> LinkdListExtensions<T, F> for LinkedList<T> {
             return None;
         }
 
-        let item_pos = if let Some(ind) = self.iter().position(f) {
-            ind
-        } else {
-            return None;
-        };
+        let item_pos = self.iter().position(f)?;
 
         if item_pos == self.len() - 1 {
             return self.pop_back();
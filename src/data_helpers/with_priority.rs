@@ -4,13 +4,13 @@ pub struct WithPriority<T> {
 }
 impl<T> WithPriority<T> {
     pub fn default(payload: T) -> Self {
-        Self {
-            payload,
-            priority: 0.0,
-        }
+        Self { payload, priority: 0.0 }
     }
 
-    pub fn new(payload: T, priority: f32) -> Self {
+    pub fn new(
+        payload: T,
+        priority: f32,
+    ) -> Self {
         Self { payload, priority }
     }
 
@@ -19,19 +19,30 @@ impl<T> WithPriority<T> {
     }
 }
 impl<T> PartialEq for WithPriority<T> {
-    fn eq(&self, other: &Self) -> bool {
+    fn eq(
+        &self,
+        other: &Self,
+    ) -> bool {
         self.priority == other.priority
     }
 }
 
+//NaN priorities stay unordered for the comparison operators - only the heap needs a total order
+#[allow(clippy::non_canonical_partial_ord_impl)]
 impl<T> PartialOrd for WithPriority<T> {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+    fn partial_cmp(
+        &self,
+        other: &Self,
+    ) -> Option<std::cmp::Ordering> {
         self.priority.partial_cmp(&other.priority)
     }
 }
 
 impl<T> Ord for WithPriority<T> {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+    fn cmp(
+        &self,
+        other: &Self,
+    ) -> std::cmp::Ordering {
         self.priority.total_cmp(&other.priority)
     }
 }
@@ -1,20 +1,19 @@
 use std::io::Write;
-use std::{collections::LinkedList, sync::atomic::Ordering};
+use std::sync::atomic::Ordering;
+
+use log::info;
 
 use rusty_rain::config::buildings::Buildings;
 use rusty_rain::config::inventory::InventoryItems;
+use rusty_rain::data_helpers::to_string::ToString;
 use rusty_rain::world::building::Building;
-use rusty_rain::world::worker::Idle;
-use rusty_rain::world::worker::worker_states::WorkerWithAction;
+use rusty_rain::world::events::WorldEvent;
 use rusty_rain::world_interaction::commands::{self, BuildMethod};
 use rusty_rain::{
     FRAME_NUM,
     math::Pos,
     world::{
         World,
-        actions::BasicAction,
-        inventory::Inventory,
-        worker::Worker,
         world_map::{TileType, WorldMap, resources::ResourceType},
     },
 };
@@ -37,6 +36,20 @@ fn main() {
     while seconds < 60.0 {
         world.next_tick(DELTA);
         seconds += DELTA;
+
+        //nobody else takes the events - they would pile up for as long as the game runs
+        for event in world.take_events() {
+            report(event);
+        }
+    }
+}
+
+fn report(event: WorldEvent) {
+    match event {
+        WorldEvent::NewcomersArrived { names, hearth_pos } => {
+            info!("{} arrived at the hearth at {}.", names.join(", "), hearth_pos)
+        }
+        WorldEvent::ItemsSpoiled { pos, items } => info!("{} went bad at {}.", items.to_string(), pos),
     }
 }
 
@@ -78,31 +91,16 @@ pub fn new_test_world(
     //plant tree
     *map.get_mut(&Pos::new(3, 7)) = ResourceType::tile_tree();
 
-    World {
-        map,
-        shops: LinkedList::new(),
-        frame_number: 0,
-        build_zones: LinkedList::new(),
-    }
+    World::new(map)
 }
 
 pub fn configure_world(world: &mut World) {
-    let maybe_hearth = commands::build(
-        world,
-        Buildings::MainHearth,
-        Pos::new(world.map.width() / 2, world.map.height() / 2),
-        BuildMethod::SpawnExisting,
-    );
+    let hearth_pos = Pos::new(world.map.width() / 2, world.map.height() / 2);
+    let hearth_tender = world.spawn_worker("Hearth Tender".to_string(), hearth_pos);
+    let maybe_hearth = commands::build(world, Buildings::MainHearth, hearth_pos, BuildMethod::SpawnExisting);
 
     if let Some(Building { building_base, .. }) = maybe_hearth {
-        building_base.workers.push_front(Worker::Idle(WorkerWithAction::<Idle> {
-            name: "Hearth Tender".to_string(),
-            inventory: Inventory::limited(5.0),
-            pos: building_base.pos,
-            break_progress: BasicAction::new(120.0),
-            exhausted: false,
-            action_data: Idle(),
-        }))
+        building_base.workers.push_front(hearth_tender)
     };
 
     let maybe_store = commands::build(world, Buildings::MainStore, Pos::new(4, 3), BuildMethod::SpawnExisting);
@@ -110,34 +108,19 @@ pub fn configure_world(world: &mut World) {
         building_base.output.add(&InventoryItems::Wood, 40.0);
     }
 
-    let maybe_woodcutter = commands::build(
-        world,
-        Buildings::Woodcutter,
-        Pos::new(11, 5),
-        BuildMethod::SpawnExisting,
-    );
+    let woodcutter_pos = Pos::new(11, 5);
+    let woodcutter = world.spawn_worker("Woodchuck Chuck".to_string(), woodcutter_pos);
+    let maybe_woodcutter = commands::build(world, Buildings::Woodcutter, woodcutter_pos, BuildMethod::SpawnExisting);
 
     if let Some(Building { building_base, .. }) = maybe_woodcutter {
-        building_base.workers.push_back(Worker::Idle(WorkerWithAction::<Idle> {
-            name: "Woodchuck Chuck".to_string(),
-            inventory: Inventory::limited(5.0),
-            pos: building_base.pos,
-            break_progress: BasicAction::new(120.0),
-            exhausted: false,
-            action_data: Idle(),
-        }));
+        building_base.workers.push_back(woodcutter);
     };
 
-    let maybe_lumbermill = commands::build(world, Buildings::Lumbermill, Pos::new(5, 9), BuildMethod::SpawnExisting);
+    let lumbermill_pos = Pos::new(5, 9);
+    let lumbermill_worker = world.spawn_worker("Jane".to_string(), lumbermill_pos);
+    let maybe_lumbermill = commands::build(world, Buildings::Lumbermill, lumbermill_pos, BuildMethod::SpawnExisting);
 
     if let Some(Building { building_base, .. }) = maybe_lumbermill {
-        building_base.workers.push_back(Worker::Idle(WorkerWithAction::<Idle> {
-            name: "Jane".to_string(),
-            inventory: Inventory::limited(5.0),
-            pos: building_base.pos,
-            break_progress: BasicAction::new(120.0),
-            exhausted: false,
-            action_data: Idle(),
-        }));
+        building_base.workers.push_back(lumbermill_worker);
     };
 }
@@ -1,10 +1,10 @@
-use log::{error, info};
+use log::info;
 
 use crate::{
+    ai::pathfinding::flow_field,
     math::Pos,
     world::{
         World,
-        building::build_zone::BuildZone,
         world_map::{TileType, WorldMap},
     },
 };
@@ -13,7 +13,8 @@ use super::{ActionResult, TransitAction, TransitActionResult};
 
 pub struct BuildingAction {
     pub state: BuildingActionInternalState,
-    pub build_zone: Option<BuildZone>,
+    ///the zone itself stays in the world - other workers may be building it too
+    pub build_zone_pos: Pos,
 }
 
 pub enum BuildingActionInternalState {
@@ -30,11 +31,11 @@ impl BuildingAction {
     pub fn new(
         path: Vec<Pos>,
         map: &WorldMap,
-        build_zone: BuildZone,
+        build_zone_pos: Pos,
     ) -> Self {
         Self {
             state: BuildingActionInternalState::Going(TransitAction::new(path, map)),
-            build_zone: Some(build_zone),
+            build_zone_pos,
         }
     }
 
@@ -42,24 +43,25 @@ impl BuildingAction {
         &mut self,
         world: &mut World,
         delta: f32,
+        work_speed: f32,
+        walked: &mut Vec<Pos>,
     ) -> BuildingActionResult {
         match &mut self.state {
             BuildingActionInternalState::Going(transit_action) => {
                 let result = transit_action.continue_action(delta);
+                walked.extend_from_slice(transit_action.take_traversed_tiles());
 
                 match result {
-                    TransitActionResult::InProgress(pos) => {
-                        return BuildingActionResult::InProgress(pos);
-                    }
+                    TransitActionResult::InProgress(pos) => BuildingActionResult::InProgress(pos),
                     TransitActionResult::Completed(pos) => {
                         self.state = BuildingActionInternalState::Building;
 
-                        return BuildingActionResult::InProgress(pos);
+                        BuildingActionResult::InProgress(pos)
                     }
                 }
             }
             BuildingActionInternalState::Building => {
-                return progres_build_action(world, &mut self.build_zone, delta);
+                progres_build_action(world, &self.build_zone_pos, delta * work_speed)
             }
         }
     }
@@ -67,44 +69,49 @@ impl BuildingAction {
 
 fn progres_build_action(
     world: &mut World,
-
-    maybe_build_zone: &mut Option<BuildZone>,
+    build_zone_pos: &Pos,
     delta: f32,
 ) -> BuildingActionResult {
-    let build_zone = if let Some(bz) = maybe_build_zone {
+    let build_zone = if let Some(bz) = world.build_zone_mut(build_zone_pos) {
         bz
     } else {
-        error!(
-            "Building was already built, but the action was not properly completed by the invoking party. The action was invoked in the completed state."
-        );
+        //someone else has just finished it
         return BuildingActionResult::Completed;
     };
 
+    //every builder adds their own work - the more of them, the sooner it stands
     let action = build_zone.progress.continue_action(delta);
 
     if let ActionResult::InProgress = action {
-        return BuildingActionResult::InProgress(build_zone.building.building_base.pos);
+        return BuildingActionResult::InProgress(*build_zone_pos);
     }
 
-    let data_taken = maybe_build_zone.take().unwrap(); //safe unwrap, I already checked if the data
-    //is there
+    let data_taken = world.take_build_zone(build_zone_pos).unwrap(); //safe unwrap, I just found it
+
+    //materials are used up once the building stands, not when they are delivered
+    let materials = data_taken
+        .materials_delivered
+        .iter()
+        .map(|(item, amount)| (*item, *amount));
+    world.statistics.record_consumed(materials);
 
     let shop = data_taken.building;
 
     //TODO: this function assumes the check was already made and the build_zone footprint matches
     //the building's footprint - verify
 
-    let config = shop.building_base.building.get_data();
+    let (width, height) = shop.building_base.footprint();
 
-    world
-        .map
-        .build(&shop.building_base.pos, config.width, config.height, || {
-            TileType::Structure(shop.building_base.building)
-        });
+    world.map.build(&shop.building_base.pos, width, height, || {
+        TileType::Structure(shop.building_base.building)
+    });
+    if flow_field::wants_flow_field(shop.building_base.building) {
+        world.map.add_flow_field(shop.building_base.pos);
+    }
 
     world.shops.push_back(shop);
 
     info!("Build action completed successfully.");
 
-    return BuildingActionResult::Completed;
+    BuildingActionResult::Completed
 }
@@ -26,7 +26,7 @@ impl GatheringAction {
         path: Vec<Pos>,
         map: &mut WorldMap,
     ) -> Self {
-        let pos = path.first().unwrap().clone();
+        let pos = *path.first().unwrap();
         let final_pos = path.last().unwrap();
 
         let resource = map.get_mut(final_pos);
@@ -51,23 +51,25 @@ impl GatheringAction {
         &mut self,
         map: &mut WorldMap,
         delta: f32,
+        work_speed: f32,
+        walked: &mut Vec<Pos>,
     ) -> GatheringActionResult {
         match &mut self.state {
             GatheringActionInternalState::Going(transit_action) => {
                 let result = transit_action.continue_action(delta);
+                walked.extend_from_slice(transit_action.take_traversed_tiles());
 
                 match result {
                     TransitActionResult::InProgress(pos) => self.pos = pos,
                     TransitActionResult::Completed(pos) => {
-                        //TODO: what should be the gathering time??
-                        self.state = GatheringActionInternalState::Gathering(BasicAction::new(10.0));
                         self.pos = pos;
 
                         //arrived at the destination - check if resource still there :P
                         //TODO: proposition above solves this!
-
                         let resource = map.get(&self.pos);
-                        if let TileType::Resource(_, _, _) = resource {
+                        if let TileType::Resource(_, charge, _) = resource {
+                            let gathering_time = charge.config().gathering_time;
+                            self.state = GatheringActionInternalState::Gathering(BasicAction::new(gathering_time));
                         } else {
                             return GatheringActionResult::Completed(vec![]);
                         }
@@ -75,7 +77,7 @@ impl GatheringAction {
                 }
             }
             GatheringActionInternalState::Gathering(basic_action) => {
-                let result = basic_action.continue_action(delta);
+                let result = basic_action.continue_action(delta * work_speed);
                 if let ActionResult::Completed = result {
                     let resource = map.get_mut(&self.pos);
 
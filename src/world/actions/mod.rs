@@ -1,6 +1,6 @@
 use crate::math::Pos;
 
-use super::world_map::WorldMap;
+use super::{traffic::Traffic, world_map::WorldMap};
 
 pub mod building_action;
 pub mod gathering_action;
@@ -23,6 +23,10 @@ pub struct TransitAction {
     pub path_cost: Vec<f32>,
     pub progress: f32,
     pub requirement: f32,
+    ///number of tiles from the beginning of the path, that were already reported as traversed
+    pub traversed: usize,
+    ///number of tiles from the beginning of the path, that were already checked for other workers
+    pub congestion_checked: usize,
 }
 
 pub enum TransitActionResult {
@@ -37,13 +41,16 @@ impl TransitAction {
         path: Vec<Pos>,
         map: &WorldMap,
     ) -> Self {
-        let path_cost: Vec<f32> = path.iter().map(|p| map.get(p).cost()).collect();
+        //diagonal steps cost more - see WorldMap::step_cost
+        let path_cost: Vec<f32> = map.path_to_cost(&path);
         let requirement = path_cost.iter().sum::<f32>();
         Self {
             path,
             path_cost,
             progress: 0.0,
             requirement,
+            traversed: 0,
+            congestion_checked: 0,
         }
     }
 
@@ -54,20 +61,76 @@ impl TransitAction {
         self.progress += delta;
 
         if self.progress >= self.requirement {
-            return TransitActionResult::Completed(self.path.last().unwrap().clone());
+            return TransitActionResult::Completed(*self.path.last().unwrap());
         }
         TransitActionResult::InProgress(self.current_pos())
     }
 
-    fn current_pos(&self) -> Pos {
+    pub fn current_pos(&self) -> Pos {
+        self.path[self.current_index()]
+    }
+
+    pub fn current_index(&self) -> usize {
         let mut acc = 0.0;
         for (i, cost) in self.path_cost.iter().enumerate() {
             acc += cost;
             if acc >= self.progress {
-                return self.path.get(i).unwrap_or(self.path.last().unwrap()).clone();
+                return i;
             }
         }
-        self.path.last().unwrap().clone()
+        self.path.len() - 1
+    }
+
+    /// Has any of the changed tiles on the rest of the way become impassable? The last tile is the
+    /// destination - buildings are never traversible, so it does not count
+    pub fn is_blocked(
+        &self,
+        map: &WorldMap,
+        changed_tiles: &[Pos],
+    ) -> bool {
+        let from = self.current_index() + 1;
+        let to = self.path.len() - 1;
+        if from >= to {
+            return false;
+        }
+
+        self.path[from..to]
+            .iter()
+            .any(|p| changed_tiles.contains(p) && !map.get(p).is_traversible())
+    }
+
+    /// Makes the tile the worker has just stepped on more expensive, if there are other workers on
+    /// it. Every tile is checked only once. Returns the tile and the delay
+    pub fn apply_congestion(
+        &mut self,
+        traffic: &Traffic,
+    ) -> Option<(Pos, f32)> {
+        let index = self.current_index();
+        if index < self.congestion_checked {
+            return None;
+        }
+        self.congestion_checked = index + 1;
+
+        let pos = self.path[index];
+        let delay = traffic.penalty(&pos, self.path_cost[index]);
+        if delay <= 0.0 {
+            return None;
+        }
+        self.path_cost[index] += delay;
+        self.requirement += delay;
+        Some((pos, delay))
+    }
+
+    /// Tiles the worker has stepped on since the last time this function was called
+    pub fn take_traversed_tiles(&mut self) -> &[Pos] {
+        let from = self.traversed;
+        let to = self.current_index() + 1;
+        if from >= to {
+            return &[];
+        }
+
+        self.traversed = to;
+        &self.path[from..to]
     }
 }
 
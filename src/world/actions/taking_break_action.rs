@@ -19,12 +19,14 @@ pub enum TakingBreakActionResult {
 
 impl TakingBreakAction {
     pub const BREAK_TIME: f32 = 30.0;
+    ///it is hard to get some rest, when the hearth is out
+    pub const HEARTH_OUT_BREAK_SPEED: f32 = 0.75;
 
     pub fn new(
         path: Vec<Pos>,
         map: &WorldMap,
     ) -> Self {
-        let pos = path.first().unwrap().clone();
+        let pos = *path.first().unwrap();
 
         Self {
             state: TakingBreakActionInternalState::Going(TransitAction::new(path, map)),
@@ -35,10 +37,13 @@ impl TakingBreakAction {
     pub fn continue_action(
         &mut self,
         delta: f32,
+        break_speed: f32,
+        walked: &mut Vec<Pos>,
     ) -> TakingBreakActionResult {
         match &mut self.state {
             TakingBreakActionInternalState::Going(transit_action) => {
                 let result = transit_action.continue_action(delta);
+                walked.extend_from_slice(transit_action.take_traversed_tiles());
 
                 match result {
                     TransitActionResult::InProgress(pos) => self.pos = pos,
@@ -51,7 +56,7 @@ impl TakingBreakAction {
                 }
             }
             TakingBreakActionInternalState::TakingBreak(basic_action) => {
-                let result = basic_action.continue_action(delta);
+                let result = basic_action.continue_action(delta * break_speed);
                 if let ActionResult::Completed = result {
                     return TakingBreakActionResult::Completed;
                 }
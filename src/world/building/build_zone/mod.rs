@@ -1,24 +1,46 @@
-use crate::world::{actions::BasicAction, inventory::Inventory};
+use crate::{
+    config::inventory::InventoryItems,
+    math::Pos,
+    world::{World, actions::BasicAction, inventory::Inventory},
+};
 
 use super::Building;
 
+/// Build zones stay in the world until they are built - workers only know where they are, so
+/// several of them can bring the materials and build at the same time
 pub struct BuildZone {
     pub progress: BasicAction,
     pub materials_delivered: Inventory,
+    ///materials someone is already carrying here - nobody else needs to bring them
+    pub materials_incoming: Inventory,
+    ///workers building (or on their way to build)
+    pub builders: u8,
+    ///workers on their way with the materials
+    pub suppliers: u8,
     pub building: Building,
 }
 
 impl BuildZone {
+    ///more would just get in each other's way
+    pub const MAX_WORKERS: u8 = 3;
+
     pub fn new(shop_type: Building) -> Self {
         //FIXME: get build data
         let data = shop_type.building_base.building.get_data();
         Self {
             progress: BasicAction::new(data.build_time),
             materials_delivered: Inventory::new(),
+            materials_incoming: Inventory::new(),
+            builders: 0,
+            suppliers: 0,
             building: shop_type,
         }
     }
 
+    pub fn pos(&self) -> Pos {
+        self.building.building_base.pos
+    }
+
     pub fn is_delivery_complete(&self) -> bool {
         for (key, item) in &self.building.building_base.building.get_data().build_materials {
             if self.materials_delivered.get(key) < *item {
@@ -27,4 +49,69 @@ impl BuildZone {
         }
         true
     }
+
+    /// Whatever the zone still needs, that nobody is bringing yet
+    pub fn missing_materials(&self) -> Vec<(InventoryItems, f32)> {
+        self.building
+            .building_base
+            .building
+            .get_data()
+            .build_materials
+            .iter()
+            .map(|(item, amount)| {
+                let missing = amount - self.materials_delivered.get(item) - self.materials_incoming.get(item);
+                (*item, missing)
+            })
+            .filter(|(_, amount)| *amount > 0.0)
+            .collect()
+    }
+
+    /// Number of workers that can still join - whether to bring the materials or to build
+    pub fn free_slots(&self) -> u8 {
+        BuildZone::MAX_WORKERS.saturating_sub(self.builders + self.suppliers)
+    }
+
+    /// The worker is not bringing the items after all
+    pub fn cancel_incoming<T>(
+        &mut self,
+        items: T,
+    ) where
+        T: IntoIterator<Item = (InventoryItems, f32)>,
+    {
+        for (item, amount) in items {
+            let incoming = self.materials_incoming.get(&item);
+            self.materials_incoming.remove(&item, amount.min(incoming));
+        }
+    }
+}
+
+impl World {
+    pub fn build_zone_mut(
+        &mut self,
+        pos: &Pos,
+    ) -> Option<&mut BuildZone> {
+        self.build_zones.iter_mut().find(|bz| bz.pos() == *pos)
+    }
+
+    /// Nothing could be done with the zone right now - it goes to the back of the list, so that it
+    /// does not block the others
+    pub(in crate::world) fn postpone_build_zone(
+        &mut self,
+        pos: &Pos,
+    ) {
+        if let Some(build_zone) = self.take_build_zone(pos) {
+            self.build_zones.push_back(build_zone);
+        }
+    }
+
+    pub(in crate::world) fn take_build_zone(
+        &mut self,
+        pos: &Pos,
+    ) -> Option<BuildZone> {
+        let index = self.build_zones.iter().position(|bz| bz.pos() == *pos)?;
+        let mut rest = self.build_zones.split_off(index);
+        let build_zone = rest.pop_front();
+        self.build_zones.append(&mut rest);
+        build_zone
+    }
 }
@@ -5,7 +5,8 @@ use crate::{
     config::inventory::InventoryItems,
     world::{
         World,
-        building::BuildingBase,
+        building::{BuildingBase, tools},
+        jobs::{Job, JobBoard, JobKind},
         worker::{Worker, worker_state_transitions::WorkerActionResult},
         world_map::TileType,
     },
@@ -13,6 +14,7 @@ use crate::{
 
 use super::shared;
 
+#[derive(Default)]
 pub struct GathererBehaviour {
     pub storing_all: bool,
 }
@@ -24,14 +26,6 @@ impl GathererBehaviour {
     pub const MAX_WORKERS: u8 = 2;
 }
 
-impl Default for GathererBehaviour {
-    fn default() -> Self {
-        Self {
-            storing_all: Default::default(),
-        }
-    }
-}
-
 impl GathererBehaviour {
     pub fn process(
         &mut self,
@@ -43,7 +37,15 @@ impl GathererBehaviour {
         let shop_id = &format!("{}", shop_base.building);
         for _ in 0..shop_base.workers.len() {
             let worker = shop_base.workers.pop_front().unwrap();
-            let (mut worker, result) = worker.continue_action(shop_base.pos, delta, world, false);
+            let (mut worker, result) = worker.continue_action(
+                shop_base.pos,
+                shop_base.building,
+                &mut shop_base.tools,
+                delta,
+                world,
+                false,
+            );
+            let mut took_job = false;
 
             match result {
                 WorkerActionResult::InProgress => {
@@ -55,26 +57,67 @@ impl GathererBehaviour {
                 }
 
                 WorkerActionResult::BroughtToShop(inventory) => {
-                    shop_base.output.add_range(inventory);
+                    let gathered = shop_base.tools.unpack(inventory);
+                    shop_base.output.add_range(gathered);
                 }
 
                 WorkerActionResult::Idle => {
-                    if shop_base.output.is_full() || self.storing_all {
-                        worker = shared::store_command(worker, world, &mut shop_base.output, shop_id);
-
-                        //once started storing - store everything
-                        if shop_base.output.total_items() <= 0.0 {
-                            self.storing_all = false;
-                        } else {
-                            self.storing_all = true;
-                        }
-                    } else {
-                        let resource_types = &shop_base.building.get_data().gathered_resource_types;
-                        worker = gather_command(worker, world, resource_types, shop_id);
-                    }
+                    worker = self.take_job(shop_base, worker, world, shop_id);
+                    took_job = true;
                 }
             }
             shop_base.workers.push_back(worker);
+
+            //only once the worker is back in the list - otherwise nobody would be seen bringing
+            //the tools
+            if took_job {
+                self.post_jobs(shop_base, &mut world.jobs);
+            }
+        }
+    }
+
+    pub fn post_jobs(
+        &self,
+        shop_base: &BuildingBase,
+        jobs: &mut JobBoard,
+    ) {
+        jobs.withdraw(shop_base.pos);
+        tools::post_tools_job(shop_base, jobs);
+        //reserved items are not going to the store - someone is coming for them
+        let has_something_to_store = shop_base.output.total_available() > 0.0;
+        if has_something_to_store && (shop_base.output.is_full() || self.storing_all) {
+            jobs.post(JobKind::Haul, shop_base.pos, Vec::new(), Job::UNLIMITED);
+        } else if !shop_base.output.is_full() {
+            let resource_types = shop_base.building.get_data().gathered_resource_types.clone();
+            jobs.post(JobKind::Gather, shop_base.pos, resource_types, Job::UNLIMITED);
+        }
+    }
+
+    fn take_job(
+        &mut self,
+        shop_base: &mut BuildingBase,
+        worker: Worker,
+        world: &mut World,
+        shop_id: &String,
+    ) -> Worker {
+        let (worker, job) = shared::take_job(worker, shop_base.pos, world, shop_id);
+        let job = if let Some(job) = job {
+            job
+        } else {
+            return worker;
+        };
+
+        match job.kind {
+            JobKind::Haul => {
+                let worker = shared::store_command(worker, world, &mut shop_base.output, shop_id);
+
+                //once started storing - store everything
+                self.storing_all = shop_base.output.total_available() > 0.0;
+                worker
+            }
+            JobKind::Gather => gather_command(worker, world, &job.items, shop_id),
+            JobKind::Build | JobKind::Collect => shared::open_job_command(worker, job, world),
+            _ => worker, //never posted by a gatherer
         }
     }
 }
@@ -82,7 +125,7 @@ impl GathererBehaviour {
 fn gather_command(
     worker: Worker,
     world: &mut World,
-    resource_items: &Vec<InventoryItems>,
+    resource_items: &[InventoryItems],
     shop_id: &String,
 ) -> Worker {
     let idle_worker = if let Worker::Idle(idle_worker) = worker {
@@ -92,13 +135,8 @@ fn gather_command(
     };
 
     let maybe_path = pathfinding::dijkstra_closest(&world.map, idle_worker.pos, |t| {
-        if let TileType::Resource(_, items, being_cut) = t {
-            let main_item = &items.per_gather.first().unwrap().0;
-            if resource_items.contains(main_item) && !being_cut {
-                true
-            } else {
-                false
-            }
+        if let TileType::Resource(_, charge, being_cut) = t {
+            resource_items.contains(&charge.config().primary_resource) && !being_cut
         } else {
             false
         }
@@ -111,5 +149,5 @@ fn gather_command(
         return Worker::Idle(idle_worker); //remain idle
     };
 
-    return idle_worker.to_gathering(path, &mut world.map);
+    idle_worker.to_gathering(path, &mut world.map)
 }
@@ -3,12 +3,17 @@ use std::collections::LinkedList;
 use log::info;
 
 use crate::{
-    config::inventory::InventoryItems,
+    config::{
+        buildings::{Buildings, HearthConfig},
+        inventory::{InventoryItems, ItemTag},
+    },
     world::{
         World,
         actions::{ActionResult, BasicAction},
         building::BuildingBase,
         inventory::Inventory,
+        jobs::{Job, JobBoard, JobKind},
+        statistics::Statistics,
         worker::{Worker, worker_state_transitions::WorkerActionResult},
     },
 };
@@ -33,9 +38,25 @@ impl HearthBehaviour {
 
     pub const MAX_WORKERS: u8 = 1;
 
-    pub const MATERIAL_SUPPLYING_THRESHOLD: f32 = 10.0;
+    pub fn get_config(building: Buildings) -> &'static HearthConfig {
+        building
+            .get_data()
+            .hearth
+            .as_ref()
+            .expect("Buildings with hearth behaviour must have a hearth config.")
+    }
+
+    /// Total amount of fuel of all kinds in stock
+    pub fn fuel(&self) -> f32 {
+        InventoryItems::with_tag(ItemTag::Fuel)
+            .iter()
+            .map(|f| self.input.get(f))
+            .sum()
+    }
 
-    pub const WOOD_BURNING_RATE: f32 = 20.0;
+    pub fn is_burning(&self) -> bool {
+        matches!(self.action, HearthAction::Burning(_))
+    }
 }
 
 impl Default for HearthBehaviour {
@@ -61,14 +82,24 @@ impl HearthBehaviour {
             shop_base.workers.push_back(worker);
         }
 
+        let config = HearthBehaviour::get_config(shop_base.building);
+        let has_worker = !shop_base.workers.is_empty();
         let maybe_new_action = match &mut self.action {
-            HearthAction::Burning(burning) => continue_burning(burning, delta),
-            HearthAction::Idle => process_idle(&mut self.input, !shop_base.workers.is_empty()),
+            HearthAction::Burning(burning) => continue_burning(
+                burning,
+                &mut self.input,
+                has_worker,
+                config,
+                &mut world.statistics,
+                delta,
+            ),
+            HearthAction::Idle => process_idle(&mut self.input, has_worker, config, &mut world.statistics),
         };
 
         for _ in 0..self.unassigned_workers.len() {
             let mut worker = self.unassigned_workers.pop_front().unwrap();
-            worker = worker.process_unassigned_worker(shop_base.pos, world, delta);
+            worker =
+                worker.process_unassigned_worker(shop_base.pos, shop_base.building, &mut shop_base.tools, world, delta);
 
             self.unassigned_workers.push_back(worker);
         }
@@ -86,7 +117,14 @@ impl HearthBehaviour {
         delta: f32,
     ) -> Worker {
         let shop_id = &"Hearth".to_string();
-        let (mut worker, result) = worker.continue_action(shop_base.pos, delta, world, true);
+        let (mut worker, result) = worker.continue_action(
+            shop_base.pos,
+            shop_base.building,
+            &mut shop_base.tools,
+            delta,
+            world,
+            true,
+        );
 
         match result {
             WorkerActionResult::InProgress => {
@@ -102,27 +140,52 @@ impl HearthBehaviour {
             }
 
             WorkerActionResult::Idle => {
-                //TODO: use any fuel
-                //TODO: to function so that I can return early nicely
-                if self.input.get(&InventoryItems::Wood) > HearthBehaviour::MATERIAL_SUPPLYING_THRESHOLD {
-                    //no need to fetch fuel - stock full
-                } else {
-                    worker = shared::supply_command(worker, shop_base.pos, world, &vec![InventoryItems::Wood], shop_id);
+                //the fire keeper does not leave the hearth for anything else
+                if let Some(job) = world
+                    .jobs
+                    .take(|j| j.is_for(shop_base.pos) && j.kind == JobKind::RefuelHearth)
+                {
+                    worker = shared::supply_command(worker, shop_base.pos, world, &job.items, shop_id);
                 }
+                self.post_jobs(shop_base, &mut world.jobs);
             }
         }
         worker
     }
+
+    pub fn post_jobs(
+        &self,
+        shop_base: &BuildingBase,
+        jobs: &mut JobBoard,
+    ) {
+        jobs.withdraw(shop_base.pos);
+        let config = HearthBehaviour::get_config(shop_base.building);
+        if self.fuel() <= config.fuel_supplying_threshold {
+            jobs.post(
+                JobKind::RefuelHearth,
+                shop_base.pos,
+                InventoryItems::with_tag(ItemTag::Fuel),
+                Job::UNLIMITED,
+            );
+        }
+        //no need to fetch fuel otherwise - stock full
+    }
 }
 
 fn continue_burning(
     action: &mut BasicAction,
+    inventory: &mut Inventory,
+    has_worker: bool,
+    config: &HearthConfig,
+    statistics: &mut Statistics,
     delta: f32,
 ) -> Option<HearthAction> {
     let result = action.continue_action(delta);
 
     if let ActionResult::Completed = result {
-        return Some(HearthAction::Idle);
+        //throw in the next piece of fuel right away - otherwise the hearth would go out for a
+        //tick every time
+        return process_idle(inventory, has_worker, config, statistics).or(Some(HearthAction::Idle));
     }
 
     None
@@ -131,20 +194,27 @@ fn continue_burning(
 fn process_idle(
     inventory: &mut Inventory,
     has_worker: bool,
-    //delta: f32,
+    config: &HearthConfig,
+    statistics: &mut Statistics,
 ) -> Option<HearthAction> {
-    let wood = inventory.get(&InventoryItems::Wood);
-
-    if wood > 1.0 && has_worker {
-        inventory.remove(&InventoryItems::Wood, 1.0);
-
-        let burning_action = BasicAction::new(HearthBehaviour::WOOD_BURNING_RATE);
-        info!("Hearth has started burning, remaining fuel: {}", wood - 1.0);
-
-        return Some(HearthAction::Burning(burning_action));
-    };
-    None
+    if !has_worker {
+        return None;
+    }
 
-    //TODO: bad things should happen if the hearth is not burning
-    //for now nothing happens
+    //fuels are always checked in the same order - wood first
+    let fuel = InventoryItems::with_tag(ItemTag::Fuel)
+        .into_iter()
+        .find(|f| inventory.get(f) >= 1.0)?;
+    inventory.remove(&fuel, 1.0);
+    statistics.record_consumed([(fuel, 1.0)]);
+
+    let burning_action = BasicAction::new(config.fuel_burning_time);
+    info!(
+        "Hearth has started burning {}, remaining: {}",
+        fuel,
+        inventory.get(&fuel)
+    );
+
+    //bad things happen when the hearth is not burning - see Settlement::update_hearth_status
+    Some(HearthAction::Burning(burning_action))
 }
@@ -9,8 +9,9 @@ use crate::{
     },
     world::{
         World,
-        building::BuildingBase,
+        building::{BuildingBase, tools},
         inventory::Inventory,
+        jobs::{Job, JobBoard, JobKind},
         worker::{Worker, worker_state_transitions::WorkerActionResult},
     },
 };
@@ -23,6 +24,7 @@ pub struct ProducerBehaviour {
     pub internal_state: InternalProducerState,
 }
 
+#[derive(Clone, Copy, PartialEq)]
 pub enum InternalProducerState {
     Supplying,
     Producing,
@@ -50,7 +52,15 @@ impl ProducerBehaviour {
         for _ in 0..shop_base.workers.len() {
             let worker = shop_base.workers.pop_front().unwrap();
 
-            let (mut worker, result) = worker.continue_action(shop_base.pos, delta, world, false);
+            let (mut worker, result) = worker.continue_action(
+                shop_base.pos,
+                shop_base.building,
+                &mut shop_base.tools,
+                delta,
+                world,
+                false,
+            );
+            let mut took_job = false;
 
             match result {
                 WorkerActionResult::InProgress => {
@@ -58,34 +68,111 @@ impl ProducerBehaviour {
                 }
 
                 WorkerActionResult::BroughtToShop(inventory) => {
-                    self.input.add_range(inventory);
+                    let materials = shop_base.tools.unpack(inventory);
+                    self.input.add_range(materials);
                 }
 
                 WorkerActionResult::ProductionComplete(receipe) => {
+                    world.statistics.record_produced(receipe.output.iter().copied());
                     shop_base.output.add_range(receipe.output);
                 }
 
                 WorkerActionResult::Idle => {
+                    self.update_state(shop_base);
                     worker = handle_idle(self, shop_base, worker, world, shop_id);
+                    took_job = true;
                 }
             }
             shop_base.workers.push_back(worker);
+
+            //only once the worker is back in the list - otherwise nobody would be seen bringing
+            //the tools
+            if took_job {
+                self.post_jobs(shop_base, &mut world.jobs);
+            }
+        }
+    }
+
+    /// Posting only looks at the state the building is about to be in - it is switched once one of
+    /// the workers actually takes a job, see update_state
+    pub fn post_jobs(
+        &self,
+        shop_base: &BuildingBase,
+        jobs: &mut JobBoard,
+    ) {
+        jobs.withdraw(shop_base.pos);
+        tools::post_tools_job(shop_base, jobs);
+        match self.next_state(shop_base) {
+            InternalProducerState::Supplying => {
+                let materials_to_supply = get_materials_to_supply(shop_base, self);
+                jobs.post(JobKind::Supply, shop_base.pos, materials_to_supply, Job::UNLIMITED);
+            }
+            InternalProducerState::Producing => {
+                jobs.post(JobKind::Produce, shop_base.pos, Vec::new(), Job::UNLIMITED);
+            }
+            InternalProducerState::Storing => {
+                jobs.post(JobKind::Haul, shop_base.pos, Vec::new(), Job::UNLIMITED);
+            }
         }
     }
+
+    /// Storing until the output is empty, then supplying until the input is full (or there is
+    /// nothing more to bring), then producing until the materials run out
+    fn next_state(
+        &self,
+        shop_base: &BuildingBase,
+    ) -> InternalProducerState {
+        if shop_base.output.is_full() {
+            return InternalProducerState::Storing;
+        }
+
+        let mut state = self.internal_state;
+        //this will list an array of materials that worker can bring to the store. At some point,
+        //this list will be empty - the shop has ALL the necessary materials, in many variants. Then
+        //I want to start producing
+        if state == InternalProducerState::Supplying && get_materials_to_supply(shop_base, self).is_empty() {
+            state = InternalProducerState::Producing;
+        }
+        //not enough materials for anything - store and then supply
+        if state == InternalProducerState::Producing && !has_enough_materials(shop_base, self) {
+            state = InternalProducerState::Storing;
+        }
+        state
+    }
+
+    fn update_state(
+        &mut self,
+        shop_base: &BuildingBase,
+    ) {
+        let state = self.next_state(shop_base);
+        if self.internal_state == InternalProducerState::Supplying && state == InternalProducerState::Producing {
+            info!("{} has all the necessary materials!", shop_base.building);
+        }
+        self.internal_state = state;
+    }
 }
 
 fn handle_idle(
     pb: &mut ProducerBehaviour,
     shop_base: &mut BuildingBase,
-    mut worker: Worker,
+    worker: Worker,
     world: &mut World,
     shop_id: &String,
 ) -> Worker {
-    if shop_base.output.is_full() || matches!(pb.internal_state, InternalProducerState::Storing) {
-        pb.internal_state = InternalProducerState::Storing;
+    let (mut worker, job) = shared::take_job(worker, shop_base.pos, world, shop_id);
+    let job = if let Some(job) = job {
+        job
+    } else {
+        return worker;
+    };
 
+    if job.kind.is_open() {
+        return shared::open_job_command(worker, job, world);
+    }
+
+    if let JobKind::Haul = job.kind {
         worker = shared::store_command(worker, world, &mut shop_base.output, shop_id);
-        if shop_base.output.is_empty() {
+        if shop_base.output.total_available() <= 0.0 {
             info!(
                 "{} - delivery completed - starting to store new materials.",
                 shop_base.building
@@ -95,24 +182,8 @@ fn handle_idle(
         return worker;
     }
 
-    if let InternalProducerState::Supplying = pb.internal_state {
-        //this will list an array of materials that worker can bring to the store. At some point,
-        //this list will be empty, or there will be no materials in the world - then
-        //worker will not transition to the supplying state, but remain idle.
-        //If materials to supply is empty, then I will want to start producing, because the shop
-        //has ALL the necessary materials - in many variants.
-        //If the shop could still take some materials, but the worker has not transitioned to the
-        //supplying state - it means there are no materials in the world. Then I need to check if
-        //the shop has enough to start producing - and then start producing
-
-        let materials_to_supply = get_materials_to_supply(shop_base, pb);
-        if materials_to_supply.is_empty() {
-            info!("{} has all the necessary materials!", shop_base.building);
-            pb.internal_state = InternalProducerState::Producing;
-            return worker;
-        }
-
-        worker = shared::supply_command(worker, shop_base.pos, world, &materials_to_supply, shop_id);
+    if let JobKind::Supply = job.kind {
+        worker = shared::supply_command(worker, shop_base.pos, world, &job.items, shop_id);
 
         if let Worker::Idle(_) = worker {
             //worker has not transitioned to the supplying state - presumably there are no more
@@ -130,13 +201,7 @@ fn handle_idle(
         return worker;
     }
 
-    if let InternalProducerState::Producing = pb.internal_state {
-        if !has_enough_materials(shop_base, pb) {
-            //not enough materials for anything - store and then supply
-            pb.internal_state = InternalProducerState::Storing;
-            return worker;
-        }
-
+    if let JobKind::Produce = job.kind {
         //pick receipe to make - do not worry if not enough materials for that particular one -
         //will cycle in upcomming frames and start producing something else
         let receipe_count = shop_base.building.get_data().production_receipes.len();
@@ -164,11 +229,12 @@ fn handle_idle(
         for (item, amount) in &produced_receipe.input {
             pb.input.remove(item, *amount);
         }
+        world.statistics.record_consumed(produced_receipe.input.iter().copied());
 
         return idle_worker.to_producing(produced_receipe);
     }
 
-    return worker;
+    worker //never posted by a producer
 }
 
 fn has_enough_materials(
@@ -190,8 +256,8 @@ fn has_enough_materials(
 }
 
 fn get_materials_to_supply(
-    shop_base: &mut BuildingBase,
-    pb: &mut ProducerBehaviour,
+    shop_base: &BuildingBase,
+    pb: &ProducerBehaviour,
 ) -> Vec<InventoryItems> {
     let mut ret = HashSet::<InventoryItems>::new();
     let receipes = &shop_base.building.get_data().production_receipes;
@@ -6,9 +6,53 @@ use crate::{
     config::inventory::InventoryItems,
     data_helpers::to_string::ToString,
     math::Pos,
-    world::{World, inventory::Inventory, worker::Worker},
+    world::{
+        World,
+        inventory::Inventory,
+        jobs::Job,
+        worker::{Worker, worker_unassigned_state_transistions},
+    },
 };
 
+/// The most important job a worker of the building can take. Tools are fetched first, but only if there are any in
+/// the stores - otherwise the worker goes on with the next job, instead of standing around
+pub fn take_job(
+    worker: Worker,
+    shop_pos: Pos,
+    world: &mut World,
+    shop_id: &String,
+) -> (Worker, Option<Job>) {
+    let job = if let Some(job) = world.jobs.take(|j| j.is_for(shop_pos)) {
+        job
+    } else {
+        return (worker, None);
+    };
+    if !job.is_for_tools() {
+        return (worker, Some(job));
+    }
+
+    let worker = supply_command(worker, shop_pos, world, &job.items, shop_id);
+    if !matches!(worker, Worker::Idle(_)) {
+        return (worker, None);
+    }
+    let next_job = world.jobs.take(|j| j.is_for(shop_pos) && !j.is_for_tools());
+    (worker, next_job)
+}
+
+/// Build zones and item piles - see JobKind::is_open
+pub fn open_job_command(
+    worker: Worker,
+    job: Job,
+    world: &mut World,
+) -> Worker {
+    let idle_worker = if let Worker::Idle(idle_worker) = worker {
+        idle_worker
+    } else {
+        return worker;
+    };
+    worker_unassigned_state_transistions::take_open_job(idle_worker, job, world)
+}
+
 pub fn supply_command(
     worker: Worker,
     shop_pos: Pos,
@@ -36,56 +80,60 @@ pub fn supply_command(
         return Worker::Idle(idle_worker); //remain idle
     };
 
-    let reservation = pick_one_of(
+    //the store has at least one of the materials - checked above
+    let to_take = pick_load(
         &closest_shop.building_base.output,
         materials_to_supply_any_of,
-        idle_worker.inventory.limit,
-    )
-    .unwrap(); //can safely unwrap - just checked if the store has the materials
-
-    closest_shop.building_base.output.remove(&reservation.0, reservation.1);
+        idle_worker.inventory.remaining_capacity(),
+    );
 
     info!(
-        "{} will be supplying {} {} from {} at {}. Remaining in the store: {}.",
+        "{} will be supplying {} from {} at {}. Remaining in the store: {}.",
         idle_worker.name,
-        reservation.0,
-        reservation.1,
+        to_take.to_string(),
         closest_shop.building_behaviour.discriminant(),
         path.last().unwrap(),
         closest_shop.building_base.output
     );
 
-    return idle_worker.to_supplying(path, &world.map, reservation);
+    let store_pos = closest_shop.building_base.pos;
+    let reservation = closest_shop.building_base.output.reserve(store_pos, to_take);
+
+    idle_worker.to_supplying(path, &world.map, reservation)
 }
 
-///This method will prioritize the item of the highest quantity from the store
-fn pick_one_of(
+///Fills the load with as many of the materials as fit, the ones with the highest quantity in the
+///store first
+fn pick_load(
     inventory: &Inventory,
-    materials_to_take_variant: &Vec<InventoryItems>,
-    limit: f32,
-) -> Option<(InventoryItems, f32)> {
-    if materials_to_take_variant.is_empty() || inventory.is_empty() {
-        return None;
-    }
-
-    let mut union: Vec<_> = inventory
+    materials_to_take_variant: &[InventoryItems],
+    capacity: f32,
+) -> Vec<(InventoryItems, f32)> {
+    let mut union: Vec<(InventoryItems, f32)> = materials_to_take_variant
         .iter()
-        .filter(|(key, _)| materials_to_take_variant.iter().any(|k| *key == k))
+        .map(|key| (*key, inventory.available(key)))
+        .filter(|(_, available)| *available > 0.0)
         .collect();
 
-    union.sort_by(|l, r| l.1.total_cmp(r.1));
-
-    let best_material = if let Some(a) = union.first() {
-        a.0.clone() //clone to be returned from the func
-    } else {
-        return None;
-    };
-
-    let best = inventory.get(&best_material);
+    //the same material might be listed more than once - sorting by the item too puts the repeats next
+    //to each other, so that dedup catches them
+    union.sort_by(|l, r| r.1.total_cmp(&l.1).then((l.0 as usize).cmp(&(r.0 as usize))));
+    union.dedup_by_key(|(key, _)| *key);
+
+    let mut remaining_capacity = capacity;
+    let mut load = Vec::new();
+    for (item, available) in union {
+        let weight = item.config().weight;
+        let to_take = f32::min(available, remaining_capacity / weight);
+        if to_take <= 0.0 {
+            continue;
+        }
 
-    let to_take = f32::min(best, limit);
+        remaining_capacity -= to_take * weight;
+        load.push((item, to_take));
+    }
 
-    Some((best_material, to_take))
+    load
 }
 
 pub fn store_command(
@@ -100,6 +148,11 @@ pub fn store_command(
         return worker;
     };
 
+    //reserved items stay where they are - nothing to carry to the store
+    if shop_output.total_available() <= 0.0 {
+        return Worker::Idle(idle_worker);
+    }
+
     //store items
     info!("{} is storing resources. Current inventory: {}", shop_id, shop_output);
 
@@ -113,26 +166,26 @@ pub fn store_command(
         info!("The follwoing materials remain in the shop {}", shop_output);
     }
 
-    return storing_or_idle_worker;
+    storing_or_idle_worker
 }
 
 fn transfer_until_full(
     source: &mut Inventory,
     target: &mut Inventory,
 ) {
-    if target.limit <= 0.0 || source.total_items() < target.limit - target.total_items() {
-        for (key, items) in source.drain() {
-            target.add(&key, items);
-        }
-    } else {
-        for (&key, items) in source.inv.iter_mut() {
-            let remaining_capacity = target.limit - target.total_items();
-            if remaining_capacity <= 0.0 {
-                return;
-            }
-            let to_transfer = f32::min(remaining_capacity, *items);
-            *items -= to_transfer;
-            target.add(&key, to_transfer);
+    //reserved items stay where they are - someone is coming for them
+    let available: Vec<(InventoryItems, f32)> = source
+        .iter()
+        .map(|(key, _)| (*key, source.available(key)))
+        .filter(|(_, available)| *available > 0.0)
+        .collect();
+
+    for (key, items) in available {
+        let to_transfer = f32::min(target.fits(&key), items);
+        if to_transfer <= 0.0 {
+            continue;
         }
+        source.remove(&key, to_transfer);
+        target.add(&key, to_transfer);
     }
 }
@@ -0,0 +1,68 @@
+use crate::{config::buildings::DecorationConfig, math::Pos, world::World};
+
+/// The area around a decoration, in which the workers receive a buff to their resolve
+pub struct DecorationArea {
+    pub pos: Pos,
+    pub width: u8,
+    pub height: u8,
+    pub config: &'static DecorationConfig,
+}
+
+impl DecorationArea {
+    /// Distance is measured from the closest tile of the decoration footprint, not from its corner -
+    /// a large park should cover the same distance from each of its sides
+    pub fn covers(
+        &self,
+        tile: &Pos,
+    ) -> bool {
+        let dx = distance_to_range(tile.x, self.pos.x, self.pos.x + self.width as usize - 1);
+        let dy = distance_to_range(tile.y, self.pos.y, self.pos.y + self.height as usize - 1);
+        dx.max(dy) <= self.config.radius as usize
+    }
+
+    /// The footprint grown by the radius on each side, cut off at the edges of the map - every tile
+    /// of it is covered
+    pub fn covered_tiles(
+        &self,
+        map_width: usize,
+        map_height: usize,
+    ) -> impl Iterator<Item = Pos> {
+        let radius = self.config.radius as usize;
+        let min_x = self.pos.x.saturating_sub(radius);
+        let min_y = self.pos.y.saturating_sub(radius);
+        let max_x = (self.pos.x + self.width as usize - 1 + radius).min(map_width - 1);
+        let max_y = (self.pos.y + self.height as usize - 1 + radius).min(map_height - 1);
+
+        (min_y..=max_y).flat_map(move |y| (min_x..=max_x).map(move |x| Pos::new(x, y)))
+    }
+}
+
+fn distance_to_range(
+    value: usize,
+    min: usize,
+    max: usize,
+) -> usize {
+    if value < min {
+        min - value
+    } else {
+        value.saturating_sub(max)
+    }
+}
+
+impl World {
+    pub fn get_decoration_areas(&self) -> Vec<DecorationArea> {
+        self.shops
+            .iter()
+            .filter_map(|s| {
+                let data = s.building_base.building.get_data();
+                let (width, height) = s.building_base.footprint();
+                data.decoration.as_ref().map(|config| DecorationArea {
+                    pos: s.building_base.pos,
+                    width,
+                    height,
+                    config,
+                })
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,60 @@
+use crate::{config::buildings::Buildings, math::Pos};
+
+use super::{BuildingBase, rotation::Rotation};
+
+/// A way into a building. Workers walk up to the doorstep and step through the door - a tile of the
+/// building itself
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Entrance {
+    pub door: Pos,
+    pub doorstep: Pos,
+}
+
+impl Entrance {
+    /// Entrances of the building placed with its top-left corner at pos. None if any of the
+    /// doorsteps would end up off the map - the right and bottom edge is up to the map to check
+    pub fn for_building(
+        building: Buildings,
+        pos: &Pos,
+        rotation: Rotation,
+    ) -> Option<Vec<Entrance>> {
+        let config = building.get_data();
+        let (width, height) = rotation.footprint(config.width, config.height);
+        config
+            .entrances
+            .iter()
+            .map(|e| {
+                let (dx, dy) = rotation.rotate(e.x as i32, e.y as i32, config.width, config.height);
+                let x = pos.x as i32 + dx;
+                let y = pos.y as i32 + dy;
+                if x < 0 || y < 0 {
+                    return None;
+                }
+
+                //the door is the tile of the building right next to the doorstep
+                let door_x = dx.clamp(0, width as i32 - 1) as usize;
+                let door_y = dy.clamp(0, height as i32 - 1) as usize;
+                Some(Entrance {
+                    door: Pos::new(pos.x + door_x, pos.y + door_y),
+                    doorstep: Pos::new(x as usize, y as usize),
+                })
+            })
+            .collect()
+    }
+}
+
+impl BuildingBase {
+    pub fn entrances(&self) -> Vec<Entrance> {
+        Entrance::for_building(self.building, &self.pos, self.rotation).unwrap_or_default()
+    }
+
+    /// Is the tile a part of the building?
+    pub fn contains(
+        &self,
+        pos: &Pos,
+    ) -> bool {
+        let (width, height) = self.footprint();
+        (self.pos.x..self.pos.x + width as usize).contains(&pos.x)
+            && (self.pos.y..self.pos.y + height as usize).contains(&pos.y)
+    }
+}
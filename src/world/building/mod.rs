@@ -1,14 +1,20 @@
 pub mod build_zone;
 pub mod building_behaviour;
+pub mod decoration;
+pub mod entrance;
+pub mod rotation;
+pub mod tools;
 
 use std::collections::LinkedList;
 
 use building_behaviour::{gatherer::GathererBehaviour, hearth::HearthBehaviour, producer::ProducerBehaviour};
+use rotation::Rotation;
 use strum_macros::{Display, EnumDiscriminants, EnumIs};
+use tools::ToolStock;
 
 use crate::{config::buildings::Buildings, math::Pos};
 
-use super::{World, inventory::Inventory, worker::Worker};
+use super::{World, inventory::Inventory, jobs::JobBoard, worker::Worker};
 
 pub struct BuildingBase {
     pub pos: Pos,
@@ -16,6 +22,8 @@ pub struct BuildingBase {
     pub max_workers: u8,
     pub output: Inventory, //todo: really needed here? maybe move to data?
     pub building: Buildings,
+    pub rotation: Rotation,
+    pub tools: ToolStock,
 }
 
 pub struct Building {
@@ -34,10 +42,20 @@ pub enum BuildingBehaviour {
     Store(StoreBehaviour),
     Gatherer(GathererBehaviour),
     Producer(ProducerBehaviour),
+    House(HouseBehaviour),
+    Decoration(DecorationBehaviour),
 }
 
 pub struct StoreBehaviour {}
 
+pub struct HouseBehaviour {}
+
+impl HouseBehaviour {
+    pub const CAPACITY: u8 = 4;
+}
+
+pub struct DecorationBehaviour {}
+
 impl Building {
     pub fn process(
         &mut self,
@@ -51,6 +69,19 @@ impl Building {
             _ => {} //currently no update necessary...
         }
     }
+
+    /// Posts whatever the building currently needs its workers to do, replacing its older jobs
+    pub fn post_jobs(
+        &self,
+        jobs: &mut JobBoard,
+    ) {
+        match &self.building_behaviour {
+            BuildingBehaviour::Hearth(hearth) => hearth.post_jobs(&self.building_base, jobs),
+            BuildingBehaviour::Gatherer(gatherer) => gatherer.post_jobs(&self.building_base, jobs),
+            BuildingBehaviour::Producer(producer) => producer.post_jobs(&self.building_base, jobs),
+            _ => {} //stores, houses and decorations need nothing from their workers
+        }
+    }
 }
 
 impl BuildingBehaviourDiscriminants {
@@ -60,6 +91,8 @@ impl BuildingBehaviourDiscriminants {
             BuildingBehaviourDiscriminants::Store => BuildingBehaviour::Store(StoreBehaviour {}),
             BuildingBehaviourDiscriminants::Gatherer => BuildingBehaviour::Gatherer(GathererBehaviour::default()),
             BuildingBehaviourDiscriminants::Producer => BuildingBehaviour::Producer(ProducerBehaviour::default()),
+            BuildingBehaviourDiscriminants::House => BuildingBehaviour::House(HouseBehaviour {}),
+            BuildingBehaviourDiscriminants::Decoration => BuildingBehaviour::Decoration(DecorationBehaviour {}),
         }
     }
 }
@@ -0,0 +1,73 @@
+use strum_macros::Display;
+
+use super::BuildingBase;
+
+/// Clockwise rotation of a building, as it was placed on the map. The top-left corner of the
+/// rotated footprint stays at the building position
+#[derive(Default, Clone, Copy, PartialEq, Eq, Display)]
+pub enum Rotation {
+    #[default]
+    Deg0,
+    Deg90,
+    Deg180,
+    Deg270,
+}
+
+impl Rotation {
+    pub fn from_degrees(degrees: u16) -> Option<Rotation> {
+        match degrees % 360 {
+            0 => Some(Rotation::Deg0),
+            90 => Some(Rotation::Deg90),
+            180 => Some(Rotation::Deg180),
+            270 => Some(Rotation::Deg270),
+            _ => None,
+        }
+    }
+
+    pub fn degrees(&self) -> u16 {
+        match self {
+            Rotation::Deg0 => 0,
+            Rotation::Deg90 => 90,
+            Rotation::Deg180 => 180,
+            Rotation::Deg270 => 270,
+        }
+    }
+
+    /// Width and height of the rotated footprint
+    pub fn footprint(
+        &self,
+        width: u8,
+        height: u8,
+    ) -> (u8, u8) {
+        match self {
+            Rotation::Deg0 | Rotation::Deg180 => (width, height),
+            Rotation::Deg90 | Rotation::Deg270 => (height, width),
+        }
+    }
+
+    /// Where the tile at (x, y) of the unrotated building ends up. Works for the tiles just outside
+    /// of the footprint too - that's where the doorsteps are
+    pub fn rotate(
+        &self,
+        x: i32,
+        y: i32,
+        width: u8,
+        height: u8,
+    ) -> (i32, i32) {
+        let (w, h) = (width as i32, height as i32);
+        match self {
+            Rotation::Deg0 => (x, y),
+            Rotation::Deg90 => (h - 1 - y, x),
+            Rotation::Deg180 => (w - 1 - x, h - 1 - y),
+            Rotation::Deg270 => (y, w - 1 - x),
+        }
+    }
+}
+
+impl BuildingBase {
+    /// Width and height of the building on the map, after the rotation
+    pub fn footprint(&self) -> (u8, u8) {
+        let config = self.building.get_data();
+        self.rotation.footprint(config.width, config.height)
+    }
+}
@@ -0,0 +1,96 @@
+use crate::{
+    config::{buildings::ToolsConfig, inventory::InventoryItems},
+    world::{
+        jobs::{Job, JobBoard, JobKind},
+        statistics::Statistics,
+        worker::Worker,
+    },
+};
+
+use super::BuildingBase;
+
+/// Tools kept in the building for its workers. They are not a part of the output - nobody hauls
+/// them back to the store
+pub struct ToolStock {
+    pub amount: f32,
+    config: Option<&'static ToolsConfig>,
+}
+
+impl ToolStock {
+    pub fn new(config: Option<&'static ToolsConfig>) -> Self {
+        Self { amount: 0.0, config }
+    }
+
+    fn equipped(&self) -> Option<&'static ToolsConfig> {
+        self.config.filter(|_| self.amount > 0.0)
+    }
+
+    pub fn is_equipped(&self) -> bool {
+        self.equipped().is_some()
+    }
+
+    pub fn speed_bonus(&self) -> f32 {
+        self.equipped().map_or(1.0, |c| c.speed_bonus)
+    }
+
+    pub fn yield_bonus(&self) -> f32 {
+        self.equipped().map_or(1.0, |c| c.yield_bonus)
+    }
+
+    /// A worker has spent the time working with the tools. The worn out part is used up, as far
+    /// as the statistics are concerned
+    pub fn wear(
+        &mut self,
+        delta: f32,
+        statistics: &mut Statistics,
+    ) {
+        if let Some(config) = self.equipped() {
+            let worn = f32::min(config.wear_per_second * delta, self.amount);
+            self.amount -= worn;
+            statistics.record_consumed([(InventoryItems::Tools, worn)]);
+        }
+    }
+
+    pub fn needs_supply(&self) -> bool {
+        self.config.is_some_and(|c| self.amount <= c.supplying_threshold)
+    }
+
+    /// Puts the delivered tools into the stock - the rest of the items is returned
+    pub fn unpack(
+        &mut self,
+        items: Vec<(InventoryItems, f32)>,
+    ) -> Vec<(InventoryItems, f32)> {
+        if self.config.is_none() {
+            return items;
+        }
+
+        let (tools, rest): (Vec<_>, Vec<_>) = items.into_iter().partition(|(item, _)| *item == InventoryItems::Tools);
+        self.amount += tools.iter().map(|(_, amount)| amount).sum::<f32>();
+        rest
+    }
+}
+
+impl Job {
+    pub fn is_for_tools(&self) -> bool {
+        self.kind == JobKind::Supply && self.items == [InventoryItems::Tools]
+    }
+}
+
+/// Asks for more tools when running low - unless one of the workers is already bringing them
+pub fn post_tools_job(
+    shop_base: &BuildingBase,
+    jobs: &mut JobBoard,
+) {
+    if !shop_base.tools.needs_supply() {
+        return;
+    }
+
+    let already_bringing = shop_base.workers.iter().any(|w| match w {
+        Worker::Supplying(w) => w.action_data.1.items.iter().any(|(i, _)| *i == InventoryItems::Tools),
+        Worker::Returning(w) => w.inventory.get(&InventoryItems::Tools) > 0.0,
+        _ => false,
+    });
+    if !already_bringing {
+        jobs.post(JobKind::Supply, shop_base.pos, vec![InventoryItems::Tools], 1);
+    }
+}
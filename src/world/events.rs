@@ -0,0 +1,15 @@
+use crate::{config::inventory::InventoryItems, math::Pos};
+
+/// Things that happened in the world, that the player should be notified about. Events pile up
+/// until someone takes them - see `World::take_events`
+pub enum WorldEvent {
+    NewcomersArrived {
+        names: Vec<String>,
+        hearth_pos: Pos,
+    },
+    ///perishable goods went bad - in a building, a worker's hands or on the ground
+    ItemsSpoiled {
+        pos: Pos,
+        items: Vec<(InventoryItems, f32)>,
+    },
+}
@@ -0,0 +1,103 @@
+use log::info;
+use strum::IntoEnumIterator;
+
+use crate::{
+    ai::pathfinding,
+    config::inventory::InventoryItems,
+    math::Pos,
+    world::{World, inventory::Inventory},
+};
+
+/// Items lying on the ground - dropped by workers, who could not bring them anywhere. Idle
+/// workers pick them up and bring them to a store.
+pub struct ItemPile {
+    pub pos: Pos,
+    pub items: Inventory,
+    ///someone is already on their way to pick it up
+    pub claimed: bool,
+}
+
+impl World {
+    /// Items dropped on a tile with a pile already on it, end up in the same pile
+    pub fn drop_items<T>(
+        &mut self,
+        pos: Pos,
+        items: T,
+    ) where
+        T: IntoIterator<Item = (InventoryItems, f32)>,
+    {
+        let pile = if let Some(pile) = self.item_piles.iter_mut().find(|p| p.pos == pos) {
+            pile
+        } else {
+            self.item_piles.push_back(ItemPile {
+                pos,
+                items: Inventory::new(),
+                claimed: false,
+            });
+            self.item_piles.back_mut().unwrap()
+        };
+
+        pile.items.add_range(items);
+        info!("Items were dropped at {}. The pile now contains: {}", pos, pile.items);
+    }
+
+    /// Finds the closest pile nobody is going for yet, and marks it as claimed
+    pub(in crate::world) fn claim_closest_pile(
+        &mut self,
+        start: Pos,
+    ) -> Option<Vec<Pos>> {
+        let mut piles: Vec<&mut ItemPile> = self.item_piles.iter_mut().filter(|p| !p.claimed).collect();
+        let targets: Vec<Pos> = piles.iter().map(|p| p.pos).collect();
+
+        let (index, path) = pathfinding::dijkstra_closest_target(&self.map, start, &targets)?;
+        let pile = piles.swap_remove(index);
+        pile.claimed = true;
+        Some(path)
+    }
+
+    /// The worker going for the pile is not coming after all - someone else can claim it
+    pub(in crate::world) fn release_pile(
+        &mut self,
+        pos: Pos,
+    ) {
+        if let Some(pile) = self.item_piles.iter_mut().find(|p| p.pos == pos) {
+            pile.claimed = false;
+        }
+    }
+
+    /// Takes as much from the pile as fits in the inventory. Whatever remains can be claimed again.
+    pub(in crate::world) fn pick_up_pile(
+        &mut self,
+        pos: Pos,
+        inventory: &mut Inventory,
+    ) {
+        let pile = if let Some(pile) = self.item_piles.iter_mut().find(|p| p.pos == pos) {
+            pile
+        } else {
+            return; //someone else must have taken it
+        };
+
+        //iterating the enum rather than the hash map - the order has to be deterministic
+        let to_take: Vec<(InventoryItems, f32)> = InventoryItems::iter()
+            .map(|i| (i, pile.items.get(&i)))
+            .filter(|(_, amount)| *amount > 0.0)
+            .collect();
+        for (item, amount) in to_take {
+            let amount = amount.min(inventory.fits(&item));
+            if amount <= 0.0 {
+                continue;
+            }
+
+            pile.items.remove(&item, amount);
+            inventory.add(&item, amount);
+        }
+        pile.claimed = false;
+
+        if pile.items.is_empty() {
+            self.item_piles = std::mem::take(&mut self.item_piles)
+                .into_iter()
+                .filter(|p| p.pos != pos)
+                .collect();
+        }
+    }
+}
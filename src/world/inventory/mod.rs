@@ -3,9 +3,17 @@ use std::fmt::Display;
 
 use crate::config::inventory::InventoryItems;
 
+pub mod reservation;
+pub mod spoilage;
+
 pub struct Inventory {
     pub inv: HashMap<InventoryItems, f32>,
+    ///maximum total weight of the items - see InventoryItemConfig. 0.0 means no limit
     pub limit: f32,
+    ///part of `inv` promised to workers that are on their way to pick it up - see reservation.rs
+    pub reserved: HashMap<InventoryItems, f32>,
+    ///how far each stack of perishable items is on the way to losing its next unit - see spoilage.rs
+    pub spoilage: HashMap<InventoryItems, f32>,
 }
 
 impl Display for Inventory {
@@ -20,11 +28,33 @@ impl Display for Inventory {
     }
 }
 
+impl FromIterator<(InventoryItems, f32)> for Inventory {
+    fn from_iter<T>(iter: T) -> Self
+    where
+        T: IntoIterator<Item = (InventoryItems, f32)>,
+    {
+        Self {
+            inv: HashMap::from_iter(iter),
+            limit: 0.0,
+            reserved: HashMap::new(),
+            spoilage: HashMap::new(),
+        }
+    }
+}
+
+impl Default for Inventory {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Inventory {
     pub fn new() -> Self {
         Self {
             inv: HashMap::new(),
             limit: 0.0,
+            reserved: HashMap::new(),
+            spoilage: HashMap::new(),
         }
     }
 
@@ -32,6 +62,8 @@ impl Inventory {
         Self {
             inv: HashMap::new(),
             limit,
+            reserved: HashMap::new(),
+            spoilage: HashMap::new(),
         }
     }
 
@@ -39,10 +71,34 @@ impl Inventory {
         if self.limit <= 0.0 {
             false
         } else {
-            self.inv.values().map(|x| *x).sum::<f32>() >= self.limit
+            self.total_weight() >= self.limit
         }
     }
 
+    pub fn total_weight(&self) -> f32 {
+        self.inv
+            .iter()
+            .map(|(item, amount)| amount * item.config().weight)
+            .sum()
+    }
+
+    /// Weight that can still be added
+    pub fn remaining_capacity(&self) -> f32 {
+        if self.limit <= 0.0 {
+            f32::INFINITY
+        } else {
+            (self.limit - self.total_weight()).max(0.0)
+        }
+    }
+
+    /// How much of the given item still fits
+    pub fn fits(
+        &self,
+        item: &InventoryItems,
+    ) -> f32 {
+        self.remaining_capacity() / item.config().weight
+    }
+
     pub fn is_empty(&self) -> bool {
         for (_, &amount) in self.inv.iter() {
             if amount > 0.0 {
@@ -60,14 +116,9 @@ impl Inventory {
         acc
     }
 
-    pub fn from_iter<T>(iter: T) -> Self
-    where
-        T: IntoIterator<Item = (InventoryItems, f32)>,
-    {
-        Self {
-            inv: HashMap::from_iter(iter),
-            limit: 0.0,
-        }
+    /// Items that are not reserved by anyone - that is, can still be taken away
+    pub fn total_available(&self) -> f32 {
+        self.inv.keys().map(|item| self.available(item)).sum()
     }
 
     pub fn add_range<T>(
@@ -96,7 +147,22 @@ impl Inventory {
         &self,
         item: &InventoryItems,
     ) -> f32 {
-        self.inv.get(item).unwrap_or(&0.0).clone()
+        *self.inv.get(item).unwrap_or(&0.0)
+    }
+
+    /// Amount that is not reserved by anyone and can be taken right away
+    pub fn available(
+        &self,
+        item: &InventoryItems,
+    ) -> f32 {
+        self.get(item) - self.get_reserved(item)
+    }
+
+    pub fn get_reserved(
+        &self,
+        item: &InventoryItems,
+    ) -> f32 {
+        *self.reserved.get(item).unwrap_or(&0.0)
     }
 
     pub fn get_mut(
@@ -128,7 +194,7 @@ impl Inventory {
         *current_amount -= amount;
     }
 
-    pub fn drain(&mut self) -> std::collections::hash_map::Drain<InventoryItems, f32> {
+    pub fn drain(&mut self) -> std::collections::hash_map::Drain<'_, InventoryItems, f32> {
         self.inv.drain()
     }
 
@@ -141,7 +207,7 @@ impl Inventory {
         materials: &Vec<InventoryItems>,
     ) -> bool {
         for key in materials {
-            if self.get(key) >= 1.0 {
+            if self.available(key) >= 1.0 {
                 return true;
             }
         }
@@ -0,0 +1,119 @@
+use crate::{config::inventory::InventoryItems, math::Pos, world::World};
+
+use super::Inventory;
+
+/// Items promised to a worker. The items stay in the store until the worker gets there and
+/// commits the reservation - if anything goes wrong on the way, the reservation is released and
+/// the items are available again. Reservations are neither Clone nor Copy - whoever holds one is
+/// responsible for either committing or releasing it.
+pub struct Reservation {
+    ///position of the building the items were reserved in
+    pub store_pos: Pos,
+    pub items: Vec<(InventoryItems, f32)>,
+}
+
+/// Reservation, that is going to be picked up on the way, once the worker reaches the given index
+/// of the path
+pub struct PendingPickup {
+    pub reservation: Reservation,
+    pub path_index: usize,
+}
+
+impl Inventory {
+    /// Reserves as much of the requested items as is available
+    pub fn reserve(
+        &mut self,
+        store_pos: Pos,
+        items: Vec<(InventoryItems, f32)>,
+    ) -> Reservation {
+        let mut reserved_items = Vec::new();
+        for (item, amount) in items {
+            let amount = amount.min(self.available(&item));
+            if amount <= 0.0 {
+                continue;
+            }
+
+            *self.reserved.entry(item).or_insert(0.0) += amount;
+            reserved_items.push((item, amount));
+        }
+
+        Reservation {
+            store_pos,
+            items: reserved_items,
+        }
+    }
+
+    /// Takes the reserved items out of the inventory
+    pub fn commit(
+        &mut self,
+        reservation: Reservation,
+    ) -> Vec<(InventoryItems, f32)> {
+        for (item, amount) in &reservation.items {
+            self.unreserve(item, *amount);
+            self.remove(item, *amount);
+        }
+        reservation.items
+    }
+
+    pub fn release(
+        &mut self,
+        reservation: Reservation,
+    ) {
+        for (item, amount) in &reservation.items {
+            self.unreserve(item, *amount);
+        }
+    }
+
+    fn unreserve(
+        &mut self,
+        item: &InventoryItems,
+        amount: f32,
+    ) {
+        let reserved = self.reserved.entry(*item).or_insert(0.0);
+        *reserved -= amount;
+        //floating point noise
+        if *reserved <= f32::EPSILON {
+            self.reserved.remove(item);
+        }
+    }
+
+    /// Nothing is ever reserved twice, and nothing reserved is ever taken by someone else
+    pub fn reservations_valid(&self) -> bool {
+        self.reserved
+            .iter()
+            .all(|(item, reserved)| *reserved >= 0.0 && *reserved <= self.get(item) + f32::EPSILON)
+    }
+}
+
+impl World {
+    /// Returns the items taken from the store. The store might be gone by now (e.g. demolished),
+    /// in which case there is nothing to take.
+    pub fn commit_reservation(
+        &mut self,
+        reservation: Reservation,
+    ) -> Vec<(InventoryItems, f32)> {
+        match self
+            .shops
+            .iter_mut()
+            .find(|s| s.building_base.pos == reservation.store_pos)
+        {
+            Some(store) => store.building_base.output.commit(reservation),
+            None => Vec::new(),
+        }
+    }
+
+    /// The worker is not coming for the items after all - e.g. they gave up on a blocked way (see
+    /// World::update_paths). The store might be gone by now too
+    pub fn release_reservation(
+        &mut self,
+        reservation: Reservation,
+    ) {
+        if let Some(store) = self
+            .shops
+            .iter_mut()
+            .find(|s| s.building_base.pos == reservation.store_pos)
+        {
+            store.building_base.output.release(reservation);
+        }
+    }
+}
@@ -0,0 +1,104 @@
+use log::info;
+use strum::IntoEnumIterator;
+
+use crate::{
+    config::inventory::{InventoryItems, ItemTag},
+    data_helpers::to_string::ToString,
+    math::Pos,
+    world::{World, building::BuildingBehaviour, events::WorldEvent},
+};
+
+use super::Inventory;
+
+impl Inventory {
+    /// Progresses the spoilage of every perishable stack and removes whole units that went bad.
+    /// Bigger stacks rot faster - every unit in the stack adds to the progress. No randomness, so
+    /// the same stack always spoils at the same time. Returns what spoiled
+    pub fn spoil(
+        &mut self,
+        delta: f32,
+        preserves: &[ItemTag],
+    ) -> Vec<(InventoryItems, f32)> {
+        let mut spoiled = Vec::new();
+
+        //iterating over the enum rather than the HashMap - order has to be deterministic
+        for item in InventoryItems::iter() {
+            let Some(shelf_life) = item.config().shelf_life else {
+                continue;
+            };
+
+            let amount = self.get(&item);
+            if amount <= 0.0 {
+                self.spoilage.remove(&item);
+                continue;
+            }
+
+            //progress is kept, so food taken out of the cellar does not come out fresh
+            if preserves.iter().any(|tag| item.has_tag(*tag)) {
+                continue;
+            }
+
+            //reserved items are promised to someone already - they can rot once they are picked up
+            let available = self.available(&item);
+
+            let progress = self.spoilage.entry(item).or_insert(0.0);
+            *progress = (*progress + amount * delta / shelf_life).min(amount);
+
+            let lost = progress.min(available).floor();
+            if lost >= 1.0 {
+                *progress -= lost;
+                self.remove(&item, lost);
+                spoiled.push((item, lost));
+            }
+        }
+
+        spoiled
+    }
+}
+
+impl World {
+    /// Everything rots - goods in buildings, in the hands of workers and lying on the ground.
+    /// Only buildings can preserve food, see BuildingConfig::preserves
+    pub(in crate::world) fn update_spoilage(
+        &mut self,
+        delta: f32,
+    ) {
+        let mut reports: Vec<(Pos, Vec<(InventoryItems, f32)>)> = Vec::new();
+
+        for shop in self.shops.iter_mut() {
+            let base = &mut shop.building_base;
+            let preserves = &base.building.get_data().preserves;
+
+            let mut spoiled = base.output.spoil(delta, preserves);
+            if let BuildingBehaviour::Producer(producer) = &mut shop.building_behaviour {
+                spoiled.append(&mut producer.input.spoil(delta, preserves));
+            }
+            reports.push((base.pos, spoiled));
+
+            let mut workers: Vec<_> = base.workers.iter_mut().collect();
+            if let BuildingBehaviour::Hearth(hearth) = &mut shop.building_behaviour {
+                workers.extend(hearth.unassigned_workers.iter_mut());
+            }
+            for worker in workers {
+                let spoiled = worker.inventory_mut().spoil(delta, &[]);
+                reports.push((*worker.pos(), spoiled));
+            }
+        }
+
+        for pile in self.item_piles.iter_mut() {
+            reports.push((pile.pos, pile.items.spoil(delta, &[])));
+        }
+        //nobody needs to walk to a pile of nothing. Claimed ones are left to the worker going there
+        self.item_piles = std::mem::take(&mut self.item_piles)
+            .into_iter()
+            .filter(|p| p.claimed || !p.items.is_empty())
+            .collect();
+
+        for (pos, items) in reports.into_iter().filter(|(_, items)| !items.is_empty()) {
+            info!("Goods have spoiled at {}: {}", pos, items.to_string());
+            self.settlement.spoiled.add_range(items.iter().copied());
+            self.statistics.record_spoiled(items.iter().copied());
+            self.events.push(WorldEvent::ItemsSpoiled { pos, items });
+        }
+    }
+}
@@ -0,0 +1,212 @@
+use std::collections::HashMap;
+
+use strum_macros::Display;
+
+use crate::{config::inventory::InventoryItems, math::Pos, world::World};
+
+/// Everything a worker can be asked to do
+#[derive(Hash, PartialEq, Eq, Clone, Copy, Display)]
+pub enum JobKind {
+    ///bring the output of a building to the store
+    Haul,
+    ///bring materials from the store to the building
+    Supply,
+    Build,
+    Gather,
+    ///bring fuel to the hearth
+    RefuelHearth,
+    Produce,
+    ///pick up a pile lying on the ground
+    Collect,
+}
+
+impl JobKind {
+    pub fn default_priority(&self) -> i32 {
+        match self {
+            JobKind::RefuelHearth => 50,
+            JobKind::Haul => 30,
+            JobKind::Build => 20,
+            JobKind::Supply => 20,
+            JobKind::Produce => 10,
+            JobKind::Gather => 10,
+            JobKind::Collect => 0,
+        }
+    }
+
+    /// Jobs that do not belong to any building - anyone can take these, the unassigned workers as
+    /// well as the workers of every building. Everything else is done in (or for) the building that
+    /// posted it, by its own workers
+    pub fn is_open(&self) -> bool {
+        matches!(self, JobKind::Build | JobKind::Collect)
+    }
+}
+
+#[derive(Clone)]
+pub struct Job {
+    pub kind: JobKind,
+    ///the building that posted the job, the build zone or the pile
+    pub pos: Pos,
+    ///what to supply or gather - empty for the rest
+    pub items: Vec<InventoryItems>,
+    pub priority: i32,
+    ///how many more workers can take the job
+    pub slots: u8,
+}
+
+impl Job {
+    ///anyone who is eligible can take the job, no matter how many took it before
+    pub const UNLIMITED: u8 = u8::MAX;
+
+    /// A worker of the building can take the job - it is one of the building's own, or an open one.
+    /// Which of them comes first is up to the priorities
+    pub fn is_for(
+        &self,
+        building_pos: Pos,
+    ) -> bool {
+        self.kind.is_open() || self.pos == building_pos
+    }
+}
+
+/// Jobs posted by the buildings, build zones and item piles. The board is posted anew every tick -
+/// the behaviours still decide what their building needs, but the workers pick what they do from
+/// here, most important job first.
+pub struct JobBoard {
+    jobs: Vec<Job>,
+    ///overrides of JobKind::default_priority
+    priorities: HashMap<JobKind, i32>,
+    ///added to every job at the given position - the player wants this done first
+    building_priorities: HashMap<Pos, i32>,
+}
+
+impl Default for JobBoard {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl JobBoard {
+    pub fn new() -> Self {
+        Self {
+            jobs: Vec::new(),
+            priorities: HashMap::new(),
+            building_priorities: HashMap::new(),
+        }
+    }
+
+    pub fn jobs(&self) -> &[Job] {
+        &self.jobs
+    }
+
+    pub fn priority(
+        &self,
+        kind: JobKind,
+    ) -> i32 {
+        self.priorities
+            .get(&kind)
+            .copied()
+            .unwrap_or_else(|| kind.default_priority())
+    }
+
+    pub fn set_priority(
+        &mut self,
+        kind: JobKind,
+        priority: i32,
+    ) {
+        self.priorities.insert(kind, priority);
+    }
+
+    pub fn set_building_priority(
+        &mut self,
+        pos: Pos,
+        priority: i32,
+    ) {
+        self.building_priorities.insert(pos, priority);
+    }
+
+    pub fn post(
+        &mut self,
+        kind: JobKind,
+        pos: Pos,
+        items: Vec<InventoryItems>,
+        slots: u8,
+    ) {
+        let priority = self.priority(kind) + self.building_priorities.get(&pos).copied().unwrap_or(0);
+        self.jobs.push(Job {
+            kind,
+            pos,
+            items,
+            priority,
+            slots,
+        });
+    }
+
+    /// Removes everything the building posted - it is about to post its current needs
+    pub fn withdraw(
+        &mut self,
+        pos: Pos,
+    ) {
+        self.jobs.retain(|j| j.kind.is_open() || j.pos != pos);
+    }
+
+    pub fn clear(&mut self) {
+        self.jobs.clear();
+    }
+
+    /// The most important job the worker can take. Jobs of the same priority are handed out in the
+    /// order they were posted - build zones get built in the order they were placed
+    pub fn take<F>(
+        &mut self,
+        eligible: F,
+    ) -> Option<Job>
+    where
+        F: Fn(&Job) -> bool,
+    {
+        let mut best: Option<usize> = None;
+        for (index, job) in self.jobs.iter().enumerate() {
+            if !eligible(job) {
+                continue;
+            }
+            //strictly greater - the first posted one wins the ties
+            if best.is_none_or(|b| job.priority > self.jobs[b].priority) {
+                best = Some(index);
+            }
+        }
+        let index = best?;
+
+        let job = &mut self.jobs[index];
+        if job.slots != Job::UNLIMITED {
+            job.slots -= 1;
+        }
+        let taken = job.clone();
+        if job.slots == 0 {
+            self.jobs.remove(index);
+        }
+        Some(taken)
+    }
+}
+
+impl World {
+    /// Every building, build zone and item pile posts what needs doing. The buildings re-post their
+    /// own jobs whenever one of their workers finishes something, so the board stays up to date
+    /// during the tick too
+    pub(in crate::world) fn post_jobs(&mut self) {
+        self.jobs.clear();
+
+        //zones take several workers at once - some bring the materials, some build
+        for zone in &self.build_zones {
+            let has_work = zone.is_delivery_complete() || !zone.missing_materials().is_empty();
+            if has_work && zone.free_slots() > 0 {
+                self.jobs
+                    .post(JobKind::Build, zone.pos(), Vec::new(), zone.free_slots());
+            }
+        }
+
+        for pile in self.item_piles.iter().filter(|p| !p.claimed) {
+            self.jobs.post(JobKind::Collect, pile.pos, Vec::new(), 1);
+        }
+
+        for shop in self.shops.iter() {
+            shop.post_jobs(&mut self.jobs);
+        }
+    }
+}
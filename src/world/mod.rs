@@ -1,6 +1,12 @@
 use std::collections::LinkedList;
 
 use building::{Building, BuildingBehaviour, build_zone::BuildZone};
+use events::WorldEvent;
+use ground_items::ItemPile;
+use jobs::JobBoard;
+use settlement::{Settlement, SettlementConfig};
+use statistics::Statistics;
+use traffic::Traffic;
 use worker::Worker;
 use world_map::WorldMap;
 
@@ -8,7 +14,13 @@ use crate::FRAME_NUM;
 
 pub mod actions;
 pub mod building;
+pub mod events;
+pub mod ground_items;
 pub mod inventory;
+pub mod jobs;
+pub mod settlement;
+pub mod statistics;
+pub mod traffic;
 pub mod worker;
 pub mod world_map;
 
@@ -16,10 +28,31 @@ pub struct World {
     pub map: WorldMap,
     pub shops: LinkedList<Building>,
     pub build_zones: LinkedList<BuildZone>,
+    pub item_piles: LinkedList<ItemPile>,
     pub frame_number: usize,
+    pub settlement: Settlement,
+    pub events: Vec<WorldEvent>,
+    pub statistics: Statistics,
+    pub traffic: Traffic,
+    pub jobs: JobBoard,
 }
 
 impl World {
+    pub fn new(map: WorldMap) -> Self {
+        Self {
+            map,
+            shops: LinkedList::new(),
+            build_zones: LinkedList::new(),
+            item_piles: LinkedList::new(),
+            frame_number: 0,
+            settlement: Settlement::new(SettlementConfig::default()),
+            events: Vec::new(),
+            statistics: Statistics::new(),
+            traffic: Traffic::new(),
+            jobs: JobBoard::new(),
+        }
+    }
+
     pub fn next_tick(
         &mut self,
         delta: f32,
@@ -33,15 +66,31 @@ impl World {
         //I just learned I could use something like RefCell, to check borrowing rules at runtime,
         //if I need to. I dont wanna. They say that in programming you either write a code or write
         //a theorem. I'm in the second team
+        //the workers looking for a new way are counted where they stand now, not a tile behind
+        self.update_traffic();
+        self.update_paths();
+        self.post_jobs();
+
         for _ in 0..self.shops.len() {
             let mut shop = self.shops.pop_front().unwrap();
             shop.process(self, delta);
             self.shops.push_back(shop);
         }
 
+        self.update_hearth_status(delta);
+        self.update_resolve(delta);
+        self.update_newcomers(delta);
+        self.update_spoilage(delta);
+        self.update_statistics(delta);
+
         self.frame_number += 1;
     }
 
+    /// Events that happened since the last call
+    pub fn take_events(&mut self) -> Vec<WorldEvent> {
+        std::mem::take(&mut self.events)
+    }
+
     pub fn get_all_unassigned_workers(&self) -> Vec<&Worker> {
         let mut ret = Vec::new();
         for shop in &self.shops {
@@ -54,20 +103,13 @@ impl World {
         ret
     }
 
-    pub fn get_all_build_zones(&self) -> Vec<&BuildZone> {
-        let mut ret = Vec::new();
-        for worker in &self.get_all_unassigned_workers() {
-            if let Worker::Building(worker) = worker {
-                if let Some(bz) = &worker.action_data.build_zone {
-                    ret.push(bz);
-                }
-            }
-        }
-
-        for bz in &self.build_zones {
-            ret.push(bz);
-        }
-
+    pub fn get_all_workers(&self) -> Vec<&Worker> {
+        let mut ret: Vec<&Worker> = self.shops.iter().flat_map(|s| s.building_base.workers.iter()).collect();
+        ret.append(&mut self.get_all_unassigned_workers());
         ret
     }
+
+    pub fn get_all_build_zones(&self) -> Vec<&BuildZone> {
+        self.build_zones.iter().collect()
+    }
 }
@@ -0,0 +1,120 @@
+use strum_macros::Display;
+
+use crate::{
+    config::species::Species,
+    math::Pos,
+    world::{
+        World,
+        inventory::Inventory,
+        worker::{Worker, worker_states::WorkerWithAction},
+    },
+};
+
+pub mod newcomers;
+
+/// Settlement-wide state, that does not belong to any particular building
+pub struct Settlement {
+    pub config: SettlementConfig,
+    pub spawned_workers: usize,
+    pub arrived_newcomers: usize,
+    pub time_since_newcomers: f32,
+    ///there is a hearth, but none of them is burning
+    pub hearth_out: bool,
+    pub time_hearth_out: f32,
+    ///everything that went bad since the settlement was founded
+    pub spoiled: Inventory,
+}
+
+/// Bad things that happen while the hearth is out
+#[derive(PartialEq, Eq, Clone, Copy, Display)]
+pub enum HearthOutPenalty {
+    ///see ResolveFactor::HearthOut
+    Resolve,
+    SlowerBreaks,
+    NoNewcomers,
+}
+
+pub struct SettlementConfig {
+    ///species that can join the settlement, with their weights
+    pub species: Vec<(Species, u32)>,
+}
+
+impl Default for SettlementConfig {
+    fn default() -> Self {
+        Self {
+            species: vec![(Species::Human, 1), (Species::Beaver, 1), (Species::Lizard, 1)],
+        }
+    }
+}
+
+impl Settlement {
+    pub fn new(config: SettlementConfig) -> Self {
+        Self {
+            config,
+            spawned_workers: 0,
+            arrived_newcomers: 0,
+            time_since_newcomers: 0.0,
+            hearth_out: false,
+            time_hearth_out: 0.0,
+            spoiled: Inventory::new(),
+        }
+    }
+
+    /// The game should be deterministic, so no randomness here - species take turns, each
+    /// getting as many workers in a row as its weight
+    pub fn next_species(&mut self) -> Species {
+        let total_weight: u32 = self.config.species.iter().map(|(_, w)| w).sum();
+        if total_weight == 0 {
+            return Species::Human;
+        }
+
+        let mut slot = (self.spawned_workers as u32) % total_weight;
+        self.spawned_workers += 1;
+
+        for (species, weight) in &self.config.species {
+            if slot < *weight {
+                return *species;
+            }
+            slot -= weight;
+        }
+
+        unreachable!("Slot is always smaller than the total weight.")
+    }
+
+    /// See World::spawn_worker - for when the rest of the world is borrowed already
+    pub fn spawn_worker(
+        &mut self,
+        name: String,
+        pos: Pos,
+    ) -> Worker {
+        let species = self.next_species();
+        Worker::Idle(WorkerWithAction::new(name, species, pos))
+    }
+}
+
+impl World {
+    pub(in crate::world) fn update_hearth_status(
+        &mut self,
+        delta: f32,
+    ) {
+        let has_hearth = self.get_hearths().next().is_some();
+        let hearth_out = has_hearth && !self.get_hearths().any(|(_, h)| h.is_burning());
+
+        self.settlement.hearth_out = hearth_out;
+        if hearth_out {
+            self.settlement.time_hearth_out += delta;
+        } else {
+            self.settlement.time_hearth_out = 0.0;
+        }
+    }
+
+    /// Creates a new idle worker of the species picked according to the settlement configuration.
+    /// It is up to the caller to assign the worker to a building or the hearth.
+    pub fn spawn_worker(
+        &mut self,
+        name: String,
+        pos: Pos,
+    ) -> Worker {
+        self.settlement.spawn_worker(name, pos)
+    }
+}
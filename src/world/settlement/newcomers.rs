@@ -0,0 +1,133 @@
+use log::info;
+
+use crate::{
+    config::buildings::Buildings,
+    world::{World, building::BuildingBehaviour, events::WorldEvent, worker::resolve::Resolve},
+};
+
+use super::Settlement;
+
+//the order matters - names are handed out one after another, so that the game stays deterministic
+const NAMES: [&str; 16] = [
+    "Ash", "Bramble", "Cinder", "Dell", "Ember", "Fern", "Gale", "Hazel", "Ivy", "Juniper", "Kestrel", "Linden",
+    "Moss", "Nettle", "Oak", "Rowan",
+];
+
+/// What newcomers look at, when deciding whether the settlement is worth joining
+pub struct NewcomersOutlook {
+    ///average resolve of the workers already living in the settlement
+    pub average_resolve: f32,
+    pub free_beds: usize,
+    ///fuel in stock, not counting what is burning right now
+    pub hearth_has_fuel: bool,
+}
+
+impl Settlement {
+    pub const NEWCOMERS_BASE_INTERVAL: f32 = 180.0;
+    pub const NEWCOMERS_MIN_GROUP: usize = 1;
+    pub const NEWCOMERS_MAX_GROUP: usize = 4;
+    ///newcomers are in no hurry to join a settlement that is about to run out of fuel
+    pub const NEWCOMERS_NO_FUEL_INTERVAL_MULTIPLIER: f32 = 2.0;
+
+    /// Happy settlements attract newcomers faster - at the bonus threshold twice as fast as
+    /// at the base resolve
+    pub fn newcomers_interval(outlook: &NewcomersOutlook) -> f32 {
+        let mood = (outlook.average_resolve / Resolve::BASE).clamp(0.5, 2.0);
+
+        let mut interval = Self::NEWCOMERS_BASE_INTERVAL / mood;
+        if !outlook.hearth_has_fuel {
+            interval *= Self::NEWCOMERS_NO_FUEL_INTERVAL_MULTIPLIER;
+        }
+        interval
+    }
+
+    /// One more newcomer for every two free beds
+    pub fn newcomers_group_size(outlook: &NewcomersOutlook) -> usize {
+        (Self::NEWCOMERS_MIN_GROUP + outlook.free_beds / 2).min(Self::NEWCOMERS_MAX_GROUP)
+    }
+
+    pub fn next_newcomer_name(&mut self) -> String {
+        let i = self.arrived_newcomers;
+        self.arrived_newcomers += 1;
+
+        let name = NAMES[i % NAMES.len()];
+        let generation = i / NAMES.len();
+        if generation == 0 {
+            name.to_string()
+        } else {
+            format!("{} {}", name, generation + 1)
+        }
+    }
+}
+
+impl World {
+    pub(in crate::world) fn update_newcomers(
+        &mut self,
+        delta: f32,
+    ) {
+        let outlook = if let Some(outlook) = self.newcomers_outlook() {
+            outlook
+        } else {
+            return; //no main hearth - nowhere for the newcomers to arrive
+        };
+
+        if self.settlement.hearth_out {
+            return; //nobody wants to join a settlement with a cold hearth
+        }
+
+        self.settlement.time_since_newcomers += delta;
+        if self.settlement.time_since_newcomers < Settlement::newcomers_interval(&outlook) {
+            return;
+        }
+        self.settlement.time_since_newcomers = 0.0;
+
+        let Some(hearth) = self
+            .shops
+            .iter_mut()
+            .find(|s| s.building_base.building == Buildings::MainHearth)
+        else {
+            return;
+        };
+        let hearth_pos = hearth.building_base.pos;
+        let BuildingBehaviour::Hearth(hearth) = &mut hearth.building_behaviour else {
+            return;
+        };
+
+        let mut names = Vec::new();
+        for _ in 0..Settlement::newcomers_group_size(&outlook) {
+            let name = self.settlement.next_newcomer_name();
+            let newcomer = self.settlement.spawn_worker(name.clone(), hearth_pos);
+            hearth.unassigned_workers.push_back(newcomer);
+            names.push(name);
+        }
+
+        info!("Newcomers have arrived at the hearth: {}", names.join(", "));
+        self.events.push(WorldEvent::NewcomersArrived { names, hearth_pos });
+    }
+
+    fn newcomers_outlook(&self) -> Option<NewcomersOutlook> {
+        let (_, hearth) = self.get_hearths().find(|(b, _)| b.building == Buildings::MainHearth)?;
+
+        let hearth_has_fuel = hearth.fuel() >= 1.0;
+
+        let workers = self.get_all_workers();
+        let average_resolve = if workers.is_empty() {
+            Resolve::BASE
+        } else {
+            workers.iter().map(|w| w.resolve().value).sum::<f32>() / workers.len() as f32
+        };
+
+        let beds: usize = self
+            .settlement_conditions()
+            .housing_capacity
+            .iter()
+            .map(|(_, beds)| beds)
+            .sum();
+
+        Some(NewcomersOutlook {
+            average_resolve,
+            free_beds: beds.saturating_sub(workers.len()),
+            hearth_has_fuel,
+        })
+    }
+}
@@ -0,0 +1,185 @@
+use std::collections::{HashMap, VecDeque};
+
+use crate::{
+    config::inventory::InventoryItems,
+    math::Pos,
+    world::{World, inventory::Inventory},
+};
+
+pub mod stock;
+
+/// Settlement-wide history of goods. Time is cut into buckets - every bucket remembers what was
+/// produced, consumed and spoiled during it, and how much of everything there was when it ended.
+/// Old buckets fall off the end, so the memory used stays the same no matter how long the game runs.
+pub struct Statistics {
+    ///time since the statistics started being recorded
+    pub time: f32,
+    pub current: StatisticsBucket,
+    ///closed buckets, oldest first
+    pub history: VecDeque<StatisticsBucket>,
+}
+
+pub struct StatisticsBucket {
+    pub start: f32,
+    pub produced: Inventory,
+    pub consumed: Inventory,
+    pub spoiled: Inventory,
+    ///total stock at the moment the bucket was closed. Empty for the current bucket
+    pub stock: Inventory,
+    ///seconds lost by the workers stuck in the crowd on each tile - see Traffic
+    pub congestion: HashMap<Pos, f32>,
+}
+
+/// Per minute, averaged over the requested window
+pub struct ItemRates {
+    pub produced: f32,
+    pub consumed: f32,
+    pub spoiled: f32,
+}
+
+impl ItemRates {
+    pub fn net(&self) -> f32 {
+        self.produced - self.consumed - self.spoiled
+    }
+}
+
+impl StatisticsBucket {
+    fn new(start: f32) -> Self {
+        Self {
+            start,
+            produced: Inventory::new(),
+            consumed: Inventory::new(),
+            spoiled: Inventory::new(),
+            stock: Inventory::new(),
+            congestion: HashMap::new(),
+        }
+    }
+}
+
+impl Default for Statistics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Statistics {
+    pub const BUCKET_LENGTH: f32 = 10.0;
+    ///10 minutes of history
+    pub const HISTORY_LENGTH: usize = 60;
+
+    pub fn new() -> Self {
+        Self {
+            time: 0.0,
+            current: StatisticsBucket::new(0.0),
+            history: VecDeque::new(),
+        }
+    }
+
+    pub fn record_produced<T>(
+        &mut self,
+        items: T,
+    ) where
+        T: IntoIterator<Item = (InventoryItems, f32)>,
+    {
+        self.current.produced.add_range(items);
+    }
+
+    pub fn record_consumed<T>(
+        &mut self,
+        items: T,
+    ) where
+        T: IntoIterator<Item = (InventoryItems, f32)>,
+    {
+        self.current.consumed.add_range(items);
+    }
+
+    pub fn record_spoiled<T>(
+        &mut self,
+        items: T,
+    ) where
+        T: IntoIterator<Item = (InventoryItems, f32)>,
+    {
+        self.current.spoiled.add_range(items);
+    }
+
+    pub fn record_congestion(
+        &mut self,
+        pos: Pos,
+        delay: f32,
+    ) {
+        *self.current.congestion.entry(pos).or_insert(0.0) += delay;
+    }
+
+    /// Tiles where the workers lost the most time in the last `window` seconds, worst first
+    pub fn bottlenecks(
+        &self,
+        window: f32,
+        count: usize,
+    ) -> Vec<(Pos, f32)> {
+        let buckets = (window / Self::BUCKET_LENGTH).ceil() as usize;
+        let mut delays: HashMap<Pos, f32> = HashMap::new();
+        let recent = self.history.iter().rev().take(buckets).chain([&self.current]);
+        for (pos, delay) in recent.flat_map(|b| b.congestion.iter()) {
+            *delays.entry(*pos).or_insert(0.0) += delay;
+        }
+
+        //sorted by the tile too - HashMap order is random
+        let mut ret: Vec<(Pos, f32)> = delays.into_iter().collect();
+        ret.sort_by(|l, r| r.1.total_cmp(&l.1).then(l.0.cmp(&r.0)));
+        ret.truncate(count);
+        ret
+    }
+
+    /// Rates of the last `window` seconds. The current, unfinished bucket counts too - otherwise
+    /// the charts would lag behind by up to a whole bucket
+    pub fn rates(
+        &self,
+        item: &InventoryItems,
+        window: f32,
+    ) -> ItemRates {
+        let buckets = (window / Self::BUCKET_LENGTH).ceil() as usize;
+        let closed: Vec<&StatisticsBucket> = self.history.iter().rev().take(buckets).collect();
+
+        let mut duration = self.time - self.current.start + closed.len() as f32 * Self::BUCKET_LENGTH;
+        if duration <= 0.0 {
+            duration = 1.0;
+        }
+        let per_minute = 60.0 / duration;
+
+        let sum = |get: fn(&StatisticsBucket) -> &Inventory| {
+            let total: f32 = closed.iter().map(|b| get(b).get(item)).sum();
+            (total + get(&self.current).get(item)) * per_minute
+        };
+
+        ItemRates {
+            produced: sum(|b| &b.produced),
+            consumed: sum(|b| &b.consumed),
+            spoiled: sum(|b| &b.spoiled),
+        }
+    }
+}
+
+impl World {
+    pub(in crate::world) fn update_statistics(
+        &mut self,
+        delta: f32,
+    ) {
+        self.statistics.time += delta;
+
+        //a long tick might span several buckets - all of them get closed, otherwise the rates would
+        //lag behind. Whatever happened during the tick goes to the first one
+        while self.statistics.time - self.statistics.current.start >= Statistics::BUCKET_LENGTH {
+            let stock = self.collect_stock().total();
+            let statistics = &mut self.statistics;
+
+            let next = StatisticsBucket::new(statistics.current.start + Statistics::BUCKET_LENGTH);
+            let mut closed = std::mem::replace(&mut statistics.current, next);
+            closed.stock = stock;
+
+            statistics.history.push_back(closed);
+            if statistics.history.len() > Statistics::HISTORY_LENGTH {
+                statistics.history.pop_front();
+            }
+        }
+    }
+}
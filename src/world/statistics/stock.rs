@@ -0,0 +1,88 @@
+use crate::{
+    config::inventory::InventoryItems,
+    world::{World, building::BuildingBehaviour, inventory::Inventory},
+};
+
+/// All the goods in the settlement, split by where they are
+pub struct Stock {
+    pub stores: Inventory,
+    ///outputs of buildings that are not stores - gathered or produced goods waiting to be stored
+    pub outputs: Inventory,
+    ///goods brought to producers and hearths, not used up yet
+    pub inputs: Inventory,
+    pub carried: Inventory,
+    pub on_ground: Inventory,
+    ///tools kept in the buildings for their workers - see ToolStock
+    pub tools: Inventory,
+}
+
+impl Stock {
+    pub fn get(
+        &self,
+        item: &InventoryItems,
+    ) -> f32 {
+        self.stores.get(item)
+            + self.outputs.get(item)
+            + self.inputs.get(item)
+            + self.carried.get(item)
+            + self.on_ground.get(item)
+            + self.tools.get(item)
+    }
+
+    pub fn total(&self) -> Inventory {
+        let mut total = Inventory::new();
+        for inventory in [
+            &self.stores,
+            &self.outputs,
+            &self.inputs,
+            &self.carried,
+            &self.on_ground,
+            &self.tools,
+        ] {
+            total.add_range(inventory.iter().map(|(item, amount)| (*item, *amount)));
+        }
+        total
+    }
+}
+
+impl World {
+    /// Goods promised to someone (see reservation.rs) still count - they have not moved yet.
+    /// Materials delivered to build zones do not, they are as good as used up
+    pub fn collect_stock(&self) -> Stock {
+        let mut stock = Stock {
+            stores: Inventory::new(),
+            outputs: Inventory::new(),
+            inputs: Inventory::new(),
+            carried: Inventory::new(),
+            on_ground: Inventory::new(),
+            tools: Inventory::new(),
+        };
+
+        let add = |target: &mut Inventory, source: &Inventory| {
+            target.add_range(source.iter().map(|(item, amount)| (*item, *amount)));
+        };
+
+        for shop in &self.shops {
+            match &shop.building_behaviour {
+                BuildingBehaviour::Store(_) => add(&mut stock.stores, &shop.building_base.output),
+                BuildingBehaviour::Producer(producer) => add(&mut stock.inputs, &producer.input),
+                BuildingBehaviour::Hearth(hearth) => add(&mut stock.inputs, &hearth.input),
+                _ => {}
+            }
+            if !shop.building_behaviour.is_store() {
+                add(&mut stock.outputs, &shop.building_base.output);
+            }
+            stock.tools.add(&InventoryItems::Tools, shop.building_base.tools.amount);
+        }
+
+        for worker in self.get_all_workers() {
+            add(&mut stock.carried, worker.inventory());
+        }
+
+        for pile in &self.item_piles {
+            add(&mut stock.on_ground, &pile.items);
+        }
+
+        stock
+    }
+}
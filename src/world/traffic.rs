@@ -0,0 +1,106 @@
+use std::collections::{HashMap, LinkedList};
+
+use crate::{
+    math::Pos,
+    world::{
+        World,
+        building::{Building, BuildingBehaviour},
+        worker::Worker,
+    },
+};
+
+/// Who stands where. Off by default - with it on, workers squeezing through a busy tile walk
+/// slower, so narrow roads and crowded store entrances become bottlenecks worth fixing.
+pub struct Traffic {
+    pub enabled: bool,
+    ///workers on each walkable tile, counted at the beginning of the tick. Workers inside the
+    ///buildings do not get in each other's way
+    occupancy: HashMap<Pos, u8>,
+}
+
+impl Default for Traffic {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Traffic {
+    ///every other worker on the tile makes stepping on it this much more expensive
+    pub const CONGESTION_PENALTY: f32 = 0.5;
+    ///no matter how big the crowd, crossing a tile takes at most this many times longer
+    pub const MAX_SLOWDOWN: f32 = 3.0;
+
+    pub fn new() -> Self {
+        Self {
+            enabled: false,
+            occupancy: HashMap::new(),
+        }
+    }
+
+    pub fn occupancy(
+        &self,
+        pos: &Pos,
+    ) -> u8 {
+        self.occupancy.get(pos).copied().unwrap_or(0)
+    }
+
+    /// Extra cost of stepping on the tile, for a worker who is already standing there
+    pub fn penalty(
+        &self,
+        pos: &Pos,
+        base_cost: f32,
+    ) -> f32 {
+        base_cost * Self::slowdown(self.occupancy(pos).saturating_sub(1))
+    }
+
+    /// Extra cost of a tile with the given number of workers on it, as a fraction of its usual cost
+    pub(crate) fn slowdown(workers: u8) -> f32 {
+        (workers as f32 * Self::CONGESTION_PENALTY).min(Self::MAX_SLOWDOWN - 1.0)
+    }
+}
+
+impl World {
+    /// Counts the workers on every tile, and slows down the ones who have just stepped on a busy
+    /// one. The delay goes to the statistics, so that the bottlenecks can be found. The new paths
+    /// go around the crowds - found again once the crowds move, see WorldMap::congestion_revision
+    pub(in crate::world) fn update_traffic(&mut self) {
+        if !self.traffic.enabled {
+            //might have just been switched off
+            self.map.set_crowd(HashMap::new());
+            return;
+        }
+
+        let mut occupancy: HashMap<Pos, u8> = HashMap::new();
+        for worker in all_workers_mut(&mut self.shops) {
+            let pos = match worker.transit_action_mut() {
+                Some(transit) => transit.current_pos(),
+                None => *worker.pos(),
+            };
+            if self.map.get(&pos).is_traversible() {
+                *occupancy.entry(pos).or_insert(0) += 1;
+            }
+        }
+        self.map.set_crowd(occupancy.clone());
+        self.traffic.occupancy = occupancy;
+
+        for worker in all_workers_mut(&mut self.shops) {
+            let Some(transit) = worker.transit_action_mut() else {
+                continue;
+            };
+            if let Some((pos, delay)) = transit.apply_congestion(&self.traffic) {
+                self.statistics.record_congestion(pos, delay);
+            }
+        }
+    }
+}
+
+fn all_workers_mut(shops: &mut LinkedList<Building>) -> Vec<&mut Worker> {
+    let mut ret = Vec::new();
+    for shop in shops.iter_mut() {
+        ret.extend(shop.building_base.workers.iter_mut());
+        if let BuildingBehaviour::Hearth(hearth) = &mut shop.building_behaviour {
+            ret.extend(hearth.unassigned_workers.iter_mut());
+        }
+    }
+    ret
+}
@@ -0,0 +1,49 @@
+use strum_macros::Display;
+
+use super::{Idle, Worker, worker_states::WorkerWithAction};
+
+/// Things that let the worker carry more than they could with bare hands
+#[derive(PartialEq, Eq, Clone, Copy, Display)]
+pub enum CarryUpgrade {
+    ///the workplace of the worker has tools in stock - see ToolStock
+    Tools,
+}
+
+impl CarryUpgrade {
+    ///extra weight the worker can carry
+    pub fn bonus(&self) -> f32 {
+        match self {
+            CarryUpgrade::Tools => 2.0,
+        }
+    }
+}
+
+impl Worker {
+    pub fn carry_capacity(&self) -> f32 {
+        let bonus: f32 = self.carry_upgrades().iter().map(|u| u.bonus()).sum();
+        WorkerWithAction::<Idle>::CARRY_CAPACITY + bonus
+    }
+
+    /// The same upgrade does not stack
+    pub fn add_carry_upgrade(
+        &mut self,
+        upgrade: CarryUpgrade,
+    ) {
+        if self.carry_upgrades().contains(&upgrade) {
+            return;
+        }
+
+        self.carry_upgrades_mut().push(upgrade);
+        self.inventory_mut().limit = self.carry_capacity();
+    }
+
+    pub fn remove_carry_upgrade(
+        &mut self,
+        upgrade: CarryUpgrade,
+    ) {
+        self.carry_upgrades_mut().retain(|u| *u != upgrade);
+        //whatever is carried already stays in the inventory - it just will not be able to pick up
+        //as much next time
+        self.inventory_mut().limit = self.carry_capacity();
+    }
+}
@@ -2,15 +2,20 @@ use worker_states::WorkerWithAction;
 
 use crate::{
     config::receipes::ProducedReceipe,
+    math::Pos,
     world::{
         actions::{
             BasicAction, TransitAction, building_action::BuildingAction, gathering_action::GatheringAction,
             taking_break_action::TakingBreakAction,
         },
-        building::build_zone::BuildZone,
+        inventory::reservation::{PendingPickup, Reservation},
     },
 };
 
+pub mod carry;
+pub mod repathing;
+pub mod resolve;
+pub mod skills;
 pub mod worker_impl;
 pub mod worker_state_transitions;
 pub mod worker_states;
@@ -31,6 +36,7 @@ pub enum Worker {
     //unassigned only actions
     SupplyingBuildZone(WorkerWithAction<SupplyingBuildZoneAction>),
     Building(WorkerWithAction<BuildingAction>),
+    Collecting(WorkerWithAction<CollectingAction>),
 }
 
 pub trait CanReturn {}
@@ -41,11 +47,15 @@ pub trait CanGetLost {}
 pub struct Idle();
 pub struct InHearth();
 pub struct LostAction(pub BasicAction);
-pub struct SupplyingAction(pub TransitAction);
+pub struct SupplyingAction(pub TransitAction, pub Reservation);
 pub struct StoringAction(pub TransitAction);
 pub struct ReturningAction(pub TransitAction);
 pub struct ProducingAction(pub BasicAction, pub ProducedReceipe);
-pub struct SupplyingBuildZoneAction(pub TransitAction, pub BuildZone);
+///going to pick up the item pile at the given position
+pub struct CollectingAction(pub TransitAction, pub Pos);
+///the materials are picked up from the buildings on the way to the build zone at the given
+///position
+pub struct SupplyingBuildZoneAction(pub TransitAction, pub Pos, pub Vec<PendingPickup>);
 
 impl CanReturn for SupplyingAction {}
 impl CanReturn for StoringAction {}
@@ -58,6 +68,7 @@ impl CanIdle for ProducingAction {} //finished production at the shop
 impl CanIdle for Idle {} //Still idle or, If was trying to transition to a state, but path not found
 
 impl CanStore for Idle {}
+impl CanStore for CollectingAction {} //picked up the pile, now bringing it to the store
 impl CanStore for LostAction {} //bring whatever is in the inventory to store before attempting to
 //come back to the shop
 
@@ -66,8 +77,10 @@ impl CanGetLost for StoringAction {} //was trying to return, but got lost
 impl CanGetLost for SupplyingAction {} //was trying to return, but got lost
 impl CanGetLost for GatheringAction {} //was trying to return, but got lost
 impl CanGetLost for TakingBreakAction {} //was trying to return, but got lost
+impl CanGetLost for ReturningAction {} //the way back got blocked
 
 impl CanGetLost for SupplyingBuildZoneAction {}
 impl CanGetLost for BuildingAction {}
 impl CanReturn for SupplyingBuildZoneAction {}
 impl CanReturn for BuildingAction {}
+impl CanGetLost for CollectingAction {}
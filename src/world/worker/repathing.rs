@@ -0,0 +1,246 @@
+use std::collections::LinkedList;
+
+use log::info;
+
+use crate::{
+    ai::pathfinding,
+    config::inventory::InventoryItems,
+    math::Pos,
+    world::{
+        World,
+        actions::TransitAction,
+        building::BuildingBehaviour,
+        inventory::reservation::{PendingPickup, Reservation},
+        world_map::{TileType, WorldMap},
+    },
+};
+
+use super::{SupplyingBuildZoneAction, Worker, worker_states::WorkerWithAction, worker_unassigned_state_transistions};
+
+/// Whatever a worker was holding on to, when they gave up on their way. Handed back once all the
+/// workers are processed - until then, the workers are borrowed from the shops
+enum Abandoned {
+    Reservation(Reservation),
+    SupplyTrip {
+        build_zone_pos: Pos,
+        pickups: Vec<PendingPickup>,
+        carried: Vec<(InventoryItems, f32)>,
+    },
+    BuildZone(Pos),
+    Resource(Pos),
+    Pile(Pos),
+}
+
+impl World {
+    /// Workers already on their way, whose path got blocked (most likely by a new building or a
+    /// build zone), look for a new way to the same destination. If there is none, they give up on
+    /// what they were doing and get lost
+    pub(in crate::world) fn update_paths(&mut self) {
+        let changed_tiles = self.map.take_changed_tiles();
+        if changed_tiles.is_empty() {
+            return;
+        }
+
+        let mut abandoned = Vec::new();
+        for shop in self.shops.iter_mut() {
+            update_worker_paths(
+                &mut shop.building_base.workers,
+                &self.map,
+                &changed_tiles,
+                &mut abandoned,
+            );
+            if let BuildingBehaviour::Hearth(hearth) = &mut shop.building_behaviour {
+                update_worker_paths(
+                    &mut hearth.unassigned_workers,
+                    &self.map,
+                    &changed_tiles,
+                    &mut abandoned,
+                );
+            }
+        }
+
+        for abandoned in abandoned {
+            self.hand_back(abandoned);
+        }
+    }
+
+    fn hand_back(
+        &mut self,
+        abandoned: Abandoned,
+    ) {
+        match abandoned {
+            Abandoned::Reservation(reservation) => self.release_reservation(reservation),
+            Abandoned::SupplyTrip {
+                build_zone_pos,
+                pickups,
+                carried,
+            } => {
+                //the carried materials were on their way too - being lost takes them to a store
+                let mut incoming = carried;
+                for pickup in pickups {
+                    incoming.extend(pickup.reservation.items.iter().copied());
+                    self.release_reservation(pickup.reservation);
+                }
+
+                if let Some(build_zone) = self.build_zone_mut(&build_zone_pos) {
+                    build_zone.cancel_incoming(incoming);
+                    build_zone.suppliers = build_zone.suppliers.saturating_sub(1);
+                }
+            }
+            Abandoned::BuildZone(pos) => {
+                if let Some(build_zone) = self.build_zone_mut(&pos) {
+                    build_zone.builders = build_zone.builders.saturating_sub(1);
+                }
+            }
+            Abandoned::Resource(pos) => {
+                if let TileType::Resource(_, _, being_cut) = self.map.get_mut(&pos) {
+                    *being_cut = false;
+                }
+            }
+            Abandoned::Pile(pos) => self.release_pile(pos),
+        }
+    }
+}
+
+fn update_worker_paths(
+    workers: &mut LinkedList<Worker>,
+    map: &WorldMap,
+    changed_tiles: &[Pos],
+    abandoned: &mut Vec<Abandoned>,
+) {
+    for _ in 0..workers.len() {
+        let worker = workers.pop_front().unwrap();
+        workers.push_back(update_path(worker, map, changed_tiles, abandoned));
+    }
+}
+
+fn update_path(
+    mut worker: Worker,
+    map: &WorldMap,
+    changed_tiles: &[Pos],
+    abandoned: &mut Vec<Abandoned>,
+) -> Worker {
+    let Some(transit) = worker.transit_action_mut() else {
+        return worker;
+    };
+    if !transit.is_blocked(map, changed_tiles) {
+        return worker;
+    }
+
+    let start = transit.current_pos();
+    let goal = *transit.path.last().unwrap();
+    let old_path = transit.path.clone();
+
+    let rerouted = if let Worker::SupplyingBuildZone(supplying) = &mut worker {
+        reroute_supply_trip(supplying, map, start)
+    } else if let Some(path) = find_new_path(map, &old_path, start, goal) {
+        *worker.transit_action_mut().unwrap() = TransitAction::new(path, map);
+        true
+    } else {
+        false
+    };
+
+    if rerouted {
+        info!(
+            "The way of {} to {} got blocked, found another one.",
+            worker.name(),
+            goal
+        );
+        return worker;
+    }
+
+    info!(
+        "The way of {} to {} got blocked, and there is no other. They give up.",
+        worker.name(),
+        goal
+    );
+    give_up(worker, goal, abandoned)
+}
+
+/// Workers heading into a building keep going through the same door - the doorstep is the tile
+/// right before it, and is never blocked (see WorldMap::can_build)
+fn find_new_path(
+    map: &WorldMap,
+    old_path: &[Pos],
+    start: Pos,
+    goal: Pos,
+) -> Option<Vec<Pos>> {
+    if map.get(&goal).is_traversible() || old_path.len() < 2 {
+        return pathfinding::a_star(map, start, goal);
+    }
+
+    let doorstep = old_path[old_path.len() - 2];
+    let mut path = if start == doorstep {
+        vec![start]
+    } else {
+        pathfinding::a_star(map, start, doorstep)?
+    };
+    path.push(goal);
+    Some(path)
+}
+
+/// The way to the build zone leads through the buildings of the pickups still ahead, in the same
+/// order - the pickups are made when the worker reaches their index on the new path
+fn reroute_supply_trip(
+    worker: &mut WorkerWithAction<SupplyingBuildZoneAction>,
+    map: &WorldMap,
+    start: Pos,
+) -> bool {
+    let SupplyingBuildZoneAction(transit, build_zone_pos, pickups) = &mut worker.action_data;
+    let stops: Vec<Pos> = pickups.iter().map(|p| p.reservation.store_pos).collect();
+    let Some((path, stop_indices)) =
+        worker_unassigned_state_transistions::path_through_stops(map, start, &stops, *build_zone_pos)
+    else {
+        return false;
+    };
+
+    for (pickup, path_index) in pickups.iter_mut().zip(stop_indices) {
+        pickup.path_index = path_index;
+    }
+    *transit = TransitAction::new(path, map);
+    true
+}
+
+/// What giving up means depends on what the worker was doing - whatever they held on to is handed
+/// back, and they get lost. Being lost takes care of the rest: the load goes to a store, and the
+/// worker back to their shop
+fn give_up(
+    worker: Worker,
+    goal: Pos,
+    abandoned: &mut Vec<Abandoned>,
+) -> Worker {
+    match worker {
+        Worker::Supplying(worker) => {
+            let (worker, action) = worker.to_lost_with_action_returned();
+            abandoned.push(Abandoned::Reservation(action.1));
+            worker
+        }
+        Worker::SupplyingBuildZone(worker) => {
+            let carried = worker.inventory.iter().map(|(i, a)| (*i, *a)).collect();
+            let (worker, action) = worker.to_lost_with_action_returned();
+            abandoned.push(Abandoned::SupplyTrip {
+                build_zone_pos: action.1,
+                pickups: action.2,
+                carried,
+            });
+            worker
+        }
+        Worker::Building(worker) => {
+            abandoned.push(Abandoned::BuildZone(worker.action_data.build_zone_pos));
+            worker.to_lost()
+        }
+        Worker::Gathering(worker) => {
+            abandoned.push(Abandoned::Resource(goal));
+            worker.to_lost()
+        }
+        Worker::Collecting(worker) => {
+            abandoned.push(Abandoned::Pile(worker.action_data.1));
+            worker.to_lost()
+        }
+        Worker::Storing(worker) => worker.to_lost(),
+        Worker::Returning(worker) => worker.to_lost(),
+        Worker::TakingBreak(worker) => worker.to_lost(),
+        //not on their way anywhere
+        Worker::Idle(_) | Worker::Producing(_) | Worker::Lost(_) => worker,
+    }
+}
@@ -0,0 +1,420 @@
+use std::collections::LinkedList;
+
+use log::info;
+use strum_macros::Display;
+
+use crate::{
+    config::{
+        buildings::Buildings,
+        inventory::{InventoryItems, ItemTag},
+        species::Species,
+    },
+    math::Pos,
+    world::{
+        World,
+        building::{
+            Building, BuildingBehaviour, HouseBehaviour, building_behaviour::hearth::HearthAction,
+            decoration::DecorationArea,
+        },
+    },
+};
+
+use super::Worker;
+
+/// Resolve describes how willing the worker is to stay in the settlement. It is recalculated every
+/// tick from the needs of the worker and the state of the settlement.
+pub struct Resolve {
+    pub value: f32,
+    pub factors: Vec<(ResolveFactor, f32)>,
+    ///set when the worker finishes a break - did they manage to get something to eat?
+    pub fed: bool,
+    pub ate_preferred_food: bool,
+    pub time_below_leaving_threshold: f32,
+    pub decoration_buffs: Vec<DecorationBuff>,
+    ///tiles the worker has stepped on since the last update - collected as they walk, so that the
+    ///end of a path is not lost when the walk finishes mid tick
+    pub walked_tiles: Vec<Pos>,
+}
+
+pub struct DecorationBuff {
+    ///position of the decoration that granted the buff
+    pub source: Pos,
+    pub strength: f32,
+    pub remaining: f32,
+    pub duration: f32,
+}
+
+#[derive(Display, PartialEq, Eq, Clone, Copy)]
+pub enum ResolveFactor {
+    Base,
+    Fed,
+    PreferredFood,
+    Hungry,
+    Exhausted,
+    HearthBurning,
+    HearthOut,
+    Housed,
+    PreferredHousing,
+    Decorations,
+    //TODO: services (tavern, temple...) once there are buildings providing them
+}
+
+/// The part of the settlement state the worker cares about. Gathered once per tick, so that I do
+/// not have to iterate over all the buildings for every worker.
+pub struct SettlementConditions {
+    pub hearth_burning: bool,
+    ///there is a hearth, but it is not burning
+    pub hearth_out: bool,
+    ///number of beds in each type of house
+    pub housing_capacity: Vec<(Buildings, usize)>,
+    pub decorations: Vec<DecorationArea>,
+}
+
+#[derive(PartialEq, Eq, Clone, Copy)]
+pub enum Housing {
+    Homeless,
+    Housed,
+    PreferredHouse,
+}
+
+impl ResolveFactor {
+    pub fn value(&self) -> f32 {
+        match self {
+            ResolveFactor::Base => Resolve::BASE,
+            ResolveFactor::Fed => 2.0,
+            ResolveFactor::PreferredFood => 1.0,
+            ResolveFactor::Hungry => -4.0,
+            ResolveFactor::Exhausted => -3.0,
+            ResolveFactor::HearthBurning => 1.0,
+            //before species resilience is taken into account
+            ResolveFactor::HearthOut => -2.0,
+            ResolveFactor::Housed => 2.0,
+            ResolveFactor::PreferredHousing => 1.0,
+            //depends on the decorations the worker has walked past - see decorations_bonus
+            ResolveFactor::Decorations => 0.0,
+        }
+    }
+}
+
+impl Default for Resolve {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Resolve {
+    pub const BASE: f32 = 5.0;
+
+    ///at or above this threshold workers are happy and work faster
+    pub const BONUS_THRESHOLD: f32 = 10.0;
+    pub const PRODUCTIVITY_BONUS: f32 = 1.25;
+
+    ///at or below this threshold workers start considering leaving the settlement
+    pub const LEAVING_THRESHOLD: f32 = 0.0;
+    pub const TIME_TO_LEAVE: f32 = 60.0;
+
+    ///every next decoration buff (from the strongest) counts only for this fraction of the previous one
+    pub const DECORATIONS_DIMINISHING_RETURNS: f32 = 0.5;
+
+    pub fn new() -> Self {
+        Self {
+            value: Self::BASE,
+            factors: vec![(ResolveFactor::Base, Self::BASE)],
+            fed: true,
+            ate_preferred_food: false,
+            time_below_leaving_threshold: 0.0,
+            decoration_buffs: Vec::new(),
+            walked_tiles: Vec::new(),
+        }
+    }
+
+    pub fn recalculate(
+        &mut self,
+        exhausted: bool,
+        housing: Housing,
+        species: Species,
+        conditions: &SettlementConditions,
+    ) {
+        let mut factors = vec![ResolveFactor::Base];
+
+        factors.push(if self.fed {
+            ResolveFactor::Fed
+        } else {
+            ResolveFactor::Hungry
+        });
+
+        if self.fed && self.ate_preferred_food {
+            factors.push(ResolveFactor::PreferredFood);
+        }
+
+        if exhausted {
+            factors.push(ResolveFactor::Exhausted);
+        }
+
+        if conditions.hearth_burning {
+            factors.push(ResolveFactor::HearthBurning);
+        }
+
+        if housing != Housing::Homeless {
+            factors.push(ResolveFactor::Housed);
+        }
+
+        if housing == Housing::PreferredHouse {
+            factors.push(ResolveFactor::PreferredHousing);
+        }
+
+        self.factors = factors.iter().map(|f| (*f, f.value())).collect();
+
+        if conditions.hearth_out {
+            let resilience = species.get_config().hearth_resilience;
+            let penalty = ResolveFactor::HearthOut.value() * (1.0 - resilience);
+            if penalty < 0.0 {
+                self.factors.push((ResolveFactor::HearthOut, penalty));
+            }
+        }
+
+        let decorations_bonus = self.decorations_bonus();
+        if decorations_bonus > 0.0 {
+            self.factors.push((ResolveFactor::Decorations, decorations_bonus));
+        }
+
+        self.value = self.factors.iter().map(|(_, v)| v).sum();
+    }
+
+    pub fn productivity(&self) -> f32 {
+        if self.value >= Self::BONUS_THRESHOLD {
+            Self::PRODUCTIVITY_BONUS
+        } else {
+            1.0
+        }
+    }
+
+    fn progress_leaving(
+        &mut self,
+        delta: f32,
+    ) {
+        if self.value <= Self::LEAVING_THRESHOLD {
+            self.time_below_leaving_threshold += delta;
+        } else {
+            self.time_below_leaving_threshold = 0.0;
+        }
+    }
+
+    pub fn wants_to_leave(&self) -> bool {
+        self.time_below_leaving_threshold >= Self::TIME_TO_LEAVE
+    }
+
+    /// Walking past the same decoration again does not stack - it only refreshes the buff
+    pub fn apply_decoration(
+        &mut self,
+        area: &DecorationArea,
+    ) {
+        if let Some(buff) = self.decoration_buffs.iter_mut().find(|b| b.source == area.pos) {
+            buff.remaining = buff.duration;
+            return;
+        }
+
+        self.decoration_buffs.push(DecorationBuff {
+            source: area.pos,
+            strength: area.config.resolve_bonus,
+            remaining: area.config.buff_duration,
+            duration: area.config.buff_duration,
+        });
+    }
+
+    fn progress_decoration_buffs(
+        &mut self,
+        delta: f32,
+    ) {
+        for buff in self.decoration_buffs.iter_mut() {
+            buff.remaining -= delta;
+        }
+        self.decoration_buffs.retain(|b| b.remaining > 0.0);
+    }
+
+    /// Buffs fade linearly. The strongest buff counts fully, every next one less and less, so
+    /// that spamming statues along the road is not the answer to everything.
+    pub fn decorations_bonus(&self) -> f32 {
+        let mut bonuses: Vec<f32> = self
+            .decoration_buffs
+            .iter()
+            .map(|b| b.strength * b.remaining / b.duration)
+            .collect();
+        bonuses.sort_by(|l, r| r.total_cmp(l));
+
+        let mut weight = 1.0;
+        let mut total = 0.0;
+        for bonus in bonuses {
+            total += bonus * weight;
+            weight *= Self::DECORATIONS_DIMINISHING_RETURNS;
+        }
+        total
+    }
+}
+
+impl World {
+    pub(in crate::world) fn update_resolve(
+        &mut self,
+        delta: f32,
+    ) {
+        let conditions = self.settlement_conditions();
+
+        //in the same order the workers are updated below
+        let mut species: Vec<Species> = Vec::new();
+        for shop in self.shops.iter() {
+            species.extend(shop.building_base.workers.iter().map(|w| *w.species()));
+            if let BuildingBehaviour::Hearth(hearth) = &shop.building_behaviour {
+                species.extend(hearth.unassigned_workers.iter().map(|w| *w.species()));
+            }
+        }
+        let mut free_beds = conditions.housing_capacity.clone();
+        let mut housing = assign_beds(&species, &mut free_beds).into_iter();
+
+        for shop in self.shops.iter_mut() {
+            update_workers(&mut shop.building_base.workers, &conditions, &mut housing, delta);
+
+            if let BuildingBehaviour::Hearth(hearth) = &mut shop.building_behaviour {
+                update_workers(&mut hearth.unassigned_workers, &conditions, &mut housing, delta);
+            }
+        }
+    }
+
+    pub(in crate::world) fn settlement_conditions(&self) -> SettlementConditions {
+        let hearth_burning = self.shops.iter().any(|s| {
+            if let BuildingBehaviour::Hearth(h) = &s.building_behaviour {
+                matches!(h.action, HearthAction::Burning(_))
+            } else {
+                false
+            }
+        });
+        let hearth_out = self.settlement.hearth_out;
+
+        //Vec rather than HashMap - iteration order has to be deterministic
+        let mut housing_capacity: Vec<(Buildings, usize)> = Vec::new();
+        for shop in self.shops.iter().filter(|s| s.building_behaviour.is_house()) {
+            let house_type = shop.building_base.building;
+            if let Some((_, beds)) = housing_capacity.iter_mut().find(|(b, _)| *b == house_type) {
+                *beds += HouseBehaviour::CAPACITY as usize;
+            } else {
+                housing_capacity.push((house_type, HouseBehaviour::CAPACITY as usize));
+            }
+        }
+
+        SettlementConditions {
+            hearth_burning,
+            hearth_out,
+            housing_capacity,
+            decorations: self.get_decoration_areas(),
+        }
+    }
+}
+
+fn update_workers(
+    workers: &mut LinkedList<Worker>,
+    conditions: &SettlementConditions,
+    housing: &mut impl Iterator<Item = Housing>,
+    delta: f32,
+) {
+    for _ in 0..workers.len() {
+        let mut worker = workers.pop_front().unwrap();
+
+        let species = *worker.species();
+        let housing = housing.next().unwrap_or(Housing::Homeless);
+
+        let exhausted = *worker.exhausted();
+        let resolve = worker.resolve_mut();
+
+        //decorations are applied as workers walk past them
+        resolve.progress_decoration_buffs(delta);
+        for tile in std::mem::take(&mut resolve.walked_tiles).iter() {
+            for area in conditions.decorations.iter().filter(|a| a.covers(tile)) {
+                resolve.apply_decoration(area);
+            }
+        }
+
+        resolve.recalculate(exhausted, housing, species, conditions);
+        resolve.progress_leaving(delta);
+
+        //only idle workers can leave - otherwise they could take a build zone or a resource
+        //reservation with them
+        if resolve.wants_to_leave() && matches!(worker, Worker::Idle(_)) {
+            info!(
+                "{} has lost all hope and is leaving the settlement! Resolve: {}",
+                worker.name(),
+                worker.resolve().value
+            );
+            continue;
+        }
+
+        workers.push_back(worker);
+    }
+}
+
+/// Houses are not assigned to particular workers - the beds are handed out every tick, in two
+/// passes. First everyone who can sleep in the house built for their species gets a bed there, only
+/// then the rest take whatever beds are left - otherwise a worker processed early could take the
+/// last bed of a house someone else prefers. The order of workers is stable between ticks, so the
+/// same workers stay housed.
+fn assign_beds(
+    species: &[Species],
+    free_beds: &mut [(Buildings, usize)],
+) -> Vec<Housing> {
+    let mut housing: Vec<Housing> = species.iter().map(|s| take_preferred_bed(free_beds, *s)).collect();
+
+    for housing in housing.iter_mut().filter(|h| **h == Housing::Homeless) {
+        if let Some((_, beds)) = free_beds.iter_mut().find(|(_, beds)| *beds > 0) {
+            *beds -= 1;
+            *housing = Housing::Housed;
+        }
+    }
+
+    housing
+}
+
+fn take_preferred_bed(
+    free_beds: &mut [(Buildings, usize)],
+    species: Species,
+) -> Housing {
+    let preferred_housing = species.get_config().preferred_housing;
+
+    if let Some((_, beds)) = free_beds
+        .iter_mut()
+        .find(|(b, beds)| *b == preferred_housing && *beds > 0)
+    {
+        *beds -= 1;
+        return Housing::PreferredHouse;
+    }
+
+    Housing::Homeless
+}
+
+/// Takes a single portion of food from any store - preferred food first. Returns None if there was
+/// nothing to eat.
+pub fn eat(
+    shops: &mut LinkedList<Building>,
+    preferred_food: &[InventoryItems],
+) -> Option<InventoryItems> {
+    let food = InventoryItems::with_tag(ItemTag::Food);
+    let preferred: Vec<InventoryItems> = food.iter().filter(|f| preferred_food.contains(f)).copied().collect();
+
+    take_one_of(shops, &preferred).or_else(|| take_one_of(shops, &food))
+}
+
+fn take_one_of(
+    shops: &mut LinkedList<Building>,
+    items: &[InventoryItems],
+) -> Option<InventoryItems> {
+    for shop in shops.iter_mut() {
+        if !shop.building_behaviour.is_store() {
+            continue;
+        }
+
+        let item = items.iter().find(|f| shop.building_base.output.available(f) >= 1.0);
+
+        if let Some(item) = item {
+            shop.building_base.output.remove(item, 1.0);
+            return Some(*item);
+        }
+    }
+    None
+}
@@ -0,0 +1,164 @@
+use std::{collections::HashMap, fmt::Display};
+
+use log::info;
+
+use crate::{
+    config::{
+        buildings::Buildings,
+        inventory::InventoryItems,
+        species::{Specialisation, Species},
+    },
+    world::building::BuildingBehaviourDiscriminants,
+};
+
+use super::{Worker, worker_states::WorkerWithAction};
+
+/// Kinds of work a worker gets better at by doing it
+#[derive(Hash, PartialEq, Eq, Clone, Copy)]
+pub enum Skill {
+    Gathering,
+    Woodcutting,
+    ///production in the buildings with the given specialisation - None for the ones with none
+    Production(Option<Specialisation>),
+    Construction,
+}
+
+impl Display for Skill {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        match self {
+            Skill::Gathering => write!(f, "Gathering"),
+            Skill::Woodcutting => write!(f, "Woodcutting"),
+            Skill::Production(Some(specialisation)) => write!(f, "Production ({})", specialisation),
+            Skill::Production(None) => write!(f, "Production"),
+            Skill::Construction => write!(f, "Construction"),
+        }
+    }
+}
+
+impl Skill {
+    /// The skill used by the workers of the building. Buildings that do not make the workers do
+    /// anything skillful (stores, houses) have none
+    pub fn for_workplace(workplace: Buildings) -> Option<Skill> {
+        let data = workplace.get_data();
+        match data.building_behaviour {
+            BuildingBehaviourDiscriminants::Gatherer => {
+                if data.gathered_resource_types.contains(&InventoryItems::Wood) {
+                    Some(Skill::Woodcutting)
+                } else {
+                    Some(Skill::Gathering)
+                }
+            }
+            BuildingBehaviourDiscriminants::Producer => Some(Skill::Production(data.specialisations.first().copied())),
+            _ => None,
+        }
+    }
+}
+
+/// Experience is the time spent working - walking around does not count
+#[derive(Default)]
+pub struct Skills {
+    experience: HashMap<Skill, f32>,
+}
+
+impl Skills {
+    ///experience needed for each level, the first one is free
+    pub const LEVEL_EXPERIENCE: [f32; 6] = [0.0, 60.0, 180.0, 360.0, 600.0, 900.0];
+    ///every level makes the work this much faster
+    pub const SPEED_BONUS_PER_LEVEL: f32 = 0.1;
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn experience(
+        &self,
+        skill: Skill,
+    ) -> f32 {
+        self.experience.get(&skill).copied().unwrap_or(0.0)
+    }
+
+    pub fn add_experience(
+        &mut self,
+        skill: Skill,
+        amount: f32,
+    ) {
+        *self.experience.entry(skill).or_insert(0.0) += amount;
+    }
+
+    /// 0 for a beginner, up to 5
+    pub fn level(
+        &self,
+        skill: Skill,
+    ) -> u8 {
+        let experience = self.experience(skill);
+        let reached = Self::LEVEL_EXPERIENCE.iter().filter(|e| experience >= **e).count();
+        (reached - 1) as u8
+    }
+
+    pub fn speed_bonus(
+        &self,
+        skill: Skill,
+    ) -> f32 {
+        1.0 + self.level(skill) as f32 * Self::SPEED_BONUS_PER_LEVEL
+    }
+
+    /// Every skill the worker has any experience in, with its level and experience - ordered by
+    /// the experience, the best skill first
+    pub fn all(&self) -> Vec<(Skill, u8, f32)> {
+        let mut ret: Vec<(Skill, u8, f32)> = self
+            .experience
+            .iter()
+            .map(|(skill, experience)| (*skill, self.level(*skill), *experience))
+            .collect();
+        //HashMap order is random - the name breaks the ties, so that the result is deterministic
+        ret.sort_by(|l, r| r.2.total_cmp(&l.2).then_with(|| l.0.to_string().cmp(&r.0.to_string())));
+        ret
+    }
+}
+
+impl<T> WorkerWithAction<T> {
+    ///multiplier for the actual work (producing, gathering, building) - walking is not affected
+    pub(super) fn work_speed(
+        &self,
+        workplace: Buildings,
+        skill: Skill,
+    ) -> f32 {
+        let specialisation_bonus = if self.species.is_specialised_in(workplace) {
+            Species::SPECIALISATION_SPEED_BONUS
+        } else {
+            1.0
+        };
+
+        self.resolve.productivity() * specialisation_bonus * self.skills.speed_bonus(skill)
+    }
+
+    /// The worker has spent the time working - they get a bit better at it
+    pub(super) fn practice(
+        &mut self,
+        skill: Skill,
+        delta: f32,
+    ) {
+        let level = self.skills.level(skill);
+        self.skills.add_experience(skill, delta);
+        if self.skills.level(skill) > level {
+            info!(
+                "{} has reached level {} in {}.",
+                self.name,
+                self.skills.level(skill),
+                skill
+            );
+        }
+    }
+}
+
+impl Worker {
+    pub fn skill_level(
+        &self,
+        skill: Skill,
+    ) -> u8 {
+        self.skills().level(skill)
+    }
+}
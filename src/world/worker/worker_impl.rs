@@ -1,9 +1,16 @@
 use crate::{
+    config::species::Species,
     math::Pos,
-    world::{actions::BasicAction, inventory::Inventory},
+    world::{
+        actions::{
+            BasicAction, TransitAction, building_action::BuildingActionInternalState,
+            gathering_action::GatheringActionInternalState, taking_break_action::TakingBreakActionInternalState,
+        },
+        inventory::Inventory,
+    },
 };
 
-use super::Worker;
+use super::{Worker, carry::CarryUpgrade, resolve::Resolve, skills::Skills};
 
 //use #![feature(macro_metavar_expr_concat)] once that becomes stable, rather than paste
 use paste::paste;
@@ -23,6 +30,7 @@ macro_rules! worker_impl {
                     Worker::Lost(w) => &w.$name,
                     Worker::SupplyingBuildZone(w) => &w.$name,
                     Worker::Building(w) => &w.$name,
+                    Worker::Collecting(w) => &w.$name,
                 }
             }
 
@@ -38,6 +46,7 @@ macro_rules! worker_impl {
                     Worker::Lost(w) => &mut w.$name,
                     Worker::SupplyingBuildZone(w) => &mut w.$name,
                     Worker::Building(w) => &mut w.$name,
+                    Worker::Collecting(w) => &mut w.$name,
                 }
             }
         }
@@ -47,6 +56,36 @@ macro_rules! worker_impl {
 
 worker_impl!(pos, Pos);
 worker_impl!(inventory, Inventory);
+worker_impl!(carry_upgrades, Vec<CarryUpgrade>);
 worker_impl!(name, String);
+worker_impl!(species, Species);
 worker_impl!(break_progress, BasicAction);
 worker_impl!(exhausted, bool);
+worker_impl!(resolve, Resolve);
+worker_impl!(skills, Skills);
+
+impl Worker {
+    /// The transit action of the worker, if they are currently walking somewhere
+    pub fn transit_action_mut(&mut self) -> Option<&mut TransitAction> {
+        match self {
+            Worker::Supplying(w) => Some(&mut w.action_data.0),
+            Worker::Storing(w) => Some(&mut w.action_data.0),
+            Worker::Returning(w) => Some(&mut w.action_data.0),
+            Worker::SupplyingBuildZone(w) => Some(&mut w.action_data.0),
+            Worker::Collecting(w) => Some(&mut w.action_data.0),
+            Worker::Gathering(w) => match &mut w.action_data.state {
+                GatheringActionInternalState::Going(transit_action) => Some(transit_action),
+                GatheringActionInternalState::Gathering(_) => None,
+            },
+            Worker::TakingBreak(w) => match &mut w.action_data.state {
+                TakingBreakActionInternalState::Going(transit_action) => Some(transit_action),
+                TakingBreakActionInternalState::TakingBreak(_) => None,
+            },
+            Worker::Building(w) => match &mut w.action_data.state {
+                BuildingActionInternalState::Going(transit_action) => Some(transit_action),
+                BuildingActionInternalState::Building => None,
+            },
+            Worker::Idle(_) | Worker::Producing(_) | Worker::Lost(_) => None,
+        }
+    }
+}
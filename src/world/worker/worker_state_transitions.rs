@@ -1,23 +1,26 @@
 use log::info;
 
 use crate::{
-    config::{inventory::InventoryItems, receipes::ProducedReceipe},
+    config::{buildings::Buildings, inventory::InventoryItems, receipes::ProducedReceipe},
+    data_helpers::to_string::ToString,
     math::Pos,
     world::{
         World,
         actions::{
             ActionResult, TransitActionResult,
-            building_action::{BuildingAction, BuildingActionResult},
-            gathering_action::{GatheringAction, GatheringActionResult},
+            building_action::{BuildingAction, BuildingActionInternalState, BuildingActionResult},
+            gathering_action::{GatheringAction, GatheringActionInternalState, GatheringActionResult},
             taking_break_action::{TakingBreakAction, TakingBreakActionResult},
         },
+        building::tools::ToolStock,
+        inventory::reservation::PendingPickup,
         worker::Worker,
     },
 };
 
 use super::{
-    Idle, LostAction, ProducingAction, ReturningAction, StoringAction, SupplyingAction, SupplyingBuildZoneAction,
-    worker_states::WorkerWithAction,
+    CollectingAction, Idle, LostAction, ProducingAction, ReturningAction, StoringAction, SupplyingAction,
+    SupplyingBuildZoneAction, carry::CarryUpgrade, resolve, skills::Skill, worker_states::WorkerWithAction,
 };
 
 pub enum WorkerActionResult {
@@ -31,24 +34,34 @@ impl Worker {
     pub const TIME_TO_BREAK: f32 = 120.0;
 
     pub fn continue_action(
-        self,
+        mut self,
         assigned_shop_pos: Pos,
+        workplace: Buildings,
+        tools: &mut ToolStock,
         delta: f32,
         world: &mut World,
         is_hearth: bool,
     ) -> (Worker, WorkerActionResult) {
+        //the tools of the workplace come with straps and baskets
+        if tools.is_equipped() {
+            self.add_carry_upgrade(CarryUpgrade::Tools);
+        } else {
+            self.remove_carry_upgrade(CarryUpgrade::Tools);
+        }
+
         match self {
             Worker::Returning(worker) => handle_returning(worker, delta),
             Worker::Storing(worker) => handle_storing(worker, delta, world, assigned_shop_pos),
             Worker::Supplying(worker) => handle_supplying(worker, delta, world, assigned_shop_pos),
-            Worker::Gathering(worker) => handle_gathering(worker, delta, world, assigned_shop_pos),
-            Worker::Producing(worker) => handle_producing(worker, delta),
+            Worker::Gathering(worker) => handle_gathering(worker, delta, world, assigned_shop_pos, workplace, tools),
+            Worker::Producing(worker) => handle_producing(worker, delta, world, workplace, tools),
             Worker::TakingBreak(worker) => handle_taking_break(worker, delta, world, assigned_shop_pos),
             Worker::Idle(worker) => handle_idle(worker, delta, world, is_hearth),
             Worker::Lost(worker) => handle_lost(worker, delta, world, assigned_shop_pos),
             //only unassigned
             Worker::SupplyingBuildZone(worker) => handle_supplying_build_zone(worker, delta, world, assigned_shop_pos),
-            Worker::Building(worker) => handle_building(worker, delta, world, assigned_shop_pos),
+            Worker::Building(worker) => handle_building(worker, delta, world, assigned_shop_pos, workplace),
+            Worker::Collecting(worker) => handle_collecting(worker, delta, world),
         }
     }
 }
@@ -61,6 +74,35 @@ fn handle_supplying_build_zone(
 ) -> (Worker, WorkerActionResult) {
     worker.progress_break_requirement(delta);
     let result = worker.action_data.0.continue_action(delta);
+    worker
+        .resolve
+        .walked_tiles
+        .extend_from_slice(worker.action_data.0.take_traversed_tiles());
+
+    //passing by a building - pick up the reserved materials
+    let current_index = worker.action_data.0.current_index();
+    let completed = matches!(result, TransitActionResult::Completed(_));
+    let (reached, pending): (Vec<PendingPickup>, Vec<PendingPickup>) = std::mem::take(&mut worker.action_data.2)
+        .into_iter()
+        .partition(|p| completed || current_index >= p.path_index);
+    worker.action_data.2 = pending;
+    for pickup in reached {
+        let reserved = pickup.reservation.items.clone();
+        let items = world.commit_reservation(pickup.reservation);
+        info!(
+            "{} has picked up {} for the build zone.",
+            worker.name,
+            items.to_string()
+        );
+
+        //the building is gone - someone else will have to bring these
+        if items.is_empty()
+            && let Some(build_zone) = world.build_zone_mut(&worker.action_data.1)
+        {
+            build_zone.cancel_incoming(reserved);
+        }
+        worker.inventory.add_range(items);
+    }
 
     match result {
         TransitActionResult::InProgress(pos) => {
@@ -69,14 +111,21 @@ fn handle_supplying_build_zone(
         }
         TransitActionResult::Completed(pos) => {
             worker.pos = pos;
-            let (mut worker, mut supplying_action) =
-                worker.try_returning_with_action_returned(&world.map, assigned_shop_pos);
+            let items: Vec<(InventoryItems, f32)> = worker.inventory.drain().collect();
 
-            let items = worker.inventory_mut().drain();
-            supplying_action.1.materials_delivered.add_range(items);
-            world.build_zones.push_back(supplying_action.1);
+            if let Some(build_zone) = world.build_zone_mut(&worker.action_data.1) {
+                build_zone.cancel_incoming(items.iter().copied());
+                build_zone.materials_delivered.add_range(items);
+                build_zone.suppliers = build_zone.suppliers.saturating_sub(1);
+            } else {
+                //should not happen - the zone cannot be built without these
+                world.drop_items(pos, items);
+            }
 
-            (worker, WorkerActionResult::InProgress)
+            (
+                worker.try_returning(&world.map, assigned_shop_pos),
+                WorkerActionResult::InProgress,
+            )
         }
     }
 }
@@ -86,9 +135,32 @@ fn handle_building(
     delta: f32,
     world: &mut World,
     assigned_shop_pos: Pos,
+    workplace: Buildings,
 ) -> (Worker, WorkerActionResult) {
     worker.progress_break_requirement(delta);
-    let result = worker.action_data.continue_action(world, delta);
+    if worker.requires_break() {
+        //the others carry on without them
+        let build_zone_pos = worker.action_data.build_zone_pos;
+        if let Some(build_zone) = world.build_zone_mut(&build_zone_pos) {
+            build_zone.builders = build_zone.builders.saturating_sub(1);
+        }
+        info!(
+            "{} needs a break and leaves the build zone at {}.",
+            worker.name, build_zone_pos
+        );
+        return (
+            worker.try_returning(&world.map, assigned_shop_pos),
+            WorkerActionResult::InProgress,
+        );
+    }
+
+    if let BuildingActionInternalState::Building = worker.action_data.state {
+        worker.practice(Skill::Construction, delta);
+    }
+    let work_speed = worker.work_speed(workplace, Skill::Construction);
+    let result = worker
+        .action_data
+        .continue_action(world, delta, work_speed, &mut worker.resolve.walked_tiles);
 
     match result {
         BuildingActionResult::InProgress(pos) => {
@@ -105,19 +177,47 @@ fn handle_building(
     }
 }
 
+fn handle_collecting(
+    mut worker: WorkerWithAction<CollectingAction>,
+    delta: f32,
+    world: &mut World,
+) -> (Worker, WorkerActionResult) {
+    worker.progress_break_requirement(delta);
+    let result = worker.action_data.0.continue_action(delta);
+    worker
+        .resolve
+        .walked_tiles
+        .extend_from_slice(worker.action_data.0.take_traversed_tiles());
+
+    match result {
+        TransitActionResult::InProgress(pos) => {
+            worker.pos = pos;
+            (Worker::Collecting(worker), WorkerActionResult::InProgress)
+        }
+        TransitActionResult::Completed(pos) => {
+            worker.pos = pos;
+            world.pick_up_pile(worker.action_data.1, &mut worker.inventory);
+            info!(
+                "{} has picked up {} from the ground and is now bringing it to the store.",
+                worker.name, worker.inventory
+            );
+
+            (worker.try_storing(world), WorkerActionResult::InProgress)
+        }
+    }
+}
+
 fn handle_lost(
     mut worker: WorkerWithAction<LostAction>,
     delta: f32,
-    world: &World,
+    world: &mut World,
     assigned_shop_pos: Pos,
 ) -> (Worker, WorkerActionResult) {
     worker.progress_break_requirement(delta);
     let retry_result = worker.action_data.0.continue_action(delta);
 
     match retry_result {
-        ActionResult::InProgress => {
-            return (Worker::Lost(worker), WorkerActionResult::InProgress);
-        }
+        ActionResult::InProgress => (Worker::Lost(worker), WorkerActionResult::InProgress),
         ActionResult::Completed => {
             info!("{} is lost and trying to find a way", worker.name);
             if worker.inventory.is_empty() {
@@ -136,7 +236,10 @@ fn handle_lost(
                 worker.name
             );
 
-            (worker.try_storing(world), WorkerActionResult::InProgress)
+            (
+                worker.try_storing(world, assigned_shop_pos),
+                WorkerActionResult::InProgress,
+            )
         }
     }
 }
@@ -147,6 +250,10 @@ fn handle_returning(
 ) -> (Worker, WorkerActionResult) {
     worker.progress_break_requirement(delta);
     let result = worker.action_data.0.continue_action(delta);
+    worker
+        .resolve
+        .walked_tiles
+        .extend_from_slice(worker.action_data.0.take_traversed_tiles());
 
     match result {
         TransitActionResult::InProgress(pos) => {
@@ -168,7 +275,7 @@ fn handle_returning(
         }
     }
 
-    return (Worker::Returning(worker), WorkerActionResult::InProgress);
+    (Worker::Returning(worker), WorkerActionResult::InProgress)
 }
 
 fn handle_storing(
@@ -179,11 +286,15 @@ fn handle_storing(
 ) -> (Worker, WorkerActionResult) {
     worker.progress_break_requirement(delta);
     let result = worker.action_data.0.continue_action(delta);
+    worker
+        .resolve
+        .walked_tiles
+        .extend_from_slice(worker.action_data.0.take_traversed_tiles());
 
     match result {
         TransitActionResult::InProgress(pos) => {
             worker.pos = pos;
-            return (Worker::Storing(worker), WorkerActionResult::InProgress);
+            (Worker::Storing(worker), WorkerActionResult::InProgress)
         }
         TransitActionResult::Completed(pos) => {
             worker.pos = pos;
@@ -196,7 +307,7 @@ fn handle_storing(
             let store = if let Some(store) = world
                 .shops
                 .iter_mut()
-                .find(|s| s.building_behaviour.is_store() && s.building_base.pos == worker.pos)
+                .find(|s| s.building_behaviour.is_store() && s.building_base.contains(&worker.pos))
             {
                 store
             } else {
@@ -214,10 +325,10 @@ fn handle_storing(
             store.building_base.output.add_range(items);
             worker.inventory.inv.clear();
 
-            return (
+            (
                 worker.try_returning(&world.map, assigned_shop_pos),
                 WorkerActionResult::InProgress,
-            );
+            )
         }
     }
 }
@@ -230,25 +341,32 @@ fn handle_supplying(
 ) -> (Worker, WorkerActionResult) {
     worker.progress_break_requirement(delta);
     let result = worker.action_data.0.continue_action(delta);
+    worker
+        .resolve
+        .walked_tiles
+        .extend_from_slice(worker.action_data.0.take_traversed_tiles());
 
     match result {
         TransitActionResult::InProgress(pos) => {
             worker.pos = pos;
-            return (Worker::Supplying(worker), WorkerActionResult::InProgress);
+            (Worker::Supplying(worker), WorkerActionResult::InProgress)
         }
         TransitActionResult::Completed(pos) => {
             worker.pos = pos;
 
+            let (mut worker, supplying_action) =
+                worker.try_returning_with_action_returned(&world.map, assigned_shop_pos);
+            let items = world.commit_reservation(supplying_action.1);
+
             info!(
                 "{} has taken reserved items at {} and is now returning.",
-                worker.name, pos
+                worker.name(),
+                pos
             );
-            info!("Reserved items: {}", worker.inventory);
+            info!("Reserved items: {}", items.to_string());
+            worker.inventory_mut().add_range(items);
 
-            return (
-                worker.try_returning(&world.map, assigned_shop_pos),
-                WorkerActionResult::InProgress,
-            );
+            (worker, WorkerActionResult::InProgress)
         }
     }
 }
@@ -258,17 +376,33 @@ fn handle_gathering(
     delta: f32,
     world: &mut World,
     assigned_shop_pos: Pos,
+    workplace: Buildings,
+    tools: &mut ToolStock,
 ) -> (Worker, WorkerActionResult) {
     worker.progress_break_requirement(delta);
-    let result = worker.action_data.continue_action(&mut world.map, delta);
+    let skill = Skill::for_workplace(workplace).unwrap_or(Skill::Gathering);
+    let efficiency = workplace.get_data().gathering_efficiency;
+    let work_speed = worker.work_speed(workplace, skill) * tools.speed_bonus() * efficiency;
+    if let GatheringActionInternalState::Gathering(_) = worker.action_data.state {
+        worker.practice(skill, delta);
+        tools.wear(delta, &mut world.statistics);
+    }
+    let result =
+        worker
+            .action_data
+            .continue_action(&mut world.map, delta, work_speed, &mut worker.resolve.walked_tiles);
 
     match result {
         GatheringActionResult::InProgress(pos) => {
             worker.pos = pos;
 
-            return (Worker::Gathering(worker), WorkerActionResult::InProgress);
+            (Worker::Gathering(worker), WorkerActionResult::InProgress)
         }
         GatheringActionResult::Completed(inv) => {
+            //the tools might have worn out just now - this is the last gathering they help with
+            let yield_bonus = tools.yield_bonus();
+            let inv: Vec<(InventoryItems, f32)> = inv.into_iter().map(|(i, a)| (i, a * yield_bonus)).collect();
+            world.statistics.record_produced(inv.iter().copied());
             worker.inventory.add_range(inv);
 
             info!(
@@ -277,10 +411,10 @@ fn handle_gathering(
             );
             info!("Gathered items: {}", worker.inventory);
 
-            return (
+            (
                 worker.try_returning(&world.map, assigned_shop_pos),
                 WorkerActionResult::InProgress,
-            );
+            )
         }
     }
 }
@@ -288,19 +422,24 @@ fn handle_gathering(
 fn handle_producing(
     mut worker: WorkerWithAction<ProducingAction>,
     delta: f32,
+    world: &mut World,
+    workplace: Buildings,
+    tools: &mut ToolStock,
 ) -> (Worker, WorkerActionResult) {
     worker.progress_break_requirement(delta);
-    let result = worker.action_data.0.continue_action(delta);
+    let skill = Skill::for_workplace(workplace).unwrap_or(Skill::Production(None));
+    let work_speed = worker.work_speed(workplace, skill) * tools.speed_bonus();
+    worker.practice(skill, delta);
+    tools.wear(delta, &mut world.statistics);
+    let result = worker.action_data.0.continue_action(delta * work_speed);
 
     match result {
-        ActionResult::InProgress => {
-            return (Worker::Producing(worker), WorkerActionResult::InProgress);
-        }
+        ActionResult::InProgress => (Worker::Producing(worker), WorkerActionResult::InProgress),
         ActionResult::Completed => {
             info!("{} has completed production.", worker.name);
             let (worker, action_data) = worker.to_idle_with_action_returned();
 
-            return (worker, WorkerActionResult::ProductionComplete(action_data.1));
+            (worker, WorkerActionResult::ProductionComplete(action_data.1))
         }
     }
 }
@@ -311,13 +450,20 @@ fn handle_taking_break(
     world: &mut World,
     assigned_shop_pos: Pos,
 ) -> (Worker, WorkerActionResult) {
-    let result = worker.action_data.continue_action(delta);
+    let break_speed = if world.settlement.hearth_out {
+        TakingBreakAction::HEARTH_OUT_BREAK_SPEED
+    } else {
+        1.0
+    };
+    let result = worker
+        .action_data
+        .continue_action(delta, break_speed, &mut worker.resolve.walked_tiles);
 
     match result {
         TakingBreakActionResult::InProgress(pos) => {
             worker.pos = pos;
 
-            return (Worker::TakingBreak(worker), WorkerActionResult::InProgress);
+            (Worker::TakingBreak(worker), WorkerActionResult::InProgress)
         }
         TakingBreakActionResult::Completed => {
             info!(
@@ -327,10 +473,25 @@ fn handle_taking_break(
             worker.break_progress.progress = 0.0;
             worker.exhausted = false;
 
-            return (
+            let preferred_food = &worker.species.get_config().preferred_food;
+            match resolve::eat(&mut world.shops, preferred_food) {
+                Some(food) => {
+                    info!("{} has eaten {} during the break.", worker.name, food);
+                    world.statistics.record_consumed([(food, 1.0)]);
+                    worker.resolve.fed = true;
+                    worker.resolve.ate_preferred_food = preferred_food.contains(&food);
+                }
+                None => {
+                    info!("{} found nothing to eat and is hungry!", worker.name);
+                    worker.resolve.fed = false;
+                    worker.resolve.ate_preferred_food = false;
+                }
+            }
+
+            (
                 worker.try_returning(&world.map, assigned_shop_pos),
                 WorkerActionResult::InProgress,
-            );
+            )
         }
     }
 }
@@ -349,5 +510,5 @@ fn handle_idle(
 
     //TODO: notify stores with result Idle at most once a sec
 
-    return (Worker::Idle(worker), WorkerActionResult::Idle);
+    (Worker::Idle(worker), WorkerActionResult::Idle)
 }
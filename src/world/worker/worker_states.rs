@@ -1,6 +1,10 @@
+//the to_* transitions consume the worker on purpose - a worker is in exactly one state at a time
+#![allow(clippy::wrong_self_convention)]
+
 use crate::{
     ai::pathfinding::{self, pathfinding_helpers},
-    config::{inventory::InventoryItems, receipes::ProducedReceipe},
+    config::{receipes::ProducedReceipe, species::Species},
+    data_helpers::to_string::ToString,
     math::Pos,
     world::{
         World,
@@ -8,8 +12,10 @@ use crate::{
             BasicAction, TransitAction, building_action::BuildingAction, gathering_action::GatheringAction,
             taking_break_action::TakingBreakAction,
         },
-        building::build_zone::BuildZone,
-        inventory::Inventory,
+        inventory::{
+            Inventory,
+            reservation::{PendingPickup, Reservation},
+        },
         worker::SupplyingAction,
         world_map::WorldMap,
     },
@@ -17,16 +23,20 @@ use crate::{
 use log::info;
 
 use super::{
-    CanGetLost, CanIdle, CanReturn, CanStore, Idle, LostAction, ProducingAction, ReturningAction, StoringAction,
-    SupplyingBuildZoneAction, Worker,
+    CanGetLost, CanIdle, CanReturn, CanStore, CollectingAction, Idle, LostAction, ProducingAction, ReturningAction,
+    StoringAction, SupplyingBuildZoneAction, Worker, carry::CarryUpgrade, resolve::Resolve, skills::Skills,
 };
 
 pub struct WorkerWithAction<T> {
     pub name: String,
+    pub species: Species,
     pub inventory: Inventory,
+    pub carry_upgrades: Vec<CarryUpgrade>,
     pub pos: Pos,
     pub break_progress: BasicAction,
     pub exhausted: bool,
+    pub resolve: Resolve,
+    pub skills: Skills,
     pub action_data: T,
 }
 
@@ -38,6 +48,24 @@ where
         Worker::Lost(WorkerWithAction::to_new_action(self, LostAction::new()))
     }
 
+    pub(super) fn to_lost_with_action_returned(self) -> (Worker, T) {
+        (
+            Worker::Lost(WorkerWithAction::<LostAction> {
+                name: self.name,
+                species: self.species,
+                inventory: self.inventory,
+                carry_upgrades: self.carry_upgrades,
+                pos: self.pos,
+                break_progress: self.break_progress,
+                exhausted: self.exhausted,
+                resolve: self.resolve,
+                skills: self.skills,
+                action_data: LostAction::new(),
+            }),
+            self.action_data,
+        )
+    }
+
     pub fn to_lost_with_immediate_retry(self) -> Worker {
         let mut worker = WorkerWithAction::to_new_action(self, LostAction::new());
         worker.break_progress.progress = LostAction::RETRY_DELAY;
@@ -57,10 +85,14 @@ where
         (
             Worker::Idle(WorkerWithAction::<Idle> {
                 name: self.name,
+                species: self.species,
                 inventory: self.inventory,
+                carry_upgrades: self.carry_upgrades,
                 pos: self.pos,
                 break_progress: self.break_progress,
                 exhausted: self.exhausted,
+                resolve: self.resolve,
+                skills: self.skills,
                 action_data: Idle {},
             }),
             self.action_data,
@@ -68,6 +100,12 @@ where
     }
 }
 
+impl Default for LostAction {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl LostAction {
     pub const RETRY_DELAY: f32 = 15.0;
     pub fn new() -> Self {
@@ -76,6 +114,29 @@ impl LostAction {
 }
 
 impl WorkerWithAction<Idle> {
+    ///weight a worker can carry without any upgrades
+    pub const CARRY_CAPACITY: f32 = 5.0;
+
+    /// A fresh, well rested worker, ready to work
+    pub fn new(
+        name: String,
+        species: Species,
+        pos: Pos,
+    ) -> Self {
+        Self {
+            name,
+            species,
+            inventory: Inventory::limited(Self::CARRY_CAPACITY),
+            carry_upgrades: Vec::new(),
+            pos,
+            break_progress: BasicAction::new(Worker::TIME_TO_BREAK),
+            exhausted: false,
+            resolve: Resolve::new(),
+            skills: Skills::new(),
+            action_data: Idle(),
+        }
+    }
+
     pub fn try_storing(
         self,
         world: &World,
@@ -93,6 +154,31 @@ impl WorkerWithAction<Idle> {
 }
 
 impl WorkerWithAction<LostAction> {
+    /// If there is no way to the store, the worker gives up on the load - drops it on the ground and
+    /// tries to get back to the shop
+    pub(super) fn try_storing(
+        mut self,
+        world: &mut World,
+        assigned_shop_pos: Pos,
+    ) -> Worker {
+        let (_, path) = if let Some(path) =
+            pathfinding_helpers::closest_shop(self.pos, world, |s| s.building_behaviour.is_store())
+        {
+            path
+        } else {
+            info!(
+                "{} cannot find a way to any store and drops the load: {}",
+                self.name, self.inventory
+            );
+            world.drop_items(self.pos, self.inventory.drain());
+            return self.try_returning(&world.map, assigned_shop_pos);
+        };
+
+        self.to_storing(&world.map, path)
+    }
+}
+
+impl WorkerWithAction<CollectingAction> {
     pub(super) fn try_storing(
         self,
         world: &World,
@@ -102,7 +188,7 @@ impl WorkerWithAction<LostAction> {
         {
             path
         } else {
-            return self.to_lost();
+            return self.to_lost(); //being lost will take care of the items
         };
 
         self.to_storing(&world.map, path)
@@ -171,21 +257,21 @@ impl WorkerWithAction<Idle> {
     }
 
     pub fn to_supplying(
-        mut self,
+        self,
         path: Vec<Pos>,
         map: &WorldMap,
-        reservation: (InventoryItems, f32),
+        reservation: Reservation,
     ) -> Worker {
         info!("{} is supplying materials, current pos {}.", self.name, self.pos);
 
         info!(
-            "{} is supplying the following materials, which were already reserved: {} {}",
-            self.name, reservation.0, reservation.1
+            "{} is supplying the following materials, which were already reserved: {}",
+            self.name,
+            reservation.items.to_string()
         );
-        self.inventory.add(&reservation.0, reservation.1);
         Worker::Supplying(WorkerWithAction::to_new_action(
             self,
-            SupplyingAction(TransitAction::new(path, map)),
+            SupplyingAction(TransitAction::new(path, map), reservation),
         ))
     }
 
@@ -217,16 +303,27 @@ impl WorkerWithAction<Idle> {
         self,
         map: &WorldMap,
         path: Vec<Pos>,
-        build_zone: BuildZone,
+        build_zone_pos: Pos,
     ) -> Worker {
-        info!(
-            "{} is building build zone at {}!",
-            self.name, build_zone.building.building_base.pos
-        );
+        info!("{} is building build zone at {}!", self.name, build_zone_pos);
 
         Worker::Building(WorkerWithAction::to_new_action(
             self,
-            BuildingAction::new(path, map, build_zone),
+            BuildingAction::new(path, map, build_zone_pos),
+        ))
+    }
+
+    pub fn to_collecting(
+        self,
+        map: &WorldMap,
+        path: Vec<Pos>,
+    ) -> Worker {
+        let pile_pos = *path.last().unwrap();
+        info!("{} is going to collect the items lying at {}.", self.name, pile_pos);
+
+        Worker::Collecting(WorkerWithAction::to_new_action(
+            self,
+            CollectingAction(TransitAction::new(path, map), pile_pos),
         ))
     }
 
@@ -234,15 +331,13 @@ impl WorkerWithAction<Idle> {
         self,
         world: &mut World,
         path: Vec<Pos>,
-        build_zone: BuildZone,
+        build_zone_pos: Pos,
+        pickups: Vec<PendingPickup>,
     ) -> Worker {
-        info!(
-            "{} is supplying build zone at {}!",
-            self.name, build_zone.building.building_base.pos
-        );
+        info!("{} is supplying build zone at {}!", self.name, build_zone_pos);
         Worker::SupplyingBuildZone(WorkerWithAction::to_new_action(
             self,
-            SupplyingBuildZoneAction(TransitAction::new(path, &world.map), build_zone),
+            SupplyingBuildZoneAction(TransitAction::new(path, &world.map), build_zone_pos, pickups),
         ))
     }
 }
@@ -256,7 +351,7 @@ where
         map: &WorldMap,
         assigned_shop_pos: Pos,
     ) -> Worker {
-        let path = if let Some(path) = pathfinding::a_star(map, self.pos, assigned_shop_pos) {
+        let path = if let Some(path) = pathfinding::path_into_building(map, self.pos, assigned_shop_pos) {
             path
         } else {
             info!("{} was not able to find a way to the shop!", self.name);
@@ -274,17 +369,21 @@ where
         map: &WorldMap,
         assigned_shop_pos: Pos,
     ) -> (Worker, T) {
-        let path = if let Some(path) = pathfinding::a_star(map, self.pos, assigned_shop_pos) {
+        let path = if let Some(path) = pathfinding::path_into_building(map, self.pos, assigned_shop_pos) {
             path
         } else {
             info!("{} was not able to find a way to the shop!", self.name);
             return (
                 Worker::Lost(WorkerWithAction::<LostAction> {
                     name: self.name,
+                    species: self.species,
                     inventory: self.inventory,
+                    carry_upgrades: self.carry_upgrades,
                     pos: self.pos,
                     break_progress: self.break_progress,
                     exhausted: self.exhausted,
+                    resolve: self.resolve,
+                    skills: self.skills,
                     action_data: LostAction::new(),
                 }),
                 self.action_data,
@@ -294,10 +393,14 @@ where
         (
             Worker::Returning(WorkerWithAction::<ReturningAction> {
                 name: self.name,
+                species: self.species,
                 inventory: self.inventory,
+                carry_upgrades: self.carry_upgrades,
                 pos: self.pos,
                 break_progress: self.break_progress,
                 exhausted: self.exhausted,
+                resolve: self.resolve,
+                skills: self.skills,
                 action_data: ReturningAction(TransitAction::new(path, map)),
             }),
             self.action_data,
@@ -312,10 +415,14 @@ impl<T> WorkerWithAction<T> {
     ) -> Self {
         Self {
             name: other.name,
+            species: other.species,
             inventory: other.inventory,
+            carry_upgrades: other.carry_upgrades,
             pos: other.pos,
             break_progress: other.break_progress,
             exhausted: other.exhausted,
+            resolve: other.resolve,
+            skills: other.skills,
             action_data: action,
         }
     }
@@ -1,8 +1,18 @@
-use crate::ai::pathfinding::{self, pathfinding_helpers};
+use crate::ai::pathfinding::{self, DistanceField};
+use crate::config::buildings::Buildings;
 use crate::config::inventory::InventoryItems;
 use crate::math::Pos;
-use crate::world::inventory::Inventory;
-use crate::world::{World, worker::Worker};
+use crate::world::inventory::{
+    Inventory,
+    reservation::{PendingPickup, Reservation},
+};
+use crate::world::{
+    World,
+    building::tools::ToolStock,
+    jobs::{Job, JobKind},
+    worker::Worker,
+    world_map::WorldMap,
+};
 
 use super::Idle;
 use super::worker_state_transitions::WorkerActionResult;
@@ -12,10 +22,12 @@ impl Worker {
     pub fn process_unassigned_worker(
         self,
         assigned_hearth_pos: Pos,
+        hearth: Buildings,
+        tools: &mut ToolStock,
         world: &mut World,
         delta: f32,
     ) -> Worker {
-        let (worker, result) = self.continue_action(assigned_hearth_pos, delta, world, true);
+        let (worker, result) = self.continue_action(assigned_hearth_pos, hearth, tools, delta, world, true);
 
         match result {
             WorkerActionResult::InProgress => {
@@ -39,64 +51,216 @@ impl Worker {
 }
 
 fn schedule_new_work(
-    mut worker: WorkerWithAction<Idle>,
+    worker: WorkerWithAction<Idle>,
     world: &mut World,
 ) -> Worker {
-    //If for whatever reason, no action can be performed on the first build_zone (e.g.
-    //unreachable), I do not want it to just sit on front() and block everything. That's why I'm
-    //popping it and shoving to the back. The next worker (or the same, if there is only 1), will
+    //build zones and item piles post their jobs at the beginning of every tick - the worker takes
+    //the most important one. If for whatever reason, nothing can be done with the build zone (e.g.
+    //unreachable), its job is gone until the next tick and the zone goes to the back of the list,
+    //so that it does not block everything. The next worker (or the same, if there is only 1), will
     //have a chance to handle the next build_zone on this or next tick.
-
-    let build_zone = if let Some(bz) = world.build_zones.pop_front() {
-        bz
+    let job = if let Some(job) = world.jobs.take(|j| j.kind.is_open()) {
+        job
     } else {
-        return Worker::Idle(worker); //no build zones
+        return Worker::Idle(worker);
     };
 
-    if build_zone.is_delivery_complete() {
-        let maybe_path = pathfinding::a_star(&world.map, worker.pos, build_zone.building.building_base.pos);
-        if let Some(path) = maybe_path {
-            return worker.to_building(&world.map, path, build_zone);
-        }
+    take_open_job(worker, job, world)
+}
+
+/// Build zones and item piles are nobody's in particular - the workers of the buildings help out
+/// the same way the unassigned ones do, and go back to their building afterwards
+pub fn take_open_job(
+    worker: WorkerWithAction<Idle>,
+    job: Job,
+    world: &mut World,
+) -> Worker {
+    if let JobKind::Collect = job.kind {
+        //all the piles are equally important - the closest one is picked, not necessarily the one
+        //of the job
+        return try_collecting(worker, world);
     }
 
-    let required_materials = &build_zone.building.building_base.building.get_data().build_materials;
+    let (delivery_complete, missing_materials) = if let Some(bz) = world.build_zone_mut(&job.pos) {
+        (bz.is_delivery_complete(), bz.missing_materials())
+    } else {
+        return Worker::Idle(worker);
+    };
+
+    if delivery_complete && let Some(path) = pathfinding::a_star(&world.map, worker.pos, job.pos) {
+        world.build_zone_mut(&job.pos).unwrap().builders += 1;
+        return worker.to_building(&world.map, path, job.pos);
+    }
 
     //if no delivery completed, or unreachable, try supplying
-    //TODO: consider taking materials from other buildings too
-    //order by the amount of materials first
-    let (closest_store, path_to_store) = if let Some(csp) =
-        pathfinding_helpers::closest_shop_mut_2(worker.pos, &world.map, &mut world.shops, |s| {
-            s.building_behaviour.is_store() //for now only consider stores - in the future maybe also take from other places
-            && has_any_of(&s.building_base.output, required_materials)
-        }) {
-        csp
+    let capacity = worker.inventory.remaining_capacity();
+    let (path, pickups) = if let Some(trip) = plan_supply_trip(world, worker.pos, job.pos, capacity, missing_materials)
+    {
+        trip
     } else {
-        world.build_zones.push_back(build_zone);
+        world.postpone_build_zone(&job.pos);
         return Worker::Idle(worker);
     };
 
-    let path_from_store_to_build_zone = if let Some(path) = pathfinding::a_star(
-        &world.map, //Should I use closest_shop_mut (no _2), this throws an error on borrow rules
-        *path_to_store.last().unwrap(),
-        build_zone.building.building_base.pos,
-    ) {
-        path
+    //the other workers bring the rest
+    let build_zone = world.build_zone_mut(&job.pos).unwrap();
+    build_zone.suppliers += 1;
+    for pickup in &pickups {
+        build_zone
+            .materials_incoming
+            .add_range(pickup.reservation.items.iter().copied());
+    }
+
+    worker.to_supplying_build_zone(world, path, job.pos, pickups)
+}
+
+/// Plans the way to the build zone, through as many buildings as it takes to fill the hands (or
+/// to get everything the zone is missing). Materials are taken from the output of any building -
+/// planks are fine straight from the lumbermill. At every stop, the next building is the one that
+/// makes the rest of the trip the cheapest - it is the walk to the zone that matters, not only the
+/// walk to the building. The buildings are only priced - the way is searched just for the chosen
+/// ones.
+fn plan_supply_trip(
+    world: &mut World,
+    start: Pos,
+    zone_pos: Pos,
+    mut capacity: f32,
+    mut missing: Vec<(InventoryItems, f32)>,
+) -> Option<(Vec<Pos>, Vec<PendingPickup>)> {
+    let mut path = vec![start];
+    let mut pickups: Vec<PendingPickup> = Vec::new();
+    //the zone does not move - the walk there is priced the same way from every stop
+    let to_zone = DistanceField::new(&world.map, zone_pos);
+
+    while !missing.is_empty() && capacity > 0.0 {
+        let here = *path.last().unwrap();
+        let sources: Vec<Pos> = world
+            .shops
+            .iter()
+            .filter(|s| has_any_of(&s.building_base.output, &missing))
+            .map(|s| s.building_base.pos)
+            .collect();
+
+        let best = cheapest_source(&world.map, here, &sources, &to_zone).and_then(|source_pos| {
+            pathfinding::path_into_building(&world.map, here, source_pos).map(|path| (source_pos, path))
+        });
+        let (source_pos, to_source) = if let Some(best) = best {
+            best
+        } else {
+            break; //nothing more to be found - bring what there is
+        };
+
+        let source = world
+            .shops
+            .iter_mut()
+            .find(|s| s.building_base.pos == source_pos)
+            .unwrap();
+        let reservation = reserve_as_much_as_possible(&mut source.building_base.output, source_pos, capacity, &missing);
+        if reservation.items.is_empty() {
+            break; //nothing fits anymore
+        }
+
+        for (item, amount) in &reservation.items {
+            capacity -= amount * item.config().weight;
+            if let Some(m) = missing.iter_mut().find(|(i, _)| i == item) {
+                m.1 -= amount;
+            }
+        }
+        missing.retain(|(_, amount)| *amount > 0.0);
+
+        //the building is the last tile of the path so far - the rest of the path does not change
+        //its index
+        path = combine_path(path, to_source);
+        pickups.push(PendingPickup {
+            reservation,
+            path_index: path.len() - 1,
+        });
+    }
+
+    if pickups.is_empty() {
+        return None;
+    }
+    //priced as reachable, so this should never fail - but the reservations must not get stuck
+    let path_to_zone = if let Some(path_to_zone) = pathfinding::a_star(&world.map, *path.last().unwrap(), zone_pos) {
+        path_to_zone
     } else {
-        world.build_zones.push_back(build_zone);
-        return Worker::Idle(worker);
+        for pickup in pickups {
+            world.release_reservation(pickup.reservation);
+        }
+        return None;
     };
+    Some((combine_path(path, path_to_zone), pickups))
+}
 
-    let total_path = combine_path(path_to_store, path_from_store_to_build_zone);
+/// The building that makes the rest of the trip the cheapest - the walk in, and the walk from its
+/// door to the zone. A single search from here prices all of them. The first one wins the ties -
+/// the shops are always in the same order
+fn cheapest_source(
+    map: &WorldMap,
+    here: Pos,
+    sources: &[Pos],
+    to_zone: &DistanceField,
+) -> Option<Pos> {
+    //the source, the door the worker would come through, and the tile to walk up to. Buildings
+    //without entrances are walked up to from any side
+    let mut targets: Vec<(Pos, Pos, Pos)> = Vec::new();
+    for source in sources {
+        let entrances = map.entrances(source);
+        if entrances.is_empty() {
+            targets.push((*source, *source, *source));
+        }
+        for entrance in entrances.iter().filter(|e| map.get(&e.doorstep).is_traversible()) {
+            targets.push((*source, entrance.door, entrance.doorstep));
+        }
+    }
 
-    //make a "reservation" at the store
-    take_as_much_as_possible(
-        &mut closest_store.building_base.output,
-        &mut worker.inventory,
-        &build_zone.building.building_base.building.get_data().build_materials,
-    );
+    let tiles: Vec<Pos> = targets.iter().map(|t| t.2).collect();
+    let costs = pathfinding::dijkstra_costs(map, here, &tiles);
+    targets
+        .iter()
+        .zip(costs)
+        .filter_map(|(&(source, door, tile), cost)| {
+            let walk_in = if door == here {
+                0.0 //already inside
+            } else if door == tile {
+                cost?
+            } else {
+                cost? + map.step_cost(&tile, &door)
+            };
+            Some((walk_in + to_zone.cost_from(map, &door)?, source))
+        })
+        .min_by(|l, r| l.0.total_cmp(&r.0))
+        .map(|(_, source)| source)
+}
 
-    worker.to_supplying_build_zone(world, total_path, build_zone)
+/// The way from the start, through the given buildings in the same order, to the build zone.
+/// Returns the path and the index of every stop on it
+pub(super) fn path_through_stops(
+    map: &WorldMap,
+    start: Pos,
+    stops: &[Pos],
+    zone_pos: Pos,
+) -> Option<(Vec<Pos>, Vec<usize>)> {
+    let mut path = vec![start];
+    let mut stop_indices = Vec::new();
+    for stop in stops {
+        let to_stop = pathfinding::path_into_building(map, *path.last().unwrap(), *stop)?;
+        path = combine_path(path, to_stop);
+        stop_indices.push(path.len() - 1);
+    }
+
+    let to_zone = pathfinding::a_star(map, *path.last().unwrap(), zone_pos)?;
+    Some((combine_path(path, to_zone), stop_indices))
+}
+
+fn try_collecting(
+    worker: WorkerWithAction<Idle>,
+    world: &mut World,
+) -> Worker {
+    if let Some(path) = world.claim_closest_pile(worker.pos) {
+        return worker.to_collecting(&world.map, path);
+    }
+    Worker::Idle(worker)
 }
 
 ///Combines 2 paths, assuming path 2 begins on the same tile as path 1 ends. Removes that repeating
@@ -116,41 +280,41 @@ fn has_any_of(
     materials: &Vec<(InventoryItems, f32)>,
 ) -> bool {
     for (item, _) in materials {
-        if inv.get(item) >= 1.0 {
+        if inv.available(item) >= 1.0 {
             return true;
         }
     }
-    return false;
+    false
 }
 
-fn take_as_much_as_possible(
+fn reserve_as_much_as_possible(
     store_inv: &mut Inventory,
-    worker_inv: &mut Inventory,
+    store_pos: Pos,
+    carry_capacity: f32,
     materials: &Vec<(InventoryItems, f32)>,
-) {
-    let mut taken_total = 0.0;
+) -> Reservation {
+    let mut to_reserve = Vec::new();
+    let mut remaining_capacity = carry_capacity;
 
     for (item, requested_amount) in materials {
-        if taken_total >= worker_inv.limit {
-            break;
-        }
-
-        let available_in_store = store_inv.get(item);
+        let available_in_store = store_inv.available(item);
 
         if available_in_store <= 0.0 {
             continue; // nothing to take
         }
 
-        let space_left = worker_inv.limit - taken_total;
-        let amount_to_take = requested_amount.min(available_in_store).min(space_left);
+        let weight = item.config().weight;
+        let amount_to_take = requested_amount
+            .min(available_in_store)
+            .min(remaining_capacity / weight);
 
         if amount_to_take <= 0.0 {
             continue;
         }
 
-        store_inv.remove(item, amount_to_take);
-        worker_inv.add(item, amount_to_take);
-
-        taken_total += amount_to_take;
+        to_reserve.push((*item, amount_to_take));
+        remaining_capacity -= amount_to_take * weight;
     }
+
+    store_inv.reserve(store_pos, to_reserve)
 }
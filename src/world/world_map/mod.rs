@@ -1,11 +1,22 @@
-use std::fmt::Display;
+use std::{
+    cell::{RefCell, RefMut},
+    collections::HashMap,
+    fmt::Display,
+};
 
 use resources::{ResourceCharge, ResourceType};
 use strum_macros::{Display, EnumDiscriminants};
 
-use crate::{config::buildings::Buildings, math::Pos};
+use crate::{
+    ai::pathfinding::{flow_field::FlowField, hierarchical::HierarchicalGraph, path_cache::PathCache},
+    config::buildings::Buildings,
+    math::Pos,
+};
 
-use super::building::BuildingBehaviourDiscriminants;
+use super::{
+    building::{BuildingBehaviourDiscriminants, entrance::Entrance},
+    traffic::Traffic,
+};
 
 pub mod resources;
 
@@ -18,6 +29,38 @@ pub struct WorldMap {
     //TODO: can I use an array?
     //If I want to, TileType needs to be Copy
     //pub map: [[TileType; A]; B]
+    ///bumped on every write through `build` and `get_mut`. Writing to `map` directly skips it - only
+    ///do that when setting the map up
+    pub revision: usize,
+    ///tiles written since the last call to take_changed_tiles
+    pub changed_tiles: Vec<Pos>,
+    //I said I would not use RefCell... but the alternative is to pass the map as mutable into
+    //every function that looks for a path, and half of them only get to see &World. The cache is
+    //not observable from the outside, so I can live with it
+    pub(crate) path_cache: RefCell<PathCache>,
+    ///only for big maps, built on the first search - same story as with the cache
+    pub(crate) hierarchy: RefCell<Option<HierarchicalGraph>>,
+    ///for the hearths and stores - updated lazily on the next use, same as the hierarchy
+    flow_fields: RefCell<Vec<FlowField>>,
+    movement: Movement,
+    ///entrances of the buildings and build zones, by the top-left corner of the building
+    entrances: HashMap<Pos, Vec<Entrance>>,
+    ///workers on each tile, counted at the beginning of the tick - see Traffic. Empty with the
+    ///traffic off
+    crowd: HashMap<Pos, u8>,
+    ///bumped whenever the crowds move. Kept apart from `revision` - a crowd only slows the workers
+    ///down, so the flow fields, the hierarchy and the workers on their way do not care about it
+    pub congestion_revision: usize,
+}
+
+/// Which tiles can workers step on from the tile they are standing on
+#[derive(Default, Clone, Copy, PartialEq, Eq, Display)]
+pub enum Movement {
+    #[default]
+    FourWay,
+    ///diagonal steps cost sqrt(2) times as much. Workers do not cut corners - both tiles next to
+    ///the diagonal step have to be traversible
+    EightWay,
 }
 #[derive(Default, Display, EnumDiscriminants)]
 pub enum TileType {
@@ -61,7 +104,18 @@ impl WorldMap {
         let tiles = (0..height)
             .map(|_| (0..width).map(|_| TileType::Empty).collect())
             .collect();
-        WorldMap { map: tiles }
+        WorldMap {
+            map: tiles,
+            revision: 0,
+            changed_tiles: Vec::new(),
+            path_cache: RefCell::new(PathCache::new()),
+            hierarchy: RefCell::new(None),
+            flow_fields: RefCell::new(Vec::new()),
+            movement: Movement::FourWay,
+            entrances: HashMap::new(),
+            crowd: HashMap::new(),
+            congestion_revision: 0,
+        }
     }
 
     /// sets the rectangular region as containing a structure. You MUST call can_build first, or
@@ -78,15 +132,20 @@ impl WorldMap {
         for h in 0..height {
             for w in 0..width {
                 self.map[pos.y + h as usize][pos.x + w as usize] = tile_type_factory();
+                self.mark_changed(Pos::new(pos.x + w as usize, pos.y + h as usize));
             }
         }
+        self.revision += 1;
     }
 
+    /// The building has to fit on empty tiles, and every one of its doorsteps has to be walkable. It
+    /// also cannot be put on a doorstep of another building
     pub fn can_build(
         &self,
         pos: &Pos,
         height: u8,
         width: u8,
+        entrances: &[Entrance],
     ) -> bool {
         if pos.y + height as usize >= self.height() {
             return false;
@@ -101,9 +160,45 @@ impl WorldMap {
                 if self.map[pos.y + h as usize][pos.x + w as usize] != TileType::Empty {
                     return false;
                 }
+                if self.is_doorstep(&Pos::new(pos.x + w as usize, pos.y + h as usize)) {
+                    return false;
+                }
             }
         }
-        return true;
+
+        entrances
+            .iter()
+            .all(|e| self.within_bounds(&e.doorstep) && self.get(&e.doorstep).is_traversible())
+    }
+
+    /// Remembers where the doors of the building placed at building_pos are. Pathfinding leads the
+    /// workers through them, and nothing can be built on the doorsteps
+    pub fn add_entrances(
+        &mut self,
+        building_pos: Pos,
+        entrances: Vec<Entrance>,
+    ) {
+        if !entrances.is_empty() {
+            self.entrances.insert(building_pos, entrances);
+        }
+    }
+
+    /// Empty for buildings that can be walked up to from any side
+    pub fn entrances(
+        &self,
+        building_pos: &Pos,
+    ) -> &[Entrance] {
+        self.entrances
+            .get(building_pos)
+            .map(|e| e.as_slice())
+            .unwrap_or_default()
+    }
+
+    fn is_doorstep(
+        &self,
+        pos: &Pos,
+    ) -> bool {
+        self.entrances.values().flatten().any(|e| e.doorstep == *pos)
     }
 
     pub fn get(
@@ -117,9 +212,64 @@ impl WorldMap {
         &mut self,
         pos: &Pos,
     ) -> &mut TileType {
+        //I cannot know if the caller is going to change anything - better safe than sorry
+        self.revision += 1;
+        self.mark_changed(*pos);
         &mut self.map[pos.y][pos.x]
     }
 
+    fn mark_changed(
+        &mut self,
+        pos: Pos,
+    ) {
+        self.changed_tiles.push(pos);
+        self.mark_dirty(pos);
+    }
+
+    /// The tile costs something else now - the searches have to take another look at it
+    fn mark_dirty(
+        &mut self,
+        pos: Pos,
+    ) {
+        if let Some(hierarchy) = self.hierarchy.get_mut() {
+            hierarchy.mark_dirty(&pos);
+        }
+        for field in self.flow_fields.get_mut().iter_mut() {
+            field.mark_dirty(&pos);
+        }
+    }
+
+    /// Workers keep walking to this building - worth knowing the way there from everywhere. The
+    /// entrances have to be added first
+    pub fn add_flow_field(
+        &mut self,
+        building_pos: Pos,
+    ) {
+        let field = FlowField::new(self, building_pos, self.entrances(&building_pos).to_vec());
+        self.flow_fields.get_mut().push(field);
+    }
+
+    /// Up to date flow field of the building, if it has one
+    pub fn flow_field(
+        &self,
+        building_pos: &Pos,
+    ) -> Option<RefMut<'_, FlowField>> {
+        let fields = self.flow_fields.borrow_mut();
+        let mut field = RefMut::filter_map(fields, |f| f.iter_mut().find(|f| f.building_pos == *building_pos)).ok()?;
+        field.update(self);
+        Some(field)
+    }
+
+    pub fn take_changed_tiles(&mut self) -> Vec<Pos> {
+        std::mem::take(&mut self.changed_tiles)
+    }
+
+    /// Number of searches answered from the path cache, and the number of actual searches
+    pub fn path_cache_stats(&self) -> (usize, usize) {
+        let cache = self.path_cache.borrow();
+        (cache.hits, cache.misses)
+    }
+
     pub fn within_bounds(
         &self,
         pos: &Pos,
@@ -127,11 +277,71 @@ impl WorldMap {
         pos.x < self.width() && pos.y < self.height()
     }
 
+    /// Cost of every step of the path. The first tile costs as much as entering it
     pub fn path_to_cost(
         &self,
-        path: &Vec<Pos>,
+        path: &[Pos],
     ) -> Vec<f32> {
-        path.iter().map(|p| self.get(p).cost()).collect()
+        let mut ret = vec![self.get(&path[0]).cost()];
+        ret.extend(path.windows(2).map(|step| self.step_cost(&step[0], &step[1])));
+        ret
+    }
+
+    /// Cost of entering `to` from the neighbouring tile `from`
+    pub fn step_cost(
+        &self,
+        from: &Pos,
+        to: &Pos,
+    ) -> f32 {
+        let cost = self.get(to).cost();
+        if from.x != to.x && from.y != to.y {
+            cost * std::f32::consts::SQRT_2
+        } else {
+            cost
+        }
+    }
+
+    /// Cost of entering `to` from `from`, with the crowd on it, for the worker standing on `own`.
+    /// The searches use this one - so that the workers go around the busy tiles, if there is a way
+    /// that is cheap enough. Nobody gets in their own way. Walking through the crowd is slowed down
+    /// separately, see TransitAction::apply_congestion
+    pub fn route_step_cost(
+        &self,
+        from: &Pos,
+        to: &Pos,
+        own: &Pos,
+    ) -> f32 {
+        let workers = self.crowd.get(to).copied().unwrap_or(0);
+        let others = if to == own { workers.saturating_sub(1) } else { workers };
+        self.step_cost(from, to) * (1.0 + Traffic::slowdown(others))
+    }
+
+    /// The paths found with the old crowds are not the cheapest anymore - but the crowd is only a
+    /// soft cost. The map itself did not change, and nothing changes for the workers already on
+    /// their way - they only get blocked by buildings
+    pub fn set_crowd(
+        &mut self,
+        crowd: HashMap<Pos, u8>,
+    ) {
+        if crowd == self.crowd {
+            return;
+        }
+        self.crowd = crowd;
+        self.congestion_revision += 1;
+    }
+
+    pub fn movement(&self) -> Movement {
+        self.movement
+    }
+
+    /// All the paths found so far are useless after this
+    pub fn set_movement(
+        &mut self,
+        movement: Movement,
+    ) {
+        self.movement = movement;
+        self.revision += 1;
+        *self.hierarchy.get_mut() = None;
     }
 }
 
@@ -178,6 +388,8 @@ impl TileType {
                 BuildingBehaviourDiscriminants::Store => "󰾁 ",
                 BuildingBehaviourDiscriminants::Gatherer => "󰧻󱔐",
                 BuildingBehaviourDiscriminants::Producer => "󰈏 ",
+                BuildingBehaviourDiscriminants::House => "󰋜 ",
+                BuildingBehaviourDiscriminants::Decoration => "󰌪 ",
             },
             TileType::BuildZone(_) => "󰡢 ",
             //TileType::Tree(_, _) => " ",
@@ -197,10 +409,7 @@ impl TileType {
     pub fn is_traversible(&self) -> bool {
         match self {
             TileType::Empty => true,
-            TileType::Resource(rt, _, _) => match rt {
-                ResourceType::Tree => false,
-                _ => true,
-            },
+            TileType::Resource(rt, _, _) => !matches!(rt, ResourceType::Tree),
             TileType::Road => true,
             TileType::Structure(_) => false,
             TileType::BuildZone(_) => true,
@@ -1,6 +1,9 @@
 use strum_macros::Display;
 
-use crate::config::inventory::InventoryItems;
+use crate::config::{
+    inventory::InventoryItems,
+    resources::{ResourceNodeConfig, ResourceNodes},
+};
 
 use super::TileType;
 
@@ -12,40 +15,61 @@ pub enum ResourceType {
     //TODO: more - clay, stone,...
 }
 
+/// What is left of the resource node on the tile. Everything else about the node comes from its
+/// config
 pub struct ResourceCharge {
-    pub per_gather: Vec<(InventoryItems, f32)>,
-    pub total: f32,
+    pub node: ResourceNodes,
     pub current: f32,
+    ///the odds of each of the node's bonus resources, added up with every gather
+    pub bonus_progress: Vec<f32>,
 }
 
 impl ResourceCharge {
+    const ODDS_TOLERANCE: f32 = 0.0001;
+
+    pub fn new(node: ResourceNodes) -> Self {
+        let config = node.get_config();
+        Self {
+            node,
+            current: config.total_charges,
+            bonus_progress: vec![0.0; config.bonus_resources.len()],
+        }
+    }
+
+    pub fn config(&self) -> &'static ResourceNodeConfig {
+        self.node.get_config()
+    }
+
+    /// Takes a single charge. No randomness - the odds add up, and once they reach a whole one, a
+    /// bonus item is found. A node with 0.2 odds gives a bonus every 5th gather, one with 1.5 odds
+    /// gives one and two bonus items in turns
     pub fn gather(&mut self) -> Vec<(InventoryItems, f32)> {
         self.current -= 1.0;
-        self.per_gather.clone()
+        let config = self.config();
+
+        let mut ret = vec![(config.primary_resource, config.yield_per_gather)];
+        for ((item, odds), progress) in config.bonus_resources.iter().zip(self.bonus_progress.iter_mut()) {
+            *progress += odds;
+            //a bit of tolerance - five times 0.2 does not quite add up to 1.0 in floats
+            let found = (*progress + Self::ODDS_TOLERANCE).floor();
+            if found > 0.0 {
+                *progress -= found;
+                ret.push((*item, found));
+            }
+        }
+        ret
     }
 }
 
 impl ResourceType {
     pub fn tile_tree() -> TileType {
-        TileType::Resource(
-            ResourceType::Tree,
-            ResourceCharge {
-                per_gather: vec![(InventoryItems::Wood, 1.0), (InventoryItems::Resin, 0.2)],
-                total: 10.0,
-                current: 10.0,
-            },
-            false,
-        )
+        TileType::Resource(ResourceType::Tree, ResourceCharge::new(ResourceNodes::Tree), false)
     }
 
     pub fn tile_berry() -> TileType {
         TileType::Resource(
             ResourceType::Berries,
-            ResourceCharge {
-                per_gather: vec![(InventoryItems::Berries, 1.0), (InventoryItems::Herbs, 0.2)],
-                total: 50.0,
-                current: 50.0,
-            },
+            ResourceCharge::new(ResourceNodes::BerryBush),
             false,
         )
     }
@@ -3,12 +3,16 @@ use std::collections::LinkedList;
 use log::info;
 
 use crate::{
+    ai::pathfinding::flow_field,
     config::buildings::Buildings,
     math::Pos,
     world::{
         World,
-        building::{Building, BuildingBase, build_zone::BuildZone},
+        building::{
+            Building, BuildingBase, build_zone::BuildZone, entrance::Entrance, rotation::Rotation, tools::ToolStock,
+        },
         inventory::Inventory,
+        jobs::JobKind,
         world_map::TileType,
     },
 };
@@ -18,18 +22,37 @@ pub enum BuildMethod {
     SpawnBuildZone,
 }
 
-pub fn build<'a>(
-    world: &'a mut World,
+pub fn build(
+    world: &mut World,
     building: Buildings,
     pos: Pos,
     build_method: BuildMethod,
-) -> Option<&'a mut Building> {
+) -> Option<&mut Building> {
+    build_rotated(world, building, pos, Rotation::Deg0, build_method)
+}
+
+/// Same as build, but the building is turned clockwise first - handy when it does not fit (or its
+/// door would face a tree)
+pub fn build_rotated(
+    world: &mut World,
+    building: Buildings,
+    pos: Pos,
+    rotation: Rotation,
+    build_method: BuildMethod,
+) -> Option<&mut Building> {
     let data = building.get_data();
+    let (width, height) = rotation.footprint(data.width, data.height);
 
-    if !world.map.can_build(&pos, data.height, data.width) {
-        info!("Cannot build {} at {}", building, pos);
+    //None means some of the doorsteps would be off the map
+    let entrances = Entrance::for_building(building, &pos, rotation);
+    let can_build = entrances
+        .as_ref()
+        .is_some_and(|e| world.map.can_build(&pos, height, width, e));
+    if !can_build {
+        info!("Cannot build {} rotated by {} at {}", building, rotation.degrees(), pos);
         return None;
     }
+    world.map.add_entrances(pos, entrances.unwrap());
 
     let building_behaviour = data.building_behaviour.to_default();
 
@@ -40,6 +63,8 @@ pub fn build<'a>(
         //TODO: what should be the output limit?
         output: Inventory::limited(10.0),
         building,
+        rotation,
+        tools: ToolStock::new(data.tools.as_ref()),
     };
 
     let final_building = Building {
@@ -51,21 +76,41 @@ pub fn build<'a>(
         BuildMethod::SpawnExisting => {
             world.shops.push_back(final_building);
 
-            world
-                .map
-                .build(&pos, data.width, data.height, || TileType::Structure(building));
+            world.map.build(&pos, width, height, || TileType::Structure(building));
+            if flow_field::wants_flow_field(building) {
+                world.map.add_flow_field(pos);
+            }
 
-            return Some(world.shops.back_mut().unwrap());
+            Some(world.shops.back_mut().unwrap())
         }
         BuildMethod::SpawnBuildZone => {
             let build_zone = BuildZone::new(final_building);
             world.build_zones.push_back(build_zone);
 
-            world
-                .map
-                .build(&pos, data.width, data.height, || TileType::BuildZone(building));
+            world.map.build(&pos, width, height, || TileType::BuildZone(building));
 
-            return Some(&mut world.build_zones.back_mut().unwrap().building);
+            Some(&mut world.build_zones.back_mut().unwrap().building)
         }
     }
 }
+
+/// Workers take the more important jobs first. Takes effect when the jobs are posted next tick
+pub fn set_job_priority(
+    world: &mut World,
+    kind: JobKind,
+    priority: i32,
+) {
+    info!("Priority of {} set to {}", kind, priority);
+    world.jobs.set_priority(kind, priority);
+}
+
+/// Added to the priority of every job of the building (or the build zone) - e.g. to get one house
+/// built before the others
+pub fn set_building_priority(
+    world: &mut World,
+    pos: Pos,
+    priority: i32,
+) {
+    info!("Priority of the jobs at {} raised by {}", pos, priority);
+    world.jobs.set_building_priority(pos, priority);
+}
@@ -1,14 +1,42 @@
+use std::collections::HashMap;
+
+use strum::IntoEnumIterator;
+
 use crate::{
     config::inventory::InventoryItems,
+    math::Pos,
     world::{
         World,
         building::{
             BuildingBase, BuildingBehaviour, StoreBehaviour,
             building_behaviour::{gatherer::GathererBehaviour, hearth::HearthBehaviour},
         },
+        ground_items::ItemPile,
+        jobs::Job,
+        settlement::HearthOutPenalty,
+        statistics::{ItemRates, Statistics, stock::Stock},
+        worker::{resolve::Resolve, skills::Skill},
     },
 };
 
+/// One point on a chart - a single statistics bucket
+pub struct ItemHistoryPoint {
+    ///time at which the bucket ended
+    pub time: f32,
+    pub stock: f32,
+    pub produced: f32,
+    pub consumed: f32,
+    pub spoiled: f32,
+}
+
+pub struct HearthStatus {
+    pub burning: bool,
+    ///fuel in stock in all the hearths
+    pub fuel: f32,
+    pub time_out: f32,
+    pub penalties: Vec<HearthOutPenalty>,
+}
+
 impl World {
     pub fn get_gatherers(
         &self,
@@ -51,4 +79,155 @@ impl World {
             }
         })
     }
+
+    /// Whether the hearth is burning, and what the settlement suffers from if it is not
+    pub fn get_hearth_status(&self) -> HearthStatus {
+        let penalties = if self.settlement.hearth_out {
+            vec![
+                HearthOutPenalty::Resolve,
+                HearthOutPenalty::SlowerBreaks,
+                HearthOutPenalty::NoNewcomers,
+            ]
+        } else {
+            Vec::new()
+        };
+
+        HearthStatus {
+            burning: self.get_hearths().any(|(_, h)| h.is_burning()),
+            fuel: self.get_hearths().map(|(_, h)| h.fuel()).sum(),
+            time_out: self.settlement.time_hearth_out,
+            penalties,
+        }
+    }
+
+    /// How much of the item the settlement has, wherever it is
+    pub fn get_item_total(
+        &self,
+        item: &InventoryItems,
+    ) -> f32 {
+        self.collect_stock().get(item)
+    }
+
+    /// All the goods in the settlement, split by where they are
+    pub fn get_stock(&self) -> Stock {
+        self.collect_stock()
+    }
+
+    /// Production, consumption and spoilage per minute over the last `window` seconds. Only the
+    /// last Statistics::HISTORY_LENGTH buckets are remembered
+    pub fn get_item_rates(
+        &self,
+        item: &InventoryItems,
+        window: f32,
+    ) -> ItemRates {
+        self.statistics.rates(item, window)
+    }
+
+    /// Recorded history of the item, oldest first - for the charts
+    pub fn get_item_history(
+        &self,
+        item: &InventoryItems,
+    ) -> Vec<ItemHistoryPoint> {
+        self.statistics
+            .history
+            .iter()
+            .map(|b| ItemHistoryPoint {
+                time: b.start + Statistics::BUCKET_LENGTH,
+                stock: b.stock.get(item),
+                produced: b.produced.get(item),
+                consumed: b.consumed.get(item),
+                spoiled: b.spoiled.get(item),
+            })
+            .collect()
+    }
+
+    /// Total amount of goods lost to spoilage, always in the same order
+    pub fn get_spoiled_items(&self) -> Vec<(InventoryItems, f32)> {
+        InventoryItems::iter()
+            .map(|i| (i, self.settlement.spoiled.get(&i)))
+            .filter(|(_, amount)| *amount > 0.0)
+            .collect()
+    }
+
+    /// Items lying on the ground, waiting to be collected
+    pub fn get_item_piles(&self) -> impl Iterator<Item = &ItemPile> {
+        self.item_piles.iter()
+    }
+
+    /// Resolve of the worker with the given name, including the factors that contributed to it
+    pub fn get_worker_resolve(
+        &self,
+        worker_name: &str,
+    ) -> Option<&Resolve> {
+        self.get_all_workers()
+            .into_iter()
+            .find(|w| w.name() == worker_name)
+            .map(|w| w.resolve())
+    }
+
+    /// Skills of the worker with the given name - each one with its level and experience, the best
+    /// one first
+    pub fn get_worker_skills(
+        &self,
+        worker_name: &str,
+    ) -> Option<Vec<(Skill, u8, f32)>> {
+        self.get_all_workers()
+            .into_iter()
+            .find(|w| w.name() == worker_name)
+            .map(|w| w.skills().all())
+    }
+
+    /// Sum of the resolve bonuses of all the decorations covering each tile - for drawing the
+    /// coverage overlay on the map
+    pub fn get_decoration_coverage(&self) -> HashMap<Pos, f32> {
+        let mut ret = HashMap::new();
+        for area in self.get_decoration_areas() {
+            for pos in area.covered_tiles(self.map.width(), self.map.height()) {
+                *ret.entry(pos).or_insert(0.0) += area.config.resolve_bonus;
+            }
+        }
+        ret
+    }
+
+    /// Cost of walking from the tile into the building - only hearths and stores keep a flow field.
+    /// None if the building has no field, or there is no way from the tile
+    pub fn sample_flow_field(
+        &self,
+        building_pos: &Pos,
+        pos: &Pos,
+    ) -> Option<f32> {
+        self.map.flow_field(building_pos)?.cost_at(pos)
+    }
+
+    /// The whole flow field of the building, row by row - for drawing the heat map overlay
+    pub fn get_flow_field_heat_map(
+        &self,
+        building_pos: &Pos,
+    ) -> Option<Vec<Vec<Option<f32>>>> {
+        self.map.flow_field(building_pos).map(|f| f.rows())
+    }
+
+    /// Tiles where the workers lost the most time in the crowd during the last `window` seconds,
+    /// worst first. Empty unless the traffic is enabled
+    pub fn get_bottlenecks(
+        &self,
+        window: f32,
+        count: usize,
+    ) -> Vec<(Pos, f32)> {
+        self.statistics.bottlenecks(window, count)
+    }
+
+    /// Number of workers standing on the tile, as counted at the beginning of the tick
+    pub fn get_tile_occupancy(
+        &self,
+        pos: &Pos,
+    ) -> u8 {
+        self.traffic.occupancy(pos)
+    }
+
+    /// Jobs waiting for a worker, in the order they were posted. The ones already taken this tick
+    /// are not on the board anymore
+    pub fn get_jobs(&self) -> &[Job] {
+        self.jobs.jobs()
+    }
 }
@@ -1,12 +1,12 @@
 use rusty_rain::{
-    config::{buildings::Buildings, inventory::InventoryItems},
+    config::{buildings::Buildings, inventory::InventoryItems, species::Species},
     math::Pos,
     world::{
         World,
         actions::BasicAction,
         building::{Building, BuildingBehaviour},
         inventory::Inventory,
-        worker::{Idle, Worker, worker_states::WorkerWithAction},
+        worker::{Idle, Worker, resolve::Resolve, skills::Skills, worker_states::WorkerWithAction},
     },
     world_interaction::commands::{self, BuildMethod},
 };
@@ -45,7 +45,7 @@ pub fn test(mut world: World) {
         })
         .unwrap();
 
-    let store = world.get_stores().nth(0).unwrap();
+    let store = world.get_stores().next().unwrap();
 
     assert!(store.0.output.get(&InventoryItems::Bricks) <= 0.0);
     assert!(store.0.output.get(&InventoryItems::Fabric) <= 0.0);
@@ -68,10 +68,14 @@ pub fn configure_world(world: &mut World) {
             .unassigned_workers
             .push_front(Worker::Idle(WorkerWithAction::<Idle> {
                 name: "Bob".to_string(),
+                species: Species::Human,
                 inventory: Inventory::limited(5.0),
+                carry_upgrades: Vec::new(),
                 pos: building_base.pos,
                 break_progress: BasicAction::new(120.0),
                 exhausted: false,
+                resolve: Resolve::new(),
+                skills: Skills::new(),
                 action_data: Idle(),
             }))
     };
@@ -95,3 +99,241 @@ pub fn configure_world(world: &mut World) {
         panic!();
     }
 }
+
+/// Both stores have the wood - the one next to the hearth is closer to the worker, but the one
+/// next to the zone makes for a shorter trip
+pub fn test_trip_cost(mut world: World) {
+    let (near_worker, near_zone) = configure_far_zone(&mut world, Buildings::Shelter, Buildings::MainStore);
+    for store in [near_worker, near_zone] {
+        add_to_output(&mut world, store, InventoryItems::Wood, 4.0);
+    }
+
+    let mut seconds = 0.0;
+    const DELTA: f32 = 1.0 / 30.0;
+    while seconds < 60.0 {
+        world.next_tick(DELTA);
+        seconds += DELTA;
+    }
+
+    assert_eq!(output_of(&world, near_worker).get(&InventoryItems::Wood), 4.0);
+    assert_eq!(output_of(&world, near_zone).get(&InventoryItems::Wood), 0.0);
+}
+
+/// Planks straight from the lumbermill, fabric from the store - both are picked up on the same trip
+pub fn test_multiple_sources(mut world: World) {
+    let (near_worker, near_zone) = configure_far_zone(&mut world, Buildings::HumanHouse, Buildings::Lumbermill);
+    add_to_output(&mut world, near_worker, InventoryItems::Fabric, 2.0);
+    add_to_output(&mut world, near_zone, InventoryItems::Plank, 4.0);
+
+    let mut trips = 0;
+    let mut was_supplying = false;
+    let mut seconds = 0.0;
+    const DELTA: f32 = 1.0 / 30.0;
+    while seconds < 240.0 {
+        world.next_tick(DELTA);
+        seconds += DELTA;
+
+        let supplying = matches!(world.get_all_unassigned_workers()[0], Worker::SupplyingBuildZone(_));
+        if supplying && !was_supplying {
+            trips += 1;
+        }
+        was_supplying = supplying;
+    }
+
+    assert_eq!(trips, 1);
+    assert!(output_of(&world, near_worker).is_empty());
+    assert!(output_of(&world, near_zone).is_empty());
+    assert!(
+        world
+            .shops
+            .iter()
+            .any(|s| s.building_base.building == Buildings::HumanHouse)
+    );
+}
+
+/// A statue is built in the way of the worker, who is yet to pick up from both buildings - they go
+/// around, and still pick up from both of them
+pub fn test_blocked_trip(mut world: World) {
+    let (near_worker, near_zone) = configure_far_zone(&mut world, Buildings::HumanHouse, Buildings::Lumbermill);
+    add_to_output(&mut world, near_worker, InventoryItems::Fabric, 2.0);
+    add_to_output(&mut world, near_zone, InventoryItems::Plank, 4.0);
+
+    const DELTA: f32 = 1.0 / 30.0;
+    let trip = wait_for_trip(&mut world, DELTA, 1);
+    assert_eq!(trip.stops.len(), 2);
+
+    //statues cannot be built everywhere - e.g. right in front of a door
+    let blocked = trip.path[trip.current_index + 2..trip.stops[0].1]
+        .iter()
+        .copied()
+        .find(|p| commands::build(&mut world, Buildings::Statue, *p, BuildMethod::SpawnExisting).is_some())
+        .unwrap();
+    world.next_tick(DELTA);
+
+    let trip = supply_trip(&world).unwrap();
+    assert!(!trip.path.contains(&blocked));
+    assert_eq!(trip.stops.len(), 2);
+    //the pickups are made when the worker steps into the buildings
+    for (store_pos, path_index) in trip.stops {
+        let entrances = world.map.entrances(&store_pos);
+        assert!(entrances.iter().any(|e| e.door == trip.path[path_index]));
+    }
+
+    let mut seconds = 0.0;
+    while seconds < 240.0 {
+        world.next_tick(DELTA);
+        seconds += DELTA;
+    }
+
+    assert!(output_of(&world, near_worker).is_empty());
+    assert!(output_of(&world, near_zone).is_empty());
+    assert!(
+        world
+            .shops
+            .iter()
+            .any(|s| s.building_base.building == Buildings::HumanHouse)
+    );
+}
+
+/// The worker on the way to the first building gets walled in - they give up on the trip, and the
+/// materials are free for anyone to bring again
+pub fn test_abandoned_trip(mut world: World) {
+    let (near_worker, near_zone) = configure_far_zone(&mut world, Buildings::HumanHouse, Buildings::Lumbermill);
+    add_to_output(&mut world, near_worker, InventoryItems::Fabric, 2.0);
+    add_to_output(&mut world, near_zone, InventoryItems::Plank, 4.0);
+
+    const DELTA: f32 = 1.0 / 30.0;
+    let trip = wait_for_trip(&mut world, DELTA, 2);
+    let pos = trip.path[trip.current_index];
+    for (x, y) in [(0, 0), (1, 0), (2, 0), (0, 1), (2, 1), (0, 2), (1, 2), (2, 2)] {
+        let wall = Pos::new(pos.x + x - 1, pos.y + y - 1);
+        commands::build(&mut world, Buildings::Statue, wall, BuildMethod::SpawnExisting);
+    }
+    world.next_tick(DELTA);
+
+    assert!(matches!(world.get_all_unassigned_workers()[0], Worker::Lost(_)));
+    assert_eq!(output_of(&world, near_worker).available(&InventoryItems::Fabric), 2.0);
+    assert_eq!(output_of(&world, near_zone).available(&InventoryItems::Plank), 4.0);
+
+    let build_zone = world.get_all_build_zones()[0];
+    assert_eq!(build_zone.suppliers, 0);
+    assert!(build_zone.materials_incoming.is_empty());
+    assert!(!build_zone.missing_materials().is_empty());
+}
+
+struct SupplyTrip {
+    path: Vec<Pos>,
+    ///the buildings still to be visited, with their index on the path
+    stops: Vec<(Pos, usize)>,
+    ///index of the tile the worker is on
+    current_index: usize,
+}
+
+/// Ticks until the worker is on a supply trip, at least the given number of steps away from the
+/// hearth
+fn wait_for_trip(
+    world: &mut World,
+    delta: f32,
+    steps: usize,
+) -> SupplyTrip {
+    let mut seconds = 0.0;
+    while seconds < 60.0 {
+        world.next_tick(delta);
+        seconds += delta;
+
+        if let Some(trip) = supply_trip(world)
+            && trip.current_index >= steps
+        {
+            return trip;
+        }
+    }
+    panic!("The worker should be on a supply trip by now");
+}
+
+fn supply_trip(world: &World) -> Option<SupplyTrip> {
+    let Worker::SupplyingBuildZone(worker) = world.get_all_unassigned_workers()[0] else {
+        return None;
+    };
+    let transit = &worker.action_data.0;
+    let stops = worker
+        .action_data
+        .2
+        .iter()
+        .map(|p| (p.reservation.store_pos, p.path_index))
+        .collect();
+    Some(SupplyTrip {
+        path: transit.path.clone(),
+        stops,
+        current_index: transit.current_index(),
+    })
+}
+
+fn add_to_output(
+    world: &mut World,
+    pos: Pos,
+    item: InventoryItems,
+    amount: f32,
+) {
+    let shop = world.shops.iter_mut().find(|s| s.building_base.pos == pos).unwrap();
+    shop.building_base.output.add(&item, amount);
+}
+
+fn output_of(
+    world: &World,
+    pos: Pos,
+) -> &Inventory {
+    &world
+        .shops
+        .iter()
+        .find(|s| s.building_base.pos == pos)
+        .unwrap()
+        .building_base
+        .output
+}
+
+/// Returns the store next to the hearth and the source building next to the zone
+fn configure_far_zone(
+    world: &mut World,
+    building: Buildings,
+    source: Buildings,
+) -> (Pos, Pos) {
+    let maybe_hearth = commands::build(
+        world,
+        Buildings::MainHearth,
+        Pos::new(14, 14),
+        BuildMethod::SpawnExisting,
+    );
+    if let Some(Building {
+        building_base,
+        building_behaviour: BuildingBehaviour::Hearth(hearth),
+    }) = maybe_hearth
+    {
+        hearth
+            .unassigned_workers
+            .push_front(Worker::Idle(WorkerWithAction::<Idle> {
+                name: "Bob".to_string(),
+                species: Species::Human,
+                inventory: Inventory::limited(5.0),
+                carry_upgrades: Vec::new(),
+                pos: building_base.pos,
+                break_progress: BasicAction::new(120.0),
+                exhausted: false,
+                resolve: Resolve::new(),
+                skills: Skills::new(),
+                action_data: Idle(),
+            }))
+    };
+
+    let near_worker = Pos::new(14, 8);
+    let near_zone = Pos::new(22, 22);
+    for (building, pos) in [(Buildings::MainStore, near_worker), (source, near_zone)] {
+        if commands::build(world, building, pos, BuildMethod::SpawnExisting).is_none() {
+            panic!();
+        }
+    }
+    if commands::build(world, building, Pos::new(26, 26), BuildMethod::SpawnBuildZone).is_none() {
+        panic!();
+    }
+
+    (near_worker, near_zone)
+}
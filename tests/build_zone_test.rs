@@ -0,0 +1,178 @@
+use rusty_rain::{
+    config::{buildings::Buildings, inventory::InventoryItems, species::Species},
+    math::Pos,
+    world::{
+        World,
+        actions::BasicAction,
+        building::{Building, BuildingBehaviour},
+        inventory::Inventory,
+        worker::{Idle, Worker, resolve::Resolve, skills::Skills, worker_states::WorkerWithAction},
+    },
+    world_interaction::commands::{self, BuildMethod},
+};
+use std::io::Write;
+
+use rusty_rain::FRAME_NUM;
+use std::sync::atomic::Ordering;
+
+const DELTA: f32 = 1.0 / 30.0;
+
+/// Three workers bring the materials and build the park together - faster than one would, and
+/// without bringing more than needed
+pub fn test_shared(
+    mut world: World,
+    mut other_world: World,
+) {
+    let _ = env_logger::builder()
+        .format(|buf, record| {
+            let tick_num = FRAME_NUM.load(Ordering::Relaxed);
+            writeln!(buf, "@{}\t{}", tick_num, record.args())?;
+            Ok(())
+        })
+        .try_init();
+
+    let zone_pos = Pos::new(10, 3);
+    configure_world(&mut world, zone_pos, &["Ann", "Bob", "Cid"]);
+    configure_world(&mut other_world, zone_pos, &["Dan"]);
+
+    let mut max_suppliers = 0;
+    let mut max_builders = 0;
+    let mut ticks = 0;
+    while !world.build_zones.is_empty() {
+        world.next_tick(DELTA);
+        ticks += 1;
+        assert!(ticks < 30 * 300, "the park should have been built by now");
+
+        let workers = world.get_all_unassigned_workers();
+        let suppliers = workers
+            .iter()
+            .filter(|w| matches!(w, Worker::SupplyingBuildZone(_)))
+            .count();
+        let builders = workers.iter().filter(|w| matches!(w, Worker::Building(_))).count();
+        max_suppliers = max_suppliers.max(suppliers);
+        max_builders = max_builders.max(builders);
+
+        if let Some(zone) = world.build_zones.front() {
+            assert_eq!(zone.suppliers as usize, suppliers);
+            assert_eq!(zone.builders as usize, builders);
+        }
+    }
+
+    let mut other_ticks = 0;
+    while !other_world.build_zones.is_empty() {
+        other_world.next_tick(DELTA);
+        other_ticks += 1;
+        assert!(other_ticks < 30 * 600, "the park should have been built by now");
+    }
+
+    assert!(max_suppliers >= 2);
+    assert!(max_builders >= 2);
+    assert!(ticks < other_ticks);
+
+    //only what was needed was taken from the store
+    for w in [&world, &other_world] {
+        let store = w.get_stores().next().unwrap();
+        assert_eq!(store.0.output.get(&InventoryItems::Wood), 14.0);
+        assert_eq!(store.0.output.get(&InventoryItems::Herbs), 6.0);
+        assert!(w.shops.iter().any(|s| s.building_base.building == Buildings::Park));
+    }
+}
+
+/// One of the builders needs a break halfway through - the other one finishes the job alone
+pub fn test_builder_leaves(mut world: World) {
+    let zone_pos = Pos::new(10, 3);
+    configure_world(&mut world, zone_pos, &["Fresh"]);
+    world.build_zones.front_mut().unwrap().materials_delivered =
+        Inventory::from_iter([(InventoryItems::Wood, 6.0), (InventoryItems::Herbs, 4.0)]);
+
+    let mut tired = new_worker("Tired", Pos::new(8, 8));
+    if let Worker::Idle(w) = &mut tired {
+        w.break_progress.progress = w.break_progress.requirement - 5.0;
+    }
+    add_unassigned_worker(&mut world, tired);
+
+    let mut tired_was_building = false;
+    let mut left = false;
+    let mut ticks = 0;
+    while !world.build_zones.is_empty() {
+        world.next_tick(DELTA);
+        ticks += 1;
+        assert!(ticks < 30 * 120, "the park should have been built by now");
+
+        let workers = world.get_all_unassigned_workers();
+        let tired_building = workers
+            .iter()
+            .any(|w| w.name() == "Tired" && matches!(w, Worker::Building(_)));
+        let fresh_building = workers
+            .iter()
+            .any(|w| w.name() == "Fresh" && matches!(w, Worker::Building(_)));
+
+        let Some(zone) = world.build_zones.front() else {
+            break;
+        };
+        if tired_was_building && fresh_building && !tired_building {
+            left = true;
+            assert_eq!(zone.builders, 1);
+        }
+        tired_was_building |= tired_building;
+    }
+
+    assert!(left);
+    assert!(world.shops.iter().any(|s| s.building_base.building == Buildings::Park));
+}
+
+fn configure_world(
+    world: &mut World,
+    zone_pos: Pos,
+    names: &[&str],
+) {
+    let hearth_pos = Pos::new(8, 8);
+    if commands::build(world, Buildings::MainHearth, hearth_pos, BuildMethod::SpawnExisting).is_none() {
+        panic!();
+    }
+    for name in names {
+        add_unassigned_worker(world, new_worker(name, hearth_pos));
+    }
+
+    let maybe_store = commands::build(world, Buildings::MainStore, Pos::new(4, 3), BuildMethod::SpawnExisting);
+    if let Some(Building { building_base, .. }) = maybe_store {
+        building_base.output.add(&InventoryItems::Wood, 20.0);
+        building_base.output.add(&InventoryItems::Herbs, 10.0);
+    } else {
+        panic!();
+    }
+
+    if commands::build(world, Buildings::Park, zone_pos, BuildMethod::SpawnBuildZone).is_none() {
+        panic!();
+    }
+}
+
+fn add_unassigned_worker(
+    world: &mut World,
+    worker: Worker,
+) {
+    for shop in world.shops.iter_mut() {
+        if let BuildingBehaviour::Hearth(hearth) = &mut shop.building_behaviour {
+            hearth.unassigned_workers.push_back(worker);
+            return;
+        }
+    }
+}
+
+fn new_worker(
+    name: &str,
+    pos: Pos,
+) -> Worker {
+    Worker::Idle(WorkerWithAction::<Idle> {
+        name: name.to_string(),
+        species: Species::Human,
+        inventory: Inventory::limited(5.0),
+        carry_upgrades: Vec::new(),
+        pos,
+        break_progress: BasicAction::new(600.0),
+        exhausted: false,
+        resolve: Resolve::new(),
+        skills: Skills::new(),
+        action_data: Idle(),
+    })
+}
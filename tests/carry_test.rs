@@ -0,0 +1,82 @@
+use rusty_rain::{
+    config::{buildings::Buildings, inventory::InventoryItems},
+    math::Pos,
+    world::{
+        World,
+        building::Building,
+        inventory::Inventory,
+        worker::{Worker, carry::CarryUpgrade},
+    },
+    world_interaction::commands::{self, BuildMethod},
+};
+
+pub fn test_weight() {
+    let mut inventory = Inventory::limited(5.0);
+    inventory.add(&InventoryItems::Stone, 2.0);
+
+    //stone is heavy
+    assert_eq!(inventory.total_items(), 2.0);
+    assert_eq!(inventory.total_weight(), 4.0);
+    assert_eq!(inventory.fits(&InventoryItems::Wood), 1.0);
+    assert_eq!(inventory.fits(&InventoryItems::Berries), 2.0);
+    assert!(!inventory.is_full());
+
+    inventory.add(&InventoryItems::Wood, 1.0);
+    assert!(inventory.is_full());
+    assert_eq!(inventory.fits(&InventoryItems::Berries), 0.0);
+}
+
+pub fn test_upgrades(mut world: World) {
+    let mut worker = world.spawn_worker("Packmule".to_string(), Pos::new(3, 3));
+    assert_eq!(worker.carry_capacity(), 5.0);
+
+    worker.add_carry_upgrade(CarryUpgrade::Tools);
+    worker.add_carry_upgrade(CarryUpgrade::Tools);
+    assert_eq!(worker.carry_capacity(), 7.0);
+    assert_eq!(worker.inventory().limit, 7.0);
+
+    worker.remove_carry_upgrade(CarryUpgrade::Tools);
+    assert_eq!(worker.carry_capacity(), 5.0);
+    assert_eq!(worker.inventory().limit, 5.0);
+}
+
+pub fn test_multi_item_load(mut world: World) {
+    let hearth_pos = Pos::new(world.map.width() / 2, world.map.height() / 2);
+    let tender = world.spawn_worker("Hearth Tender".to_string(), hearth_pos);
+    let maybe_hearth = commands::build(
+        &mut world,
+        Buildings::MainHearth,
+        hearth_pos,
+        BuildMethod::SpawnExisting,
+    );
+    if let Some(Building { building_base, .. }) = maybe_hearth {
+        building_base.workers.push_back(tender);
+    }
+
+    let maybe_store = commands::build(
+        &mut world,
+        Buildings::MainStore,
+        Pos::new(4, 3),
+        BuildMethod::SpawnExisting,
+    );
+    if let Some(Building { building_base, .. }) = maybe_store {
+        building_base.output.add(&InventoryItems::Wood, 3.0);
+        building_base.output.add(&InventoryItems::Coal, 1.0);
+        building_base.output.add(&InventoryItems::Oil, 4.0);
+    }
+
+    world.next_tick(1.0 / 30.0);
+
+    //one trip for all the fuel that fits - oil first, as there is the most of it
+    let hearth = world.get_hearths().next().unwrap();
+    let tender = hearth.0.workers.front().unwrap();
+    let reservation = if let Worker::Supplying(w) = tender {
+        &w.action_data.1
+    } else {
+        panic!("The tender should be supplying fuel");
+    };
+
+    assert_eq!(reservation.items.len(), 2);
+    assert_eq!(reservation.items[0], (InventoryItems::Oil, 4.0));
+    assert_eq!(reservation.items[1], (InventoryItems::Wood, 1.0));
+}
@@ -0,0 +1,118 @@
+use rusty_rain::{
+    config::{buildings::Buildings, inventory::InventoryItems, species::Species},
+    math::Pos,
+    world::{
+        World,
+        actions::BasicAction,
+        building::Building,
+        inventory::Inventory,
+        worker::{
+            Idle, Worker,
+            resolve::{Resolve, ResolveFactor},
+            skills::Skills,
+            worker_states::WorkerWithAction,
+        },
+    },
+    world_interaction::commands::{self, BuildMethod},
+};
+use std::io::Write;
+
+use rusty_rain::FRAME_NUM;
+use std::sync::atomic::Ordering;
+
+pub fn test(mut world: World) {
+    let _ = env_logger::builder()
+        .format(|buf, record| {
+            let tick_num = FRAME_NUM.load(Ordering::Relaxed);
+            writeln!(buf, "@{}\t{}", tick_num, record.args())?;
+            Ok(())
+        })
+        .try_init();
+
+    configure_world(&mut world, Pos::new(6, 9));
+
+    let coverage = world.get_decoration_coverage();
+    assert!(coverage.contains_key(&Pos::new(6, 9)));
+    assert!(coverage.contains_key(&Pos::new(3, 6)));
+    assert!(!coverage.contains_key(&Pos::new(12, 3)));
+
+    //only the tiles around the statue are looked at - but no covered tile is missed
+    let area = world.get_decoration_areas().pop().unwrap();
+    let covered = (0..world.map.height())
+        .flat_map(|y| (0..world.map.width()).map(move |x| Pos::new(x, y)))
+        .filter(|p| area.covers(p))
+        .count();
+    assert_eq!(coverage.len(), covered);
+
+    let mut seconds = 0.0;
+    const DELTA: f32 = 1.0 / 30.0;
+    while seconds < 30.0 {
+        world.next_tick(DELTA);
+        seconds += DELTA;
+    }
+
+    //the tender walks past the statue when fetching wood from the store
+    let resolve = world.get_worker_resolve("Hearth Tender").unwrap();
+    let (_, bonus) = resolve
+        .factors
+        .iter()
+        .find(|(f, _)| *f == ResolveFactor::Decorations)
+        .unwrap();
+
+    let statue_bonus = Buildings::Statue.get_data().decoration.as_ref().unwrap().resolve_bonus;
+    assert!(*bonus > 0.0);
+    assert!(*bonus <= statue_bonus);
+}
+
+/// Whole walks that finish within a single tick still count - the worker walked past the statue,
+/// even though they were not walking anymore when the resolve was updated
+pub fn test_long_tick(mut world: World) {
+    //half way to the store, so that it does not cover either end of the walk. Every walk is over
+    //within a single tick
+    configure_world(&mut world, Pos::new(10, 10));
+
+    let mut seconds = 0.0;
+    const DELTA: f32 = 100.0;
+    while seconds < 300.0 {
+        world.next_tick(DELTA);
+        seconds += DELTA;
+    }
+
+    let resolve = world.get_worker_resolve("Hearth Tender").unwrap();
+    assert!(resolve.factors.iter().any(|(f, _)| *f == ResolveFactor::Decorations));
+}
+
+fn configure_world(
+    world: &mut World,
+    statue_pos: Pos,
+) {
+    let maybe_hearth = commands::build(
+        world,
+        Buildings::MainHearth,
+        Pos::new(world.map.width() / 2, world.map.height() / 2),
+        BuildMethod::SpawnExisting,
+    );
+
+    if let Some(Building { building_base, .. }) = maybe_hearth {
+        building_base.workers.push_front(Worker::Idle(WorkerWithAction::<Idle> {
+            name: "Hearth Tender".to_string(),
+            species: Species::Human,
+            inventory: Inventory::limited(5.0),
+            carry_upgrades: Vec::new(),
+            pos: building_base.pos,
+            break_progress: BasicAction::new(120.0),
+            exhausted: false,
+            resolve: Resolve::new(),
+            skills: Skills::new(),
+            action_data: Idle(),
+        }))
+    };
+
+    let maybe_store = commands::build(world, Buildings::MainStore, Pos::new(4, 3), BuildMethod::SpawnExisting);
+    if let Some(Building { building_base, .. }) = maybe_store {
+        building_base.output.add(&InventoryItems::Wood, 40.0);
+    }
+
+    let maybe_statue = commands::build(world, Buildings::Statue, statue_pos, BuildMethod::SpawnExisting);
+    assert!(maybe_statue.is_some());
+}
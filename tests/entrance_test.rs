@@ -0,0 +1,110 @@
+use rusty_rain::{
+    ai::pathfinding::{self, pathfinding_helpers},
+    config::buildings::Buildings,
+    math::Pos,
+    world::{
+        World,
+        world_map::{TileType, resources::ResourceType},
+    },
+    world_interaction::commands::{self, BuildMethod},
+};
+
+/// Workers walk into the hearth through the door closer to them, and never through a wall
+pub fn test_paths(mut world: World) {
+    let hearth_pos = Pos::new(8, 8);
+    commands::build(
+        &mut world,
+        Buildings::MainHearth,
+        hearth_pos,
+        BuildMethod::SpawnExisting,
+    )
+    .unwrap();
+
+    let entrances = world.map.entrances(&hearth_pos).to_vec();
+    assert_eq!(entrances.len(), 2);
+
+    for (start, door) in [(Pos::new(9, 3), Pos::new(9, 8)), (Pos::new(10, 13), Pos::new(10, 11))] {
+        let path = pathfinding::path_into_building(&world.map, start, hearth_pos).unwrap();
+        assert!(*path.last().unwrap() == door);
+        let doorstep = path[path.len() - 2];
+        assert!(entrances.iter().any(|e| e.door == door && e.doorstep == doorstep));
+    }
+
+    //from the side - whichever door is cheaper, but still through a door
+    let path = pathfinding::path_into_building(&world.map, Pos::new(13, 10), hearth_pos).unwrap();
+    assert!(entrances.iter().any(|e| e.door == *path.last().unwrap()));
+
+    //one door blocked - the other one is used
+    *world.map.get_mut(&Pos::new(9, 7)) = ResourceType::tile_tree();
+    let path = pathfinding::path_into_building(&world.map, Pos::new(9, 3), hearth_pos).unwrap();
+    assert!(*path.last().unwrap() == Pos::new(10, 11));
+
+    let (_, path) =
+        pathfinding_helpers::closest_shop(Pos::new(9, 3), &world, |s| s.building_behaviour.is_hearth()).unwrap();
+    assert!(*path.last().unwrap() == Pos::new(10, 11));
+}
+
+/// Buildings cannot be placed with their entrance in a tree, or on the doorstep of another building
+pub fn test_can_build(mut world: World) {
+    //the store at (4, 3) would have its bottom door at (6, 6)
+    *world.map.get_mut(&Pos::new(6, 6)) = ResourceType::tile_tree();
+    assert!(
+        commands::build(
+            &mut world,
+            Buildings::MainStore,
+            Pos::new(4, 3),
+            BuildMethod::SpawnExisting
+        )
+        .is_none()
+    );
+    *world.map.get_mut(&Pos::new(6, 6)) = TileType::Road;
+    assert!(
+        commands::build(
+            &mut world,
+            Buildings::MainStore,
+            Pos::new(4, 3),
+            BuildMethod::SpawnExisting
+        )
+        .is_some()
+    );
+
+    //a statue on the doorstep
+    assert!(
+        commands::build(
+            &mut world,
+            Buildings::Statue,
+            Pos::new(6, 6),
+            BuildMethod::SpawnExisting
+        )
+        .is_none()
+    );
+    assert!(
+        commands::build(
+            &mut world,
+            Buildings::Statue,
+            Pos::new(7, 7),
+            BuildMethod::SpawnExisting
+        )
+        .is_some()
+    );
+
+    //the door of the shelter would open right into the border trees
+    assert!(
+        commands::build(
+            &mut world,
+            Buildings::Shelter,
+            Pos::new(10, 12),
+            BuildMethod::SpawnBuildZone
+        )
+        .is_none()
+    );
+    assert!(
+        commands::build(
+            &mut world,
+            Buildings::Shelter,
+            Pos::new(10, 11),
+            BuildMethod::SpawnBuildZone
+        )
+        .is_some()
+    );
+}
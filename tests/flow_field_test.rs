@@ -0,0 +1,109 @@
+use rusty_rain::{
+    ai::pathfinding::{self, flow_field::FlowField},
+    config::buildings::Buildings,
+    math::Pos,
+    world::{
+        World,
+        world_map::{Movement, TileType, WorldMap, resources::ResourceType},
+    },
+    world_interaction::commands::{self, BuildMethod},
+};
+
+/// Walking into the store follows its flow field - no searching, and just as cheap as searching
+pub fn test_paths(mut world: World) {
+    let store_pos = Pos::new(4, 3);
+    commands::build(&mut world, Buildings::MainStore, store_pos, BuildMethod::SpawnExisting).unwrap();
+    let entrances = world.map.entrances(&store_pos).to_vec();
+
+    let searches = world.map.path_cache_stats();
+    for start in [Pos::new(12, 12), Pos::new(3, 13), Pos::new(12, 2)] {
+        let path = pathfinding::path_into_building(&world.map, start, store_pos).unwrap();
+        assert!(path[0] == start);
+        let door = *path.last().unwrap();
+        let doorstep = path[path.len() - 2];
+        assert!(entrances.iter().any(|e| e.door == door && e.doorstep == doorstep));
+
+        //the field knows the cost of the whole way
+        let cost: f32 = world.map.path_to_cost(&path)[1..].iter().sum();
+        let sampled = world.sample_flow_field(&store_pos, &start).unwrap();
+        assert!((cost - sampled).abs() < 0.001);
+    }
+    assert_eq!(world.map.path_cache_stats(), searches);
+
+    //the hearth gets one too, the statue does not
+    commands::build(
+        &mut world,
+        Buildings::MainHearth,
+        Pos::new(8, 8),
+        BuildMethod::SpawnExisting,
+    )
+    .unwrap();
+    commands::build(
+        &mut world,
+        Buildings::Statue,
+        Pos::new(12, 4),
+        BuildMethod::SpawnExisting,
+    )
+    .unwrap();
+    assert!(world.get_flow_field_heat_map(&Pos::new(8, 8)).is_some());
+    assert!(world.get_flow_field_heat_map(&Pos::new(12, 4)).is_none());
+
+    //the heat map - trees and buildings cannot be walked through
+    let heat_map = world.get_flow_field_heat_map(&store_pos).unwrap();
+    assert_eq!(heat_map.len(), 16);
+    assert!(heat_map[0][0].is_none());
+    assert!(heat_map[4][5].is_none());
+    assert!(heat_map[6][6].unwrap() < heat_map[12][12].unwrap());
+}
+
+/// A change to the map recalculates only the part of the field that depended on it - the result
+/// has to be the same as calculating the whole field from scratch
+pub fn test_incremental() {
+    let mut map = WorldMap::new(48, 48);
+    let check_field = |map: &WorldMap, store_pos: Pos| {
+        let field = map.flow_field(&store_pos).unwrap();
+        let fresh = FlowField::new(map, store_pos, map.entrances(&store_pos).to_vec());
+        for (row, fresh_row) in field.rows().iter().zip(fresh.rows()) {
+            for (cost, fresh_cost) in row.iter().zip(fresh_row) {
+                match (cost, fresh_cost) {
+                    (Some(c), Some(f)) => assert!((c - f).abs() < 0.001),
+                    (None, None) => {}
+                    _ => panic!("the field does not match the fresh one"),
+                }
+            }
+        }
+        field.updated_tiles
+    };
+
+    //a wall in the middle, with a single gap
+    for y in (0..48).filter(|y| *y != 24) {
+        *map.get_mut(&Pos::new(30, y)) = ResourceType::tile_tree();
+    }
+    let mut world = World::new(map);
+    let store_pos = Pos::new(10, 10);
+    commands::build(&mut world, Buildings::MainStore, store_pos, BuildMethod::SpawnExisting).unwrap();
+    let full = check_field(&world.map, store_pos);
+
+    //a bush far in the corner only concerns the tiles behind it
+    *world.map.get_mut(&Pos::new(2, 45)) = ResourceType::tile_berry();
+    let updated = check_field(&world.map, store_pos) - full;
+    assert!(updated > 0 && updated < full / 10);
+
+    //closing the gap cuts off everything behind the wall
+    *world.map.get_mut(&Pos::new(30, 24)) = ResourceType::tile_tree();
+    check_field(&world.map, store_pos);
+    assert!(world.sample_flow_field(&store_pos, &Pos::new(40, 40)).is_none());
+
+    //a road around the corner of the wall
+    for y in 0..48 {
+        *world.map.get_mut(&Pos::new(30, y)) = TileType::Road;
+    }
+    check_field(&world.map, store_pos);
+    assert!(world.sample_flow_field(&store_pos, &Pos::new(40, 40)).is_some());
+
+    world.map.set_movement(Movement::EightWay);
+    check_field(&world.map, store_pos);
+    *world.map.get_mut(&Pos::new(20, 20)) = ResourceType::tile_tree();
+    *world.map.get_mut(&Pos::new(21, 21)) = ResourceType::tile_tree();
+    check_field(&world.map, store_pos);
+}
@@ -1,14 +1,18 @@
 use std::io::Write;
 
 use rusty_rain::{
-    config::{buildings::Buildings, inventory::InventoryItems},
+    config::{buildings::Buildings, inventory::InventoryItems, resources::ResourceNodes, species::Species},
     math::Pos,
     world::{
         World,
         actions::BasicAction,
         building::Building,
         inventory::Inventory,
-        worker::{Idle, Worker, worker_states::WorkerWithAction},
+        worker::{Idle, Worker, resolve::Resolve, skills::Skills, worker_states::WorkerWithAction},
+        world_map::{
+            TileType,
+            resources::{ResourceCharge, ResourceType},
+        },
     },
     world_interaction::commands::{self, BuildMethod},
 };
@@ -35,13 +39,110 @@ pub fn test(mut world: World) {
         seconds += DELTA;
     }
 
-    let store = world.get_stores().nth(0).unwrap();
+    let store = world.get_stores().next().unwrap();
 
     //by the end of this test, some wood should be gathered and transfered to the store.
 
     assert!(store.0.output.total_items() > 0.0);
 }
 
+/// Everything about the gathering comes from the node - a tree gives a piece of resin every 5th
+/// time
+pub fn test_node_charge() {
+    let mut charge = ResourceCharge::new(ResourceNodes::Tree);
+    let mut gathered = Inventory::new();
+    while charge.current > 0.0 {
+        gathered.add_range(charge.gather());
+    }
+
+    let config = ResourceNodes::Tree.get_config();
+    assert_eq!(
+        gathered.get(&InventoryItems::Wood),
+        config.total_charges * config.yield_per_gather
+    );
+    assert_eq!(gathered.get(&InventoryItems::Resin), 2.0);
+
+    //the berry tile keeps its herbs, and enough charges to feed a small settlement for a while
+    let TileType::Resource(_, charge, _) = ResourceType::tile_berry() else {
+        panic!("the berry tile should be a resource");
+    };
+    assert_eq!(charge.current, 50.0);
+    assert_eq!(charge.config().bonus_resources, vec![(InventoryItems::Herbs, 0.2)]);
+
+    //odds piled up past a whole one give several bonus items at once
+    let mut charge = ResourceCharge::new(ResourceNodes::DewberryBush);
+    charge.bonus_progress[0] = 1.9;
+    assert_eq!(
+        charge.gather(),
+        vec![(InventoryItems::Berries, 1.0), (InventoryItems::Berries, 2.0)]
+    );
+    assert!((charge.bonus_progress[0] - 0.1).abs() < 0.001);
+}
+
+/// The forager picks the berry bush clean, and leaves the trees alone
+pub fn test_forager(mut world: World) {
+    let _ = env_logger::builder()
+        .format(|buf, record| {
+            let tick_num = FRAME_NUM.load(Ordering::Relaxed);
+            writeln!(buf, "@{}\t{}", tick_num, record.args())?;
+            Ok(())
+        })
+        .try_init();
+
+    let maybe_store = commands::build(
+        &mut world,
+        Buildings::MainStore,
+        Pos::new(4, 3),
+        BuildMethod::SpawnExisting,
+    );
+    if maybe_store.is_none() {
+        panic!();
+    }
+
+    let maybe_forager = commands::build(
+        &mut world,
+        Buildings::Forager,
+        Pos::new(11, 5),
+        BuildMethod::SpawnExisting,
+    );
+    if let Some(Building { building_base, .. }) = maybe_forager {
+        building_base.workers.push_back(Worker::Idle(WorkerWithAction::<Idle> {
+            name: "Picky Pete".to_string(),
+            species: Species::Human,
+            inventory: Inventory::limited(5.0),
+            carry_upgrades: Vec::new(),
+            pos: building_base.pos,
+            break_progress: BasicAction::new(600.0),
+            exhausted: false,
+            resolve: Resolve::new(),
+            skills: Skills::new(),
+            action_data: Idle(),
+        }));
+    } else {
+        panic!();
+    }
+
+    let bush = Pos::new(3, 12);
+    let mut seconds = 0.0;
+    const DELTA: f32 = 1.0 / 30.0;
+    while matches!(world.map.get(&bush), TileType::Resource(_, _, _)) {
+        world.next_tick(DELTA);
+        seconds += DELTA;
+        assert!(seconds < 45.0 * 60.0, "the bush should have been picked clean by now");
+    }
+
+    let config = ResourceNodes::BerryBush.get_config();
+    //each of the charges takes at least the gathering time, slowed down by the forager
+    let forager_efficiency = Buildings::Forager.get_data().gathering_efficiency;
+    assert!(seconds > config.total_charges * config.gathering_time / forager_efficiency);
+
+    if let TileType::Resource(_, charge, _) = world.map.get(&Pos::new(3, 7)) {
+        assert_eq!(charge.current, ResourceNodes::Tree.get_config().total_charges);
+    } else {
+        panic!("the tree should still be there");
+    }
+}
+
 pub fn configure_world_for_gathering_testing(world: &mut World) {
     let maybe_store = commands::build(world, Buildings::MainStore, Pos::new(4, 3), BuildMethod::SpawnExisting);
     if let Some(Building { building_base, .. }) = maybe_store {
@@ -58,10 +159,14 @@ pub fn configure_world_for_gathering_testing(world: &mut World) {
     if let Some(Building { building_base, .. }) = maybe_woodcutter {
         building_base.workers.push_back(Worker::Idle(WorkerWithAction::<Idle> {
             name: "Woodchuck Chuck".to_string(),
+            species: Species::Human,
             inventory: Inventory::limited(5.0),
+            carry_upgrades: Vec::new(),
             pos: building_base.pos,
             break_progress: BasicAction::new(120.0),
             exhausted: false,
+            resolve: Resolve::new(),
+            skills: Skills::new(),
             action_data: Idle(),
         }));
     };
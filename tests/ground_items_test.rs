@@ -0,0 +1,123 @@
+use log::info;
+use rusty_rain::{
+    config::{buildings::Buildings, inventory::InventoryItems, species::Species},
+    math::Pos,
+    world::{
+        World,
+        actions::BasicAction,
+        building::{Building, BuildingBehaviour},
+        inventory::Inventory,
+        worker::{LostAction, Worker, resolve::Resolve, skills::Skills, worker_states::WorkerWithAction},
+        world_map::{TileType, resources::ResourceType},
+    },
+    world_interaction::commands::{self, BuildMethod},
+};
+use std::io::Write;
+
+use rusty_rain::FRAME_NUM;
+use std::sync::atomic::Ordering;
+
+pub fn test(mut world: World) {
+    let _ = env_logger::builder()
+        .format(|buf, record| {
+            let tick_num = FRAME_NUM.load(Ordering::Relaxed);
+            writeln!(buf, "@{}\t{}", tick_num, record.args())?;
+            Ok(())
+        })
+        .try_init();
+
+    configure_world(&mut world);
+
+    let mut seconds = 0.0;
+    const DELTA: f32 = 1.0 / 30.0;
+    while seconds < 20.0 {
+        world.next_tick(DELTA);
+        seconds += DELTA;
+    }
+
+    //no way to the store - the load should have been dropped
+    {
+        let pile = world.get_item_piles().next().unwrap();
+        assert!(pile.pos == Pos::new(13, 13));
+        assert_eq!(pile.items.get(&InventoryItems::Wood), 5.0);
+        assert!(!pile.claimed);
+
+        let worker = world
+            .get_all_workers()
+            .into_iter()
+            .find(|w| w.name() == "Lost in the Woods")
+            .unwrap();
+        assert!(worker.inventory().is_empty());
+
+        let tile = world.map.get_mut(&Pos::new(13, 12));
+        *tile = TileType::Empty;
+    }
+
+    let mut seconds = 0.0;
+    while seconds < 60.0 {
+        world.next_tick(DELTA);
+        seconds += DELTA;
+    }
+
+    //the idle worker should have cleaned up the pile
+    assert!(world.get_item_piles().next().is_none());
+    let store = world.get_stores().next().unwrap();
+    info!("Store: {}", store.0.output);
+    assert_eq!(store.0.output.get(&InventoryItems::Wood), 5.0);
+
+    let hauler = world
+        .get_all_workers()
+        .into_iter()
+        .find(|w| w.name() == "Hauler")
+        .unwrap();
+    assert!(hauler.inventory().is_empty());
+}
+
+fn configure_world(world: &mut World) {
+    let hearth_pos = Pos::new(world.map.width() / 2, world.map.height() / 2);
+    let hauler = world.spawn_worker("Hauler".to_string(), hearth_pos);
+    let maybe_hearth = commands::build(world, Buildings::MainHearth, hearth_pos, BuildMethod::SpawnExisting);
+
+    if let Some(Building {
+        building_behaviour: BuildingBehaviour::Hearth(hearth),
+        ..
+    }) = maybe_hearth
+    {
+        hearth.unassigned_workers.push_back(hauler);
+    };
+
+    let maybe_woodcutter = commands::build(
+        world,
+        Buildings::Woodcutter,
+        Pos::new(11, 5),
+        BuildMethod::SpawnExisting,
+    );
+    if let Some(Building { building_base, .. }) = maybe_woodcutter {
+        let mut inventory = Inventory::limited(5.0);
+        inventory.add(&InventoryItems::Wood, 5.0);
+
+        building_base
+            .workers
+            .push_back(Worker::Lost(WorkerWithAction::<LostAction> {
+                name: "Lost in the Woods".to_string(),
+                species: Species::Human,
+                inventory,
+                carry_upgrades: Vec::new(),
+                pos: Pos::new(13, 13),
+                break_progress: BasicAction::new(Worker::TIME_TO_BREAK),
+                exhausted: false,
+                resolve: Resolve::new(),
+                skills: Skills::new(),
+                action_data: LostAction::new(),
+            }));
+    };
+
+    let extra_trees_pos = [Pos::new(12, 12), Pos::new(12, 13), Pos::new(13, 12)];
+    extra_trees_pos.iter().for_each(|p| {
+        let tile = world.map.get_mut(p);
+        *tile = ResourceType::tile_tree();
+    });
+
+    let maybe_store = commands::build(world, Buildings::MainStore, Pos::new(4, 3), BuildMethod::SpawnExisting);
+    assert!(maybe_store.is_some());
+}
@@ -1,12 +1,18 @@
 use rusty_rain::{
-    config::{buildings::Buildings, inventory::InventoryItems},
+    config::{buildings::Buildings, inventory::InventoryItems, species::Species},
     math::Pos,
     world::{
         World,
         actions::BasicAction,
-        building::Building,
+        building::{Building, BuildingBehaviour},
         inventory::Inventory,
-        worker::{Idle, Worker, worker_states::WorkerWithAction},
+        settlement::HearthOutPenalty,
+        worker::{
+            Idle, Worker,
+            resolve::{Resolve, ResolveFactor},
+            skills::Skills,
+            worker_states::WorkerWithAction,
+        },
     },
     world_interaction::commands::{self, BuildMethod},
 };
@@ -33,7 +39,7 @@ pub fn test(mut world: World) {
         seconds += DELTA;
     }
 
-    let hearth = world.get_hearths().nth(0).unwrap();
+    let hearth = world.get_hearths().next().unwrap();
     let tender = hearth.0.workers.front().unwrap();
     let b = tender.break_progress();
 
@@ -41,7 +47,97 @@ pub fn test(mut world: World) {
     //have been burned in the hearth
     assert!(b.progress < 3.0 * 60.0);
     assert!(!tender.exhausted());
-    assert!(hearth.1.input.total_items() < 15.0);
+
+    //the tender keeps the hearth stocked, so there is always some fuel left - count what was
+    //brought from the store instead
+    let store = world.get_stores().next().unwrap();
+    let delivered = 40.0 - store.0.output.get(&InventoryItems::Wood);
+    assert!(hearth.1.input.total_items() + 5.0 < delivered);
+}
+
+pub fn test_any_fuel(mut world: World) {
+    configure_world_for_hearth_testing(&mut world);
+
+    //swap the wood for coal
+    {
+        let store = world
+            .shops
+            .iter_mut()
+            .find(|s| s.building_behaviour.is_store())
+            .unwrap();
+        store.building_base.output.remove(&InventoryItems::Wood, 40.0);
+        store.building_base.output.add(&InventoryItems::Coal, 20.0);
+    }
+
+    let mut seconds = 0.0;
+    const DELTA: f32 = 1.0 / 30.0;
+    while seconds < 60.0 {
+        world.next_tick(DELTA);
+        seconds += DELTA;
+    }
+
+    let status = world.get_hearth_status();
+    assert!(status.burning);
+    assert!(status.penalties.is_empty());
+    assert_eq!(status.time_out, 0.0);
+
+    let store = world.get_stores().next().unwrap();
+    assert!(store.0.output.get(&InventoryItems::Coal) < 20.0);
+}
+
+pub fn test_out(mut world: World) {
+    configure_world_for_hearth_testing(&mut world);
+
+    {
+        let store = world
+            .shops
+            .iter_mut()
+            .find(|s| s.building_behaviour.is_store())
+            .unwrap();
+        store.building_base.output.remove(&InventoryItems::Wood, 40.0);
+    }
+
+    let mut seconds = 0.0;
+    const DELTA: f32 = 1.0 / 30.0;
+    while seconds < 30.0 {
+        world.next_tick(DELTA);
+        seconds += DELTA;
+    }
+
+    //nothing to burn - the settlement should be suffering
+    let status = world.get_hearth_status();
+    assert!(!status.burning);
+    assert_eq!(status.fuel, 0.0);
+    assert!(status.time_out > 29.0);
+    assert!(status.penalties.contains(&HearthOutPenalty::Resolve));
+    assert!(status.penalties.contains(&HearthOutPenalty::SlowerBreaks));
+    assert!(status.penalties.contains(&HearthOutPenalty::NoNewcomers));
+
+    let resolve = world.get_worker_resolve("Hearth Tender").unwrap();
+    assert!(
+        resolve
+            .factors
+            .iter()
+            .any(|(f, v)| *f == ResolveFactor::HearthOut && *v < 0.0)
+    );
+}
+
+/// The very last piece of fuel gets burned too
+pub fn test_last_fuel(mut world: World) {
+    configure_world_for_hearth_testing(&mut world);
+    for shop in world.shops.iter_mut() {
+        match &mut shop.building_behaviour {
+            BuildingBehaviour::Store(_) => shop.building_base.output.remove(&InventoryItems::Wood, 40.0),
+            BuildingBehaviour::Hearth(hearth) => hearth.input.add(&InventoryItems::Wood, 1.0),
+            _ => {}
+        }
+    }
+
+    world.next_tick(1.0 / 30.0);
+
+    assert!(world.get_hearth_status().burning);
+    let hearth = world.get_hearths().next().unwrap();
+    assert_eq!(hearth.1.fuel(), 0.0);
 }
 
 pub fn configure_world_for_hearth_testing(world: &mut World) {
@@ -55,10 +151,14 @@ pub fn configure_world_for_hearth_testing(world: &mut World) {
     if let Some(Building { building_base, .. }) = maybe_hearth {
         building_base.workers.push_front(Worker::Idle(WorkerWithAction::<Idle> {
             name: "Hearth Tender".to_string(),
+            species: Species::Human,
             inventory: Inventory::limited(5.0),
+            carry_upgrades: Vec::new(),
             pos: building_base.pos,
             break_progress: BasicAction::new(120.0),
             exhausted: false,
+            resolve: Resolve::new(),
+            skills: Skills::new(),
             action_data: Idle(),
         }))
     };
@@ -1,5 +1,3 @@
-use std::collections::LinkedList;
-
 use rusty_rain::{
     math::Pos,
     world::{
@@ -46,10 +44,5 @@ pub fn new_test_world(
     //plant tree
     *map.get_mut(&Pos::new(3, 7)) = ResourceType::tile_tree();
 
-    World {
-        map,
-        shops: LinkedList::new(),
-        frame_number: 0,
-        build_zones: LinkedList::new(),
-    }
+    World::new(map)
 }
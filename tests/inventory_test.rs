@@ -0,0 +1,28 @@
+use rusty_rain::config::inventory::{InventoryItems, ItemCategory, ItemTag};
+use strum::IntoEnumIterator;
+
+pub fn test_item_configs() {
+    //every item has to have a config
+    for item in InventoryItems::iter() {
+        let config = item.config();
+        assert!(!config.name.is_empty());
+        assert!(config.weight > 0.0);
+        for tag in config.category.tags() {
+            assert!(item.has_tag(tag));
+        }
+    }
+
+    assert!(InventoryItems::Wood.config().category == ItemCategory::BuildingMaterial);
+    assert!(InventoryItems::Wood.has_tag(ItemTag::Fuel));
+    assert_eq!(InventoryItems::PackOfCrops.config().name, "Pack of Crops");
+
+    let fuels = InventoryItems::with_tag(ItemTag::Fuel);
+    assert_eq!(fuels.first(), Some(&InventoryItems::Wood));
+    assert!(fuels.contains(&InventoryItems::Coal));
+    assert!(!fuels.contains(&InventoryItems::Berries));
+
+    let food = InventoryItems::with_tag(ItemTag::Food);
+    assert!(food.contains(&InventoryItems::Berries));
+    assert!(food.contains(&InventoryItems::Pie));
+    assert!(!InventoryItems::Pie.has_tag(ItemTag::BasicFood));
+}
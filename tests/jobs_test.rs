@@ -0,0 +1,223 @@
+use rusty_rain::{
+    config::{buildings::Buildings, inventory::InventoryItems, species::Species},
+    math::Pos,
+    world::{
+        World,
+        actions::BasicAction,
+        building::{Building, BuildingBehaviour},
+        inventory::Inventory,
+        jobs::{Job, JobBoard, JobKind},
+        worker::{Idle, Worker, resolve::Resolve, skills::Skills, worker_states::WorkerWithAction},
+    },
+    world_interaction::commands::{self, BuildMethod},
+};
+use std::io::Write;
+
+use rusty_rain::FRAME_NUM;
+use std::sync::atomic::Ordering;
+
+pub fn test_board() {
+    let mut board = JobBoard::new();
+    let woodcutter = Pos::new(2, 2);
+    let zone = Pos::new(5, 5);
+    let other_zone = Pos::new(8, 8);
+
+    board.post(JobKind::Gather, woodcutter, vec![InventoryItems::Wood], Job::UNLIMITED);
+    board.post(JobKind::Build, zone, Vec::new(), 1);
+    board.post(JobKind::Build, other_zone, Vec::new(), 1);
+    board.post(JobKind::Collect, Pos::new(3, 3), Vec::new(), 1);
+
+    //the workers of the building help out with the zones first - building beats gathering. Then
+    //they all can gather, and nobody else can gather for the woodcutter
+    for zone in [zone, other_zone] {
+        let job = board.take(|j| j.is_for(woodcutter)).unwrap();
+        assert!(job.kind == JobKind::Build && job.pos == zone);
+    }
+    for _ in 0..3 {
+        let job = board.take(|j| j.is_for(woodcutter)).unwrap();
+        assert!(job.kind == JobKind::Gather);
+    }
+    let job = board.take(|j| j.is_for(Pos::new(9, 9))).unwrap();
+    assert!(job.kind == JobKind::Collect);
+    assert!(board.take(|j| j.is_for(Pos::new(9, 9))).is_none());
+
+    //building beats collecting, and the zones go in the order they were placed
+    board.clear();
+    board.post(JobKind::Build, zone, Vec::new(), 1);
+    board.post(JobKind::Build, other_zone, Vec::new(), 1);
+    board.post(JobKind::Collect, Pos::new(3, 3), Vec::new(), 1);
+    let job = board.take(|j| j.kind.is_open()).unwrap();
+    assert!(job.kind == JobKind::Build && job.pos == zone);
+    let job = board.take(|j| j.kind.is_open()).unwrap();
+    assert!(job.kind == JobKind::Build && job.pos == other_zone);
+    let job = board.take(|j| j.kind.is_open()).unwrap();
+    assert!(job.kind == JobKind::Collect);
+    assert!(board.take(|j| j.kind.is_open()).is_none());
+
+    //the player rather has the piles cleaned up, and the second zone built first
+    board.clear();
+    board.set_priority(JobKind::Collect, 100);
+    board.set_building_priority(other_zone, 5);
+    board.post(JobKind::Build, zone, Vec::new(), 1);
+    board.post(JobKind::Build, other_zone, Vec::new(), 1);
+    board.post(JobKind::Collect, Pos::new(3, 3), Vec::new(), 1);
+
+    let kinds: Vec<(JobKind, Pos)> = (0..3)
+        .map(|_| board.take(|j| j.kind.is_open()).unwrap())
+        .map(|j| (j.kind, j.pos))
+        .collect();
+    assert!(
+        kinds
+            == vec![
+                (JobKind::Collect, Pos::new(3, 3)),
+                (JobKind::Build, other_zone),
+                (JobKind::Build, zone)
+            ]
+    );
+
+    //withdrawing only removes the jobs of the building
+    board.post(JobKind::Gather, woodcutter, vec![InventoryItems::Wood], Job::UNLIMITED);
+    board.post(JobKind::Build, zone, Vec::new(), 1);
+    board.withdraw(woodcutter);
+    assert_eq!(board.jobs().len(), 1);
+}
+
+pub fn test_build_order(mut world: World) {
+    let _ = env_logger::builder()
+        .format(|buf, record| {
+            let tick_num = FRAME_NUM.load(Ordering::Relaxed);
+            writeln!(buf, "@{}\t{}", tick_num, record.args())?;
+            Ok(())
+        })
+        .try_init();
+
+    let (first, second) = configure_world(&mut world);
+
+    //there is only wood for one shelter - the one placed later should get it
+    commands::set_building_priority(&mut world, second, 10);
+
+    //both zones and the empty hearth are asking for help
+    world.next_tick(1.0 / 30.0);
+    assert!(
+        world
+            .get_jobs()
+            .iter()
+            .any(|j| j.kind == JobKind::RefuelHearth && j.items.contains(&InventoryItems::Wood))
+    );
+
+    let mut seconds = 0.0;
+    const DELTA: f32 = 1.0 / 30.0;
+    while seconds < 90.0 {
+        world.next_tick(DELTA);
+        seconds += DELTA;
+    }
+
+    assert!(
+        world
+            .shops
+            .iter()
+            .any(|s| s.building_base.building == Buildings::Shelter && s.building_base.pos == second)
+    );
+    //nothing left for the first one
+    let first_zone = world
+        .get_all_build_zones()
+        .into_iter()
+        .find(|bz| bz.building.building_base.pos == first)
+        .unwrap();
+    assert!(first_zone.materials_delivered.is_empty());
+}
+
+/// Nobody is unassigned - the woodcutter leaves the trees for a while and builds the shelter, as
+/// building is more important than gathering. Then goes back to the camp
+pub fn test_helping_out(mut world: World) {
+    let maybe_store = commands::build(
+        &mut world,
+        Buildings::MainStore,
+        Pos::new(4, 3),
+        BuildMethod::SpawnExisting,
+    );
+    if let Some(Building { building_base, .. }) = maybe_store {
+        building_base.output.add(&InventoryItems::Wood, 4.0);
+    } else {
+        panic!();
+    }
+
+    let camp_pos = Pos::new(10, 10);
+    let worker = world.spawn_worker("Chuck".to_string(), camp_pos);
+    if let Some(Building { building_base, .. }) =
+        commands::build(&mut world, Buildings::Woodcutter, camp_pos, BuildMethod::SpawnExisting)
+    {
+        building_base.workers.push_back(worker);
+    } else {
+        panic!();
+    }
+
+    let shelter_pos = Pos::new(12, 3);
+    commands::build(&mut world, Buildings::Shelter, shelter_pos, BuildMethod::SpawnBuildZone).unwrap();
+
+    let mut seconds = 0.0;
+    const DELTA: f32 = 1.0 / 30.0;
+    while seconds < 90.0 {
+        world.next_tick(DELTA);
+        seconds += DELTA;
+    }
+
+    assert!(
+        world
+            .shops
+            .iter()
+            .any(|s| s.building_base.building == Buildings::Shelter && s.building_base.pos == shelter_pos)
+    );
+    let camp = world
+        .shops
+        .iter()
+        .find(|s| s.building_base.building == Buildings::Woodcutter)
+        .unwrap();
+    assert_eq!(camp.building_base.workers.len(), 1);
+    assert!(!matches!(
+        camp.building_base.workers.front().unwrap(),
+        Worker::Building(_) | Worker::SupplyingBuildZone(_)
+    ));
+}
+
+fn configure_world(world: &mut World) -> (Pos, Pos) {
+    let maybe_hearth = commands::build(world, Buildings::MainHearth, Pos::new(8, 8), BuildMethod::SpawnExisting);
+
+    if let Some(Building {
+        building_base,
+        building_behaviour: BuildingBehaviour::Hearth(hearth),
+    }) = maybe_hearth
+    {
+        hearth
+            .unassigned_workers
+            .push_front(Worker::Idle(WorkerWithAction::<Idle> {
+                name: "Bob".to_string(),
+                species: Species::Human,
+                inventory: Inventory::limited(5.0),
+                carry_upgrades: Vec::new(),
+                pos: building_base.pos,
+                break_progress: BasicAction::new(120.0),
+                exhausted: false,
+                resolve: Resolve::new(),
+                skills: Skills::new(),
+                action_data: Idle(),
+            }))
+    };
+
+    let maybe_store = commands::build(world, Buildings::MainStore, Pos::new(4, 3), BuildMethod::SpawnExisting);
+    if let Some(Building { building_base, .. }) = maybe_store {
+        building_base.output.add(&InventoryItems::Wood, 4.0);
+    } else {
+        panic!();
+    }
+
+    let first = Pos::new(2, 9);
+    let second = Pos::new(12, 3);
+    for pos in [first, second] {
+        if commands::build(world, Buildings::Shelter, pos, BuildMethod::SpawnBuildZone).is_none() {
+            panic!();
+        }
+    }
+
+    (first, second)
+}
@@ -1,13 +1,13 @@
 use log::info;
 use rusty_rain::{
-    config::{buildings::Buildings, inventory::InventoryItems},
+    config::{buildings::Buildings, inventory::InventoryItems, species::Species},
     math::Pos,
     world::{
         World,
         actions::BasicAction,
         building::Building,
         inventory::Inventory,
-        worker::{LostAction, Worker, worker_states::WorkerWithAction},
+        worker::{LostAction, Worker, resolve::Resolve, skills::Skills, worker_states::WorkerWithAction},
         world_map::{TileType, resources::ResourceType},
     },
     world_interaction::commands::{self, BuildMethod},
@@ -36,7 +36,7 @@ pub fn test(mut world: World) {
     }
 
     {
-        let hearth = world.get_hearths().nth(0).unwrap();
+        let hearth = world.get_hearths().next().unwrap();
         let tender = hearth.0.workers.front().unwrap();
         let b = tender.break_progress();
         info!("{} break progress", b.progress);
@@ -53,14 +53,16 @@ pub fn test(mut world: World) {
         *tile = TileType::Empty;
     }
 
+    //the tender is the only one who could have lit the hearth - it is out, so the break takes
+    //longer
     let mut seconds = 0.0;
-    while seconds < 60.0 {
+    while seconds < 75.0 {
         world.next_tick(DELTA);
         seconds += DELTA;
     }
 
     {
-        let hearth = world.get_hearths().nth(0).unwrap();
+        let hearth = world.get_hearths().next().unwrap();
         let tender = hearth.0.workers.front().unwrap();
         let b = tender.break_progress();
         info!("{} break progress", b.progress);
@@ -85,17 +87,21 @@ pub fn configure_world(world: &mut World) {
             .workers
             .push_front(Worker::Lost(WorkerWithAction::<LostAction> {
                 name: "Lost in the Woods".to_string(),
+                species: Species::Human,
                 inventory: Inventory::limited(5.0),
+                carry_upgrades: Vec::new(),
                 pos: Pos::new(13, 13), //unlucky number...
                 break_progress: BasicAction::new(Worker::TIME_TO_BREAK),
                 exhausted: false,
+                resolve: Resolve::new(),
+                skills: Skills::new(),
                 action_data: LostAction::new(),
             }))
     };
 
-    let extra_trees_pos = vec![Pos::new(12, 12), Pos::new(12, 13), Pos::new(13, 12)];
+    let extra_trees_pos = [Pos::new(12, 12), Pos::new(12, 13), Pos::new(13, 12)];
     extra_trees_pos.iter().for_each(|p| {
-        let tile = world.map.get_mut(&p);
+        let tile = world.map.get_mut(p);
         *tile = ResourceType::tile_tree();
     });
 
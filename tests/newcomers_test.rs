@@ -0,0 +1,86 @@
+use log::info;
+use rusty_rain::{
+    config::{buildings::Buildings, inventory::InventoryItems},
+    math::Pos,
+    world::{World, building::Building, events::WorldEvent},
+    world_interaction::commands::{self, BuildMethod},
+};
+use std::io::Write;
+
+use rusty_rain::FRAME_NUM;
+use std::sync::atomic::Ordering;
+
+pub fn test(mut world: World) {
+    let _ = env_logger::builder()
+        .format(|buf, record| {
+            let tick_num = FRAME_NUM.load(Ordering::Relaxed);
+            writeln!(buf, "@{}\t{}", tick_num, record.args())?;
+            Ok(())
+        })
+        .try_init();
+
+    configure_world(&mut world, 40.0);
+
+    let mut seconds = 0.0;
+    const DELTA: f32 = 1.0 / 30.0;
+    while seconds < 120.0 {
+        world.next_tick(DELTA);
+        seconds += DELTA;
+    }
+
+    //the tender is fed, housed and warm - newcomers should arrive in half the base time. There
+    //are 3 free beds in the shelter, so a group of 2
+    let events = world.take_events();
+    assert_eq!(events.len(), 1);
+
+    let WorldEvent::NewcomersArrived { names, hearth_pos } = &events[0] else {
+        panic!("Newcomers should have arrived");
+    };
+    info!("Arrived: {:?}", names);
+    assert_eq!(*names, vec!["Ash".to_string(), "Bramble".to_string()]);
+
+    let hearth = world.get_hearths().next().unwrap();
+    assert!(hearth.0.pos == *hearth_pos);
+    assert_eq!(hearth.1.unassigned_workers.len(), 2);
+    assert!(world.get_worker_resolve("Bramble").is_some());
+
+    //events are only reported once
+    assert!(world.take_events().is_empty());
+}
+
+pub fn test_no_fuel(mut world: World) {
+    configure_world(&mut world, 0.0);
+
+    let mut seconds = 0.0;
+    const DELTA: f32 = 1.0 / 30.0;
+    while seconds < 200.0 {
+        world.next_tick(DELTA);
+        seconds += DELTA;
+    }
+
+    //nobody wants to join a settlement with a cold hearth
+    assert!(world.take_events().is_empty());
+    let hearth = world.get_hearths().next().unwrap();
+    assert!(hearth.1.unassigned_workers.is_empty());
+}
+
+fn configure_world(
+    world: &mut World,
+    wood: f32,
+) {
+    let hearth_pos = Pos::new(world.map.width() / 2, world.map.height() / 2);
+    let tender = world.spawn_worker("Hearth Tender".to_string(), hearth_pos);
+    let maybe_hearth = commands::build(world, Buildings::MainHearth, hearth_pos, BuildMethod::SpawnExisting);
+
+    if let Some(Building { building_base, .. }) = maybe_hearth {
+        building_base.workers.push_front(tender)
+    };
+
+    let maybe_store = commands::build(world, Buildings::MainStore, Pos::new(4, 3), BuildMethod::SpawnExisting);
+    if let Some(Building { building_base, .. }) = maybe_store {
+        building_base.output.add(&InventoryItems::Wood, wood);
+    }
+
+    let maybe_shelter = commands::build(world, Buildings::Shelter, Pos::new(12, 3), BuildMethod::SpawnExisting);
+    assert!(maybe_shelter.is_some());
+}
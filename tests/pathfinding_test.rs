@@ -0,0 +1,388 @@
+use rusty_rain::{
+    ai::pathfinding::{
+        self, DistanceField,
+        hierarchical::{self, HierarchicalGraph},
+        pathfinding_helpers,
+    },
+    config::{buildings::Buildings, inventory::InventoryItems, species::Species},
+    math::Pos,
+    world::{
+        World,
+        actions::{BasicAction, TransitAction},
+        building::Building,
+        inventory::Inventory,
+        worker::{ReturningAction, Worker, resolve::Resolve, skills::Skills, worker_states::WorkerWithAction},
+        world_map::{Movement, TileType, WorldMap, resources::ResourceType},
+    },
+    world_interaction::commands::{self, BuildMethod},
+};
+
+/// Statue up north is closer, but the way there leads through the bushes. Statue to the east can
+/// be reached by the road
+pub fn test_closest_by_cost(mut map: WorldMap) {
+    for y in 2..14 {
+        for x in 2..14 {
+            *map.get_mut(&Pos::new(x, y)) = ResourceType::tile_berry();
+        }
+    }
+    (9..13).for_each(|x| *map.get_mut(&Pos::new(x, 8)) = TileType::Road);
+    *map.get_mut(&Pos::new(8, 5)) = TileType::Empty;
+    *map.get_mut(&Pos::new(13, 8)) = TileType::Empty;
+
+    let mut world = World::new(map);
+    commands::build(
+        &mut world,
+        Buildings::Statue,
+        Pos::new(8, 5),
+        BuildMethod::SpawnExisting,
+    )
+    .unwrap();
+    commands::build(
+        &mut world,
+        Buildings::Statue,
+        Pos::new(13, 8),
+        BuildMethod::SpawnExisting,
+    )
+    .unwrap();
+
+    let start = Pos::new(8, 8);
+    let (statue, path) =
+        pathfinding_helpers::closest_shop(start, &world, |s| s.building_base.building == Buildings::Statue).unwrap();
+
+    assert!(statue.building_base.pos == Pos::new(13, 8));
+    assert!(*path.first().unwrap() == start);
+    assert!(*path.last().unwrap() == Pos::new(13, 8));
+    assert_eq!(path.len(), 6);
+
+    //the single search finds the same path as a_star to the chosen target
+    let targets = [Pos::new(8, 5), Pos::new(13, 8)];
+    let (index, dijkstra_path) = pathfinding::dijkstra_closest_target(&world.map, start, &targets).unwrap();
+    assert_eq!(index, 1);
+    assert!(dijkstra_path == pathfinding::a_star(&world.map, start, targets[1]).unwrap());
+
+    //nothing to look for
+    assert!(pathfinding::dijkstra_closest_target(&world.map, start, &[]).is_none());
+}
+
+pub fn test_cache(mut map: WorldMap) {
+    let (start, goal) = (Pos::new(3, 3), Pos::new(12, 12));
+
+    let path = pathfinding::a_star(&map, start, goal).unwrap();
+    let cached = pathfinding::a_star(&map, start, goal).unwrap();
+    assert!(path == cached);
+    assert_eq!(map.path_cache_stats(), (1, 1));
+
+    //any change to the map makes the cached paths useless
+    let revision = map.revision;
+    *map.get_mut(&path[3]) = ResourceType::tile_tree();
+    assert_eq!(map.revision, revision + 1);
+
+    let new_path = pathfinding::a_star(&map, start, goal).unwrap();
+    assert!(!new_path.contains(&path[3]));
+    assert_eq!(map.path_cache_stats(), (1, 2));
+}
+
+/// A statue is built right in the way of a worker going back to the store
+pub fn test_repath(mut world: World) {
+    let store_pos = Pos::new(4, 3);
+    let start = Pos::new(10, 10);
+    commands::build(&mut world, Buildings::MainStore, store_pos, BuildMethod::SpawnExisting).unwrap();
+
+    let path = pathfinding::a_star(&world.map, start, store_pos).unwrap();
+    //statues cannot be built on the road
+    let blocked = *path[1..].iter().find(|p| *world.map.get(p) == TileType::Empty).unwrap();
+
+    let transit = TransitAction::new(path, &world.map);
+    let store = world.shops.back_mut().unwrap();
+    store
+        .building_base
+        .workers
+        .push_back(Worker::Returning(WorkerWithAction::<ReturningAction> {
+            name: "Unlucky".to_string(),
+            species: Species::Human,
+            inventory: Inventory::limited(5.0),
+            carry_upgrades: Vec::new(),
+            pos: start,
+            break_progress: BasicAction::new(Worker::TIME_TO_BREAK),
+            exhausted: false,
+            resolve: Resolve::new(),
+            skills: Skills::new(),
+            action_data: ReturningAction(transit),
+        }));
+
+    commands::build(&mut world, Buildings::Statue, blocked, BuildMethod::SpawnExisting).unwrap();
+    world.next_tick(1.0 / 30.0);
+
+    let store = world.get_stores().next().unwrap();
+    let Worker::Returning(worker) = store.0.workers.front().unwrap() else {
+        panic!("The worker should still be returning");
+    };
+    let new_path = &worker.action_data.0.path;
+    assert!(!new_path.contains(&blocked));
+    assert!(*new_path.first().unwrap() == start);
+    assert!(*new_path.last().unwrap() == store_pos);
+}
+
+/// 96x96 map with three walls of trees, each with a few gaps, and bushes all over the place
+pub fn big_map() -> WorldMap {
+    let mut map = WorldMap::new(96, 96);
+    for y in 0..96 {
+        for x in 0..96 {
+            if (x * 7 + y * 13) % 11 == 0 {
+                *map.get_mut(&Pos::new(x, y)) = ResourceType::tile_berry();
+            }
+        }
+    }
+    for (wall_x, gaps) in [(20, [5, 50, 90]), (45, [30, 31, 70]), (70, [10, 60, 61])] {
+        for y in (0..96).filter(|y| !gaps.contains(y)) {
+            *map.get_mut(&Pos::new(wall_x, y)) = ResourceType::tile_tree();
+        }
+    }
+    map
+}
+
+fn assert_valid_path(
+    map: &WorldMap,
+    path: &[Pos],
+    start: Pos,
+    end: Pos,
+) {
+    assert!(*path.first().unwrap() == start);
+    assert!(*path.last().unwrap() == end);
+    for pair in path.windows(2) {
+        assert_eq!(pair[0].x.abs_diff(pair[1].x) + pair[0].y.abs_diff(pair[1].y), 1);
+    }
+    assert!(path[1..path.len() - 1].iter().all(|p| map.get(p).is_traversible()));
+}
+
+/// Pricing many ways at once costs the same as searching them one by one. A bush patch in the
+/// middle makes the cheapest ways bend
+pub fn test_pricing(mut map: WorldMap) {
+    for y in 2..12 {
+        for x in 6..9 {
+            *map.get_mut(&Pos::new(x, y)) = ResourceType::tile_berry();
+        }
+    }
+    map.set_movement(Movement::EightWay);
+
+    let start = Pos::new(3, 3);
+    //the corner of the map is behind the trees
+    let targets = [Pos::new(12, 12), Pos::new(12, 3), start, Pos::new(0, 0)];
+    let costs = pathfinding::dijkstra_costs(&map, start, &targets);
+    for (target, cost) in targets.iter().zip(&costs).take(3) {
+        let path = pathfinding::a_star_uncached(&map, start, *target).unwrap();
+        assert!((cost.unwrap() - path_cost(&map, &path)).abs() < 0.01);
+    }
+    assert!(costs[3].is_none());
+
+    //the goal is walked up to, not stepped through. a_star steps in from the first tile next to it,
+    //so the field is compared to the single searches
+    let goal = Pos::new(12, 8);
+    *map.get_mut(&goal) = ResourceType::tile_tree();
+    let field = DistanceField::new(&map, goal);
+    for from in [Pos::new(3, 3), Pos::new(3, 12), Pos::new(13, 13), Pos::new(12, 7)] {
+        let cost = pathfinding::dijkstra_costs(&map, from, &[goal])[0].unwrap();
+        assert!((field.cost_from(&map, &from).unwrap() - cost).abs() < 0.01);
+    }
+    assert!(field.cost_from(&map, &Pos::new(0, 0)).is_none());
+}
+
+fn path_cost(
+    map: &WorldMap,
+    path: &[Pos],
+) -> f32 {
+    map.path_to_cost(path)[1..].iter().sum()
+}
+
+pub fn test_hierarchical() {
+    let mut map = big_map();
+    let mut graph = HierarchicalGraph::new(&map);
+
+    let pairs = [
+        (Pos::new(2, 2), Pos::new(93, 93)),
+        (Pos::new(93, 2), Pos::new(2, 93)),
+        (Pos::new(30, 40), Pos::new(80, 45)),
+        (Pos::new(10, 10), Pos::new(12, 80)),
+    ];
+    for (start, end) in pairs {
+        let path = graph.find_path(&map, start, end).unwrap();
+        assert_valid_path(&map, &path, start, end);
+
+        //not always the cheapest, but not far from it
+        let direct = pathfinding::a_star_uncached(&map, start, end).unwrap();
+        assert!(path_cost(&map, &path) <= path_cost(&map, &direct) * 1.2);
+    }
+
+    //close the gap in the middle wall used by the third path - only the clusters around it are
+    //recalculated
+    let (start, end) = pairs[2];
+    let path = graph.find_path(&map, start, end).unwrap();
+    let gap = *path.iter().find(|p| p.x == 45).unwrap();
+
+    let updated = graph.updated_clusters;
+    *map.get_mut(&gap) = ResourceType::tile_tree();
+    graph.mark_dirty(&gap);
+
+    let path = graph.find_path(&map, start, end).unwrap();
+    assert_valid_path(&map, &path, start, end);
+    assert!(!path.contains(&gap));
+    assert!(graph.updated_clusters - updated <= 5);
+
+    //no way through at all
+    for y in 0..96 {
+        *map.get_mut(&Pos::new(45, y)) = ResourceType::tile_tree();
+        graph.mark_dirty(&Pos::new(45, y));
+    }
+    assert!(graph.find_path(&map, start, end).is_none());
+}
+
+/// Big maps are searched hierarchically by a_star, and the map keeps the graph up to date itself
+pub fn test_hierarchical_a_star() {
+    let mut map = big_map();
+    let (start, end) = (Pos::new(30, 40), Pos::new(80, 45));
+
+    let path = pathfinding::a_star(&map, start, end).unwrap();
+    assert_valid_path(&map, &path, start, end);
+
+    let gap = *path.iter().find(|p| p.x == 45).unwrap();
+    *map.get_mut(&gap) = ResourceType::tile_tree();
+
+    let path = pathfinding::a_star(&map, start, end).unwrap();
+    assert_valid_path(&map, &path, start, end);
+    assert!(!path.contains(&gap));
+}
+
+/// With 8-way movement workers cut across the open field, but never squeeze between two trees
+pub fn test_diagonal(mut map: WorldMap) {
+    let (start, end) = (Pos::new(4, 4), Pos::new(10, 10));
+    let straight = pathfinding::a_star(&map, start, end).unwrap();
+
+    map.set_movement(Movement::EightWay);
+    let diagonal = pathfinding::a_star(&map, start, end).unwrap();
+    assert!(diagonal.len() < straight.len());
+    assert!(path_cost(&map, &diagonal) < path_cost(&map, &straight));
+    assert_valid_diagonal_path(&map, &diagonal, start, end);
+
+    //(2,2) is boxed in by the border trees on two sides - close the gap between them
+    *map.get_mut(&Pos::new(3, 2)) = ResourceType::tile_tree();
+    *map.get_mut(&Pos::new(2, 3)) = ResourceType::tile_tree();
+    assert!(pathfinding::a_star(&map, Pos::new(2, 2), Pos::new(8, 8)).is_none());
+}
+
+/// Diagonal steps cost more, so the worker stays on a diagonal tile longer than on a straight one
+pub fn test_diagonal_transit(mut map: WorldMap) {
+    map.set_movement(Movement::EightWay);
+    let path = vec![Pos::new(4, 4), Pos::new(5, 5), Pos::new(6, 5)];
+    let mut transit = TransitAction::new(path, &map);
+
+    let diagonal = std::f32::consts::SQRT_2;
+    assert!((transit.requirement - (1.0 + diagonal + 1.0)).abs() < 0.001);
+
+    transit.progress = 1.0 + diagonal - 0.1;
+    assert!(transit.current_pos() == Pos::new(5, 5));
+    transit.progress = 1.0 + diagonal + 0.1;
+    assert!(transit.current_pos() == Pos::new(6, 5));
+}
+
+/// Hierarchical search on a big map has to obey the same rules
+pub fn test_diagonal_hierarchical() {
+    let mut map = big_map();
+    map.set_movement(Movement::EightWay);
+
+    let (start, end) = (Pos::new(2, 2), Pos::new(93, 93));
+    let path = pathfinding::a_star(&map, start, end).unwrap();
+    assert_valid_diagonal_path(&map, &path, start, end);
+
+    let direct = pathfinding::a_star_uncached(&map, start, end).unwrap();
+    assert!(path_cost(&map, &path) <= path_cost(&map, &direct) * 1.2);
+
+    //straight through the corners of the clusters on the diagonal - no detour through the cluster
+    //next to them
+    let map = {
+        let mut map = WorldMap::new(64, 64);
+        map.set_movement(Movement::EightWay);
+        map
+    };
+    let (start, end) = (Pos::new(2, 2), Pos::new(60, 60));
+    let path = HierarchicalGraph::new(&map).find_path(&map, start, end).unwrap();
+    assert_valid_diagonal_path(&map, &path, start, end);
+    assert!(path.contains(&Pos::new(31, 31)) && path.contains(&Pos::new(32, 32)));
+    assert!((path_cost(&map, &path) - 58.0 * std::f32::consts::SQRT_2).abs() < 0.01);
+}
+
+/// Workers on a big map walk the paths found by the hierarchical search. The woodcutter brings the
+/// wood to the store and walks back to the camp - through the only gap in the wall between them
+pub fn test_big_world(mut world: World) {
+    assert!(world.map.width() * world.map.height() >= hierarchical::MIN_TILES);
+    world.map.set_movement(Movement::EightWay);
+
+    let gap = Pos::new(50, 20);
+    for x in 2..world.map.width() - 2 {
+        if x != gap.x {
+            *world.map.get_mut(&Pos::new(x, gap.y)) = ResourceType::tile_tree();
+        }
+    }
+
+    commands::build(
+        &mut world,
+        Buildings::MainStore,
+        Pos::new(4, 3),
+        BuildMethod::SpawnExisting,
+    );
+    let camp_pos = Pos::new(40, 40);
+    let worker = world.spawn_worker("Chuck".to_string(), camp_pos);
+    let maybe_camp = commands::build(&mut world, Buildings::Woodcutter, camp_pos, BuildMethod::SpawnExisting);
+    if let Some(Building { building_base, .. }) = maybe_camp {
+        building_base.workers.push_back(worker);
+        //full already - the first thing to do is to take it to the store
+        building_base.output.add(&InventoryItems::Wood, 10.0);
+    } else {
+        panic!();
+    }
+
+    let mut seconds = 0.0;
+    const DELTA: f32 = 1.0 / 30.0;
+    let mut way_back = None;
+    while seconds < 5.0 * 60.0 {
+        world.next_tick(DELTA);
+        seconds += DELTA;
+
+        let camp = world
+            .shops
+            .iter()
+            .find(|s| s.building_base.building == Buildings::Woodcutter)
+            .unwrap();
+        match camp.building_base.workers.front().unwrap() {
+            Worker::Returning(worker) if way_back.is_none() => way_back = Some(worker.action_data.0.path.clone()),
+            Worker::Idle(_) if way_back.is_some() => break,
+            _ => {}
+        }
+    }
+
+    let way_back = way_back.expect("The woodcutter should have stored the wood by now");
+    assert!(seconds < 5.0 * 60.0, "The woodcutter should be back at the camp by now");
+    let store = world.get_stores().next().unwrap();
+    assert!(store.0.output.get(&InventoryItems::Wood) > 0.0);
+
+    assert_valid_diagonal_path(&world.map, &way_back, way_back[0], *way_back.last().unwrap());
+    assert!(way_back.contains(&gap));
+}
+
+fn assert_valid_diagonal_path(
+    map: &WorldMap,
+    path: &[Pos],
+    start: Pos,
+    end: Pos,
+) {
+    assert!(*path.first().unwrap() == start);
+    assert!(*path.last().unwrap() == end);
+    for pair in path.windows(2) {
+        let (dx, dy) = (pair[0].x.abs_diff(pair[1].x), pair[0].y.abs_diff(pair[1].y));
+        assert!(dx <= 1 && dy <= 1 && dx + dy > 0);
+        if dx + dy == 2 {
+            assert!(map.get(&Pos::new(pair[1].x, pair[0].y)).is_traversible());
+            assert!(map.get(&Pos::new(pair[0].x, pair[1].y)).is_traversible());
+        }
+    }
+    assert!(path[1..path.len() - 1].iter().all(|p| map.get(p).is_traversible()));
+}
@@ -1,13 +1,13 @@
 use log::info;
 use rusty_rain::{
-    config::{buildings::Buildings, inventory::InventoryItems},
+    config::{buildings::Buildings, inventory::InventoryItems, species::Species},
     math::Pos,
     world::{
         World,
         actions::BasicAction,
         building::{Building, BuildingBase, BuildingBehaviour, StoreBehaviour},
         inventory::Inventory,
-        worker::{Idle, Worker, worker_states::WorkerWithAction},
+        worker::{Idle, Worker, resolve::Resolve, skills::Skills, worker_states::WorkerWithAction},
     },
     world_interaction::commands::{self, BuildMethod},
 };
@@ -46,17 +46,17 @@ pub fn test(mut world: World) {
                     None
                 }
             })
-            .nth(0)
+            .next()
             .unwrap();
 
-        let store = world.get_stores().nth(0).unwrap();
+        let store = world.get_stores().next().unwrap();
 
         info!("store has: {}", store.0.output);
         info!("lumbermill has: {}, input: {}", lumbermill.0.output, lumbermill.1.input);
     }
 
     {
-        let store = get_stores_mut(&mut world).nth(0).unwrap();
+        let store = get_stores_mut(&mut world).next().unwrap();
 
         //by the end of this test, some wood should be taken from the store, some planks produced and
         //brought to the store
@@ -87,10 +87,10 @@ pub fn test(mut world: World) {
                 None
             }
         })
-        .nth(0)
+        .next()
         .unwrap();
 
-    let store = world.get_stores().nth(0).unwrap();
+    let store = world.get_stores().next().unwrap();
     info!("store has: {}", store.0.output);
     info!("lumbermill has: {}, input: {}", lumbermill.0.output, lumbermill.1.input);
 
@@ -109,10 +109,14 @@ fn configure_world_for_production_testing(world: &mut World) {
     if let Some(Building { building_base, .. }) = maybe_lumbermill {
         building_base.workers.push_back(Worker::Idle(WorkerWithAction::<Idle> {
             name: "Jane".to_string(),
+            species: Species::Human,
             inventory: Inventory::limited(5.0),
+            carry_upgrades: Vec::new(),
             pos: building_base.pos,
             break_progress: BasicAction::new(120.0),
             exhausted: false,
+            resolve: Resolve::new(),
+            skills: Skills::new(),
             action_data: Idle(),
         }));
     };
@@ -0,0 +1,260 @@
+use rusty_rain::{
+    config::{buildings::Buildings, inventory::InventoryItems},
+    math::Pos,
+    world::{
+        World,
+        building::{Building, BuildingBehaviour},
+        inventory::Inventory,
+        worker::Worker,
+    },
+    world_interaction::commands::{self, BuildMethod},
+};
+
+pub fn test_ledger() {
+    let mut store = Inventory::new();
+    store.add(&InventoryItems::Wood, 10.0);
+    let store_pos = Pos::new(4, 3);
+
+    let first = store.reserve(store_pos, vec![(InventoryItems::Wood, 6.0)]);
+    assert_eq!(store.get(&InventoryItems::Wood), 10.0);
+    assert_eq!(store.available(&InventoryItems::Wood), 4.0);
+    assert!(store.reservations_valid());
+
+    //cannot reserve more than is available
+    let second = store.reserve(
+        store_pos,
+        vec![(InventoryItems::Wood, 6.0), (InventoryItems::Stone, 1.0)],
+    );
+    assert_eq!(second.items, vec![(InventoryItems::Wood, 4.0)]);
+    assert_eq!(store.available(&InventoryItems::Wood), 0.0);
+    assert!(!store.has_any_of(&vec![InventoryItems::Wood]));
+
+    //the worker got there
+    let taken = store.commit(first);
+    assert_eq!(taken, vec![(InventoryItems::Wood, 6.0)]);
+    assert_eq!(store.get(&InventoryItems::Wood), 4.0);
+    assert_eq!(store.available(&InventoryItems::Wood), 0.0);
+    assert!(store.reservations_valid());
+
+    //the other one did not
+    store.release(second);
+    assert_eq!(store.get(&InventoryItems::Wood), 4.0);
+    assert_eq!(store.available(&InventoryItems::Wood), 4.0);
+    assert_eq!(store.get_reserved(&InventoryItems::Wood), 0.0);
+    assert!(store.reservations_valid());
+}
+
+pub fn test_hearth_supplying(mut world: World) {
+    configure_world(&mut world, false);
+
+    let mut seconds = 0.0;
+    const DELTA: f32 = 1.0 / 30.0;
+    let mut seen_reserved = false;
+    while seconds < 60.0 {
+        world.next_tick(DELTA);
+        seconds += DELTA;
+
+        assert_invariants(&world);
+
+        //while the tender is on their way, the wood stays in the store - but nobody else can take it
+        let store = world.get_stores().next().unwrap();
+        let tender = world.get_hearths().next().unwrap().0.workers.front().unwrap();
+        if matches!(tender, Worker::Supplying(_)) {
+            seen_reserved = true;
+            assert!(store.0.output.get_reserved(&InventoryItems::Wood) > 0.0);
+            assert!(store.0.output.available(&InventoryItems::Wood) < store.0.output.get(&InventoryItems::Wood));
+        }
+    }
+    assert!(seen_reserved);
+}
+
+pub fn test_build_supplying(mut world: World) {
+    configure_world(&mut world, true);
+
+    let mut seconds = 0.0;
+    const DELTA: f32 = 1.0 / 30.0;
+    while seconds < 60.0 {
+        world.next_tick(DELTA);
+        seconds += DELTA;
+
+        assert_invariants(&world);
+    }
+
+    //everything picked up by now - no dangling reservations
+    for shop in &world.shops {
+        assert!(shop.building_base.output.reserved.is_empty());
+    }
+}
+
+pub fn test_release(mut world: World) {
+    configure_world(&mut world, false);
+
+    let store = world
+        .shops
+        .iter_mut()
+        .find(|s| s.building_behaviour.is_store())
+        .unwrap();
+    let store_pos = store.building_base.pos;
+    let reservation = store
+        .building_base
+        .output
+        .reserve(store_pos, vec![(InventoryItems::Wood, 40.0)]);
+    assert_eq!(store.building_base.output.available(&InventoryItems::Wood), 0.0);
+
+    //with everything reserved, the tender cannot fetch anything
+    let mut seconds = 0.0;
+    const DELTA: f32 = 1.0 / 30.0;
+    while seconds < 5.0 {
+        world.next_tick(DELTA);
+        seconds += DELTA;
+    }
+    assert_eq!(world.get_hearth_status().fuel, 0.0);
+    assert!(!world.get_hearth_status().burning);
+
+    world.release_reservation(reservation);
+    let store = world.get_stores().next().unwrap();
+    assert_eq!(store.0.output.available(&InventoryItems::Wood), 40.0);
+    assert_invariants(&world);
+
+    //the released wood is there for the taking again
+    while seconds < 60.0 {
+        world.next_tick(DELTA);
+        seconds += DELTA;
+        assert_invariants(&world);
+    }
+    assert!(world.get_hearth_status().burning);
+}
+
+/// The tender gets walled in on the way to the store - they give up, and the wood they reserved
+/// can be taken by anyone again
+pub fn test_release_on_give_up(mut world: World) {
+    configure_world(&mut world, false);
+
+    let mut seconds = 0.0;
+    const DELTA: f32 = 1.0 / 30.0;
+    let pos = loop {
+        world.next_tick(DELTA);
+        seconds += DELTA;
+        assert!(seconds < 60.0, "The tender should be on their way to the store by now");
+
+        let tender = world.get_hearths().next().unwrap().0.workers.front().unwrap();
+        if let Worker::Supplying(tender) = tender
+            && tender.action_data.0.current_index() >= 2
+        {
+            break tender.pos;
+        }
+    };
+    let store = world.get_stores().next().unwrap();
+    assert!(store.0.output.get_reserved(&InventoryItems::Wood) > 0.0);
+
+    for (x, y) in [(0, 0), (1, 0), (2, 0), (0, 1), (2, 1), (0, 2), (1, 2), (2, 2)] {
+        let wall = Pos::new(pos.x + x - 1, pos.y + y - 1);
+        commands::build(&mut world, Buildings::Statue, wall, BuildMethod::SpawnExisting);
+    }
+    world.next_tick(DELTA);
+
+    let tender = world.get_hearths().next().unwrap().0.workers.front().unwrap();
+    assert!(matches!(tender, Worker::Lost(_)));
+    let store = world.get_stores().next().unwrap();
+    assert_eq!(store.0.output.get_reserved(&InventoryItems::Wood), 0.0);
+    assert_eq!(store.0.output.available(&InventoryItems::Wood), 40.0);
+    assert_invariants(&world);
+}
+
+/// The whole output of the woodcutter is promised to someone else - there is nothing to store,
+/// so nobody walks to the store with empty hands
+pub fn test_reserved_output(mut world: World) {
+    commands::build(
+        &mut world,
+        Buildings::MainStore,
+        Pos::new(4, 3),
+        BuildMethod::SpawnExisting,
+    );
+
+    let woodcutter_pos = Pos::new(11, 5);
+    let worker = world.spawn_worker("Chuck".to_string(), woodcutter_pos);
+    let maybe_woodcutter = commands::build(
+        &mut world,
+        Buildings::Woodcutter,
+        woodcutter_pos,
+        BuildMethod::SpawnExisting,
+    );
+    let reservation = if let Some(Building { building_base, .. }) = maybe_woodcutter {
+        building_base.workers.push_back(worker);
+        building_base.output.add(&InventoryItems::Wood, 10.0);
+        assert!(building_base.output.is_full());
+        building_base
+            .output
+            .reserve(woodcutter_pos, vec![(InventoryItems::Wood, 10.0)])
+    } else {
+        panic!();
+    };
+
+    let mut seconds = 0.0;
+    const DELTA: f32 = 1.0 / 30.0;
+    while seconds < 10.0 {
+        world.next_tick(DELTA);
+        seconds += DELTA;
+
+        let woodcutter = world
+            .shops
+            .iter()
+            .find(|s| s.building_base.building == Buildings::Woodcutter)
+            .unwrap();
+        let worker = woodcutter.building_base.workers.front().unwrap();
+        assert!(
+            !matches!(worker, Worker::Storing(_)) || worker.inventory().total_items() > 0.0,
+            "Nothing to store - the wood is reserved"
+        );
+    }
+
+    world.release_reservation(reservation);
+    assert_invariants(&world);
+}
+
+fn assert_invariants(world: &World) {
+    for shop in &world.shops {
+        assert!(shop.building_base.output.reservations_valid());
+        for (item, amount) in shop.building_base.output.inv.iter() {
+            assert!(*amount >= 0.0, "{} went negative", item);
+        }
+    }
+}
+
+fn configure_world(
+    world: &mut World,
+    with_build_zone: bool,
+) {
+    let hearth_pos = Pos::new(world.map.width() / 2, world.map.height() / 2);
+    let worker = world.spawn_worker("Bob".to_string(), hearth_pos);
+    let maybe_hearth = commands::build(world, Buildings::MainHearth, hearth_pos, BuildMethod::SpawnExisting);
+
+    if let Some(Building {
+        building_base,
+        building_behaviour: BuildingBehaviour::Hearth(hearth),
+    }) = maybe_hearth
+    {
+        if with_build_zone {
+            hearth.unassigned_workers.push_back(worker);
+        } else {
+            building_base.workers.push_back(worker);
+        }
+    };
+
+    let maybe_store = commands::build(world, Buildings::MainStore, Pos::new(4, 3), BuildMethod::SpawnExisting);
+    if let Some(Building { building_base, .. }) = maybe_store {
+        building_base.output.add(&InventoryItems::Wood, 40.0);
+        building_base.output.add(&InventoryItems::Bricks, 2.0);
+        building_base.output.add(&InventoryItems::Fabric, 2.0);
+    }
+
+    if with_build_zone {
+        let maybe_build_zone = commands::build(
+            world,
+            Buildings::Lumbermill,
+            Pos::new(4, 8),
+            BuildMethod::SpawnBuildZone,
+        );
+        assert!(maybe_build_zone.is_some());
+    }
+}
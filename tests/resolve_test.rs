@@ -0,0 +1,132 @@
+use log::info;
+use rusty_rain::{
+    config::{buildings::Buildings, inventory::InventoryItems, species::Species},
+    math::Pos,
+    world::{
+        World,
+        actions::BasicAction,
+        building::Building,
+        inventory::Inventory,
+        worker::{
+            Idle, Worker,
+            resolve::{Resolve, ResolveFactor},
+            skills::Skills,
+            worker_states::WorkerWithAction,
+        },
+    },
+    world_interaction::commands::{self, BuildMethod},
+};
+use std::io::Write;
+
+use rusty_rain::FRAME_NUM;
+use std::sync::atomic::Ordering;
+
+pub fn test(mut world: World) {
+    let _ = env_logger::builder()
+        .format(|buf, record| {
+            let tick_num = FRAME_NUM.load(Ordering::Relaxed);
+            writeln!(buf, "@{}\t{}", tick_num, record.args())?;
+            Ok(())
+        })
+        .try_init();
+
+    configure_world(&mut world);
+
+    let mut seconds = 0.0;
+    const DELTA: f32 = 1.0 / 30.0;
+    while seconds < 3.0 * 60.0 {
+        world.next_tick(DELTA);
+        seconds += DELTA;
+    }
+
+    //by the end of this test, the tender should have taken a break and eaten some berries. Housed,
+    //fed and warm - they should be happy enough to work faster
+    let store = world.get_stores().next().unwrap();
+    assert!(store.0.output.get(&InventoryItems::Berries) < 10.0);
+
+    let resolve = world.get_worker_resolve("Hearth Tender").unwrap();
+    for (factor, value) in &resolve.factors {
+        info!("{}: {}", factor, value);
+    }
+
+    let factors: Vec<_> = resolve.factors.iter().map(|(f, _)| *f).collect();
+    assert!(factors.contains(&ResolveFactor::Fed));
+    assert!(factors.contains(&ResolveFactor::HearthBurning));
+    assert!(factors.contains(&ResolveFactor::Housed));
+    assert!(resolve.productivity() > 1.0);
+}
+
+pub fn test_leaving(mut world: World) {
+    configure_world_for_leaving(&mut world);
+
+    let mut seconds = 0.0;
+    const DELTA: f32 = 1.0 / 30.0;
+    while seconds < 2.0 * 60.0 {
+        world.next_tick(DELTA);
+        seconds += DELTA;
+    }
+
+    //hungry, exhausted and with no hearth to warm up - the worker should have left
+    assert!(world.get_worker_resolve("Grumpy").is_none());
+    assert!(world.get_all_workers().is_empty());
+}
+
+fn configure_world(world: &mut World) {
+    let maybe_hearth = commands::build(
+        world,
+        Buildings::MainHearth,
+        Pos::new(world.map.width() / 2, world.map.height() / 2),
+        BuildMethod::SpawnExisting,
+    );
+
+    if let Some(Building { building_base, .. }) = maybe_hearth {
+        building_base.workers.push_front(Worker::Idle(WorkerWithAction::<Idle> {
+            name: "Hearth Tender".to_string(),
+            species: Species::Human,
+            inventory: Inventory::limited(5.0),
+            carry_upgrades: Vec::new(),
+            pos: building_base.pos,
+            break_progress: BasicAction::new(120.0),
+            exhausted: false,
+            resolve: Resolve::new(),
+            skills: Skills::new(),
+            action_data: Idle(),
+        }))
+    };
+
+    let maybe_store = commands::build(world, Buildings::MainStore, Pos::new(4, 3), BuildMethod::SpawnExisting);
+    if let Some(Building { building_base, .. }) = maybe_store {
+        building_base.output.add(&InventoryItems::Wood, 40.0);
+        building_base.output.add(&InventoryItems::Berries, 10.0);
+    }
+
+    let maybe_shelter = commands::build(world, Buildings::Shelter, Pos::new(12, 3), BuildMethod::SpawnExisting);
+    assert!(maybe_shelter.is_some());
+}
+
+fn configure_world_for_leaving(world: &mut World) {
+    let maybe_woodcutter = commands::build(
+        world,
+        Buildings::Woodcutter,
+        Pos::new(11, 5),
+        BuildMethod::SpawnExisting,
+    );
+
+    if let Some(Building { building_base, .. }) = maybe_woodcutter {
+        let mut resolve = Resolve::new();
+        resolve.fed = false;
+
+        building_base.workers.push_back(Worker::Idle(WorkerWithAction::<Idle> {
+            name: "Grumpy".to_string(),
+            species: Species::Human,
+            inventory: Inventory::limited(5.0),
+            carry_upgrades: Vec::new(),
+            pos: building_base.pos,
+            break_progress: BasicAction::new(120.0),
+            exhausted: true,
+            resolve,
+            skills: Skills::new(),
+            action_data: Idle(),
+        }));
+    };
+}
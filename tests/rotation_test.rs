@@ -0,0 +1,72 @@
+use rusty_rain::{
+    ai::pathfinding,
+    config::buildings::Buildings,
+    math::Pos,
+    world::{
+        World,
+        building::rotation::Rotation,
+        world_map::{TileType, resources::ResourceType},
+    },
+    world_interaction::commands::{self, BuildMethod},
+};
+
+/// The 4x3 store turned by 90 degrees takes 3x4 tiles, and its doors turn with it
+pub fn test_footprint(mut world: World) {
+    let pos = Pos::new(8, 3);
+    let store = commands::build_rotated(
+        &mut world,
+        Buildings::MainStore,
+        pos,
+        Rotation::Deg90,
+        BuildMethod::SpawnExisting,
+    )
+    .unwrap();
+    assert!(store.building_base.rotation == Rotation::Deg90);
+    assert_eq!(store.building_base.footprint(), (3, 4));
+
+    for y in 3..7 {
+        for x in 8..11 {
+            assert!(matches!(world.map.get(&Pos::new(x, y)), TileType::Structure(_)));
+        }
+        assert!(!matches!(world.map.get(&Pos::new(11, y)), TileType::Structure(_)));
+    }
+
+    //unrotated, the doorsteps are below and to the left - now they are to the left and above
+    let entrances = world.map.entrances(&pos);
+    assert!(entrances[0].doorstep == Pos::new(7, 5) && entrances[0].door == Pos::new(8, 5));
+    assert!(entrances[1].doorstep == Pos::new(9, 2) && entrances[1].door == Pos::new(9, 3));
+
+    let path = pathfinding::path_into_building(&world.map, Pos::new(12, 12), pos).unwrap();
+    assert!(entrances.iter().any(|e| e.door == *path.last().unwrap()));
+
+    //four quarter turns are a full circle
+    let (mut x, mut y, mut width, mut height) = (-1, 1, 4, 3);
+    for _ in 0..4 {
+        (x, y) = Rotation::Deg90.rotate(x, y, width, height);
+        (width, height) = (height, width);
+    }
+    assert_eq!((x, y), (-1, 1));
+    assert!(Rotation::from_degrees(270) == Some(Rotation::Deg270));
+    assert!(Rotation::from_degrees(45).is_none());
+}
+
+/// The door of the lumber mill would face a tree - turned around, it fits into the same glade
+pub fn test_tight_glade(mut world: World) {
+    let pos = Pos::new(9, 2);
+    *world.map.get_mut(&Pos::new(10, 5)) = ResourceType::tile_tree();
+
+    assert!(commands::build(&mut world, Buildings::Lumbermill, pos, BuildMethod::SpawnBuildZone).is_none());
+    let lumbermill = commands::build_rotated(
+        &mut world,
+        Buildings::Lumbermill,
+        pos,
+        Rotation::Deg90,
+        BuildMethod::SpawnBuildZone,
+    )
+    .unwrap();
+    assert!(lumbermill.building_base.rotation == Rotation::Deg90);
+
+    assert!(matches!(world.map.get(&Pos::new(11, 3)), TileType::BuildZone(_)));
+    assert!(!matches!(world.map.get(&Pos::new(9, 4)), TileType::BuildZone(_)));
+    assert!(world.map.entrances(&pos)[0].doorstep == Pos::new(8, 3));
+}
@@ -0,0 +1,113 @@
+use std::io::Write;
+
+use rusty_rain::{
+    config::{buildings::Buildings, species::Species},
+    math::Pos,
+    world::{
+        World,
+        actions::BasicAction,
+        building::Building,
+        inventory::Inventory,
+        worker::{
+            Idle, Worker,
+            resolve::Resolve,
+            skills::{Skill, Skills},
+            worker_states::WorkerWithAction,
+        },
+    },
+    world_interaction::commands::{self, BuildMethod},
+};
+
+use std::sync::atomic::Ordering;
+
+use rusty_rain::FRAME_NUM;
+
+const DELTA: f32 = 1.0 / 30.0;
+
+pub fn test_levels() {
+    let mut skills = Skills::new();
+    assert_eq!(skills.level(Skill::Woodcutting), 0);
+    assert_eq!(skills.speed_bonus(Skill::Woodcutting), 1.0);
+
+    skills.add_experience(Skill::Woodcutting, 200.0);
+    skills.add_experience(Skill::Construction, 10.0);
+    assert_eq!(skills.level(Skill::Woodcutting), 2);
+    assert_eq!(skills.level(Skill::Construction), 0);
+    assert!(skills.speed_bonus(Skill::Woodcutting) > skills.speed_bonus(Skill::Construction));
+
+    //can not get better than the last level
+    skills.add_experience(Skill::Woodcutting, 10000.0);
+    assert_eq!(skills.level(Skill::Woodcutting), 5);
+
+    let all = skills.all();
+    assert_eq!(all.len(), 2);
+    assert!(all[0].0 == Skill::Woodcutting && all[1].0 == Skill::Construction);
+}
+
+/// A fresh woodcutter learns on the job, and a veteran one cuts more wood in the same time
+pub fn test_woodcutting(
+    mut world: World,
+    mut veteran_world: World,
+) {
+    let _ = env_logger::builder()
+        .format(|buf, record| {
+            let tick_num = FRAME_NUM.load(Ordering::Relaxed);
+            writeln!(buf, "@{}\t{}", tick_num, record.args())?;
+            Ok(())
+        })
+        .try_init();
+
+    configure_world(&mut world, "Fresh", Skills::new());
+    let mut veteran_skills = Skills::new();
+    veteran_skills.add_experience(Skill::Woodcutting, 1000.0);
+    configure_world(&mut veteran_world, "Veteran", veteran_skills);
+
+    let mut seconds = 0.0;
+    while seconds < 4.0 * 60.0 {
+        world.next_tick(DELTA);
+        veteran_world.next_tick(DELTA);
+        seconds += DELTA;
+    }
+
+    let skills = world.get_worker_skills("Fresh").unwrap();
+    assert!(skills[0].0 == Skill::Woodcutting);
+    assert!(skills[0].1 >= 1);
+    //walking to the trees and back does not count
+    assert!(skills[0].2 < 4.0 * 60.0);
+
+    let gathered = world.get_stores().next().unwrap().0.output.total_items();
+    let veteran_gathered = veteran_world.get_stores().next().unwrap().0.output.total_items();
+    assert!(veteran_gathered > gathered);
+}
+
+fn configure_world(
+    world: &mut World,
+    name: &str,
+    skills: Skills,
+) {
+    let maybe_store = commands::build(world, Buildings::MainStore, Pos::new(4, 3), BuildMethod::SpawnExisting);
+    if maybe_store.is_none() {
+        panic!();
+    }
+
+    let maybe_woodcutter = commands::build(
+        world,
+        Buildings::Woodcutter,
+        Pos::new(11, 5),
+        BuildMethod::SpawnExisting,
+    );
+    if let Some(Building { building_base, .. }) = maybe_woodcutter {
+        building_base.workers.push_back(Worker::Idle(WorkerWithAction::<Idle> {
+            name: name.to_string(),
+            species: Species::Human,
+            inventory: Inventory::limited(5.0),
+            carry_upgrades: Vec::new(),
+            pos: building_base.pos,
+            break_progress: BasicAction::new(600.0),
+            exhausted: false,
+            resolve: Resolve::new(),
+            skills,
+            action_data: Idle(),
+        }));
+    };
+}
@@ -0,0 +1,185 @@
+use log::info;
+use rusty_rain::{
+    config::{buildings::Buildings, inventory::InventoryItems, species::Species},
+    math::Pos,
+    world::{
+        World,
+        building::Building,
+        settlement::{Settlement, SettlementConfig},
+        worker::{
+            Worker,
+            resolve::{Housing, Resolve, ResolveFactor, SettlementConditions},
+            worker_states::WorkerWithAction,
+        },
+    },
+    world_interaction::commands::{self, BuildMethod},
+};
+use std::io::Write;
+
+use rusty_rain::FRAME_NUM;
+use std::sync::atomic::Ordering;
+
+pub fn test(mut world: World) {
+    let _ = env_logger::builder()
+        .format(|buf, record| {
+            let tick_num = FRAME_NUM.load(Ordering::Relaxed);
+            writeln!(buf, "@{}\t{}", tick_num, record.args())?;
+            Ok(())
+        })
+        .try_init();
+
+    configure_world(&mut world);
+
+    let mut seconds = 0.0;
+    const DELTA: f32 = 1.0 / 30.0;
+    while seconds < 3.0 * 60.0 {
+        world.next_tick(DELTA);
+        seconds += DELTA;
+    }
+
+    //beavers like berries more than vegetables - and they should have picked them from the store
+    let store = world.get_stores().next().unwrap();
+    assert!(store.0.output.get(&InventoryItems::Berries) < 10.0);
+    assert_eq!(store.0.output.get(&InventoryItems::Vegetables), 10.0);
+
+    let resolve = world.get_worker_resolve("Beaver Tender").unwrap();
+    for (factor, value) in &resolve.factors {
+        info!("{}: {}", factor, value);
+    }
+
+    let factors: Vec<_> = resolve.factors.iter().map(|(f, _)| *f).collect();
+    assert!(factors.contains(&ResolveFactor::PreferredFood));
+    assert!(factors.contains(&ResolveFactor::Housed));
+    assert!(factors.contains(&ResolveFactor::PreferredHousing));
+}
+
+pub fn test_spawning(mut world: World) {
+    world.settlement = Settlement::new(SettlementConfig {
+        species: vec![(Species::Beaver, 2), (Species::Harpy, 1)],
+    });
+
+    let species: Vec<Species> = (0..6)
+        .map(|i| *world.spawn_worker(format!("Worker {}", i), Pos::new(3, 3)).species())
+        .collect();
+
+    assert_eq!(
+        species,
+        vec![
+            Species::Beaver,
+            Species::Beaver,
+            Species::Harpy,
+            Species::Beaver,
+            Species::Beaver,
+            Species::Harpy
+        ]
+    );
+
+    assert!(Species::Beaver.is_specialised_in(Buildings::Woodcutter));
+    assert!(!Species::Human.is_specialised_in(Buildings::Woodcutter));
+}
+
+/// The humans are processed first, but the only house is a beaver lodge - the beaver still gets a
+/// bed in it, and the humans share the rest
+pub fn test_housing(mut world: World) {
+    let hearth_pos = Pos::new(world.map.width() / 2, world.map.height() / 2);
+    let maybe_hearth = commands::build(
+        &mut world,
+        Buildings::MainHearth,
+        hearth_pos,
+        BuildMethod::SpawnExisting,
+    );
+    if let Some(Building { building_base, .. }) = maybe_hearth {
+        for i in 0..4 {
+            let human = WorkerWithAction::new(format!("Human {}", i), Species::Human, hearth_pos);
+            building_base.workers.push_back(Worker::Idle(human));
+        }
+        let beaver = WorkerWithAction::new("Beaver".to_string(), Species::Beaver, hearth_pos);
+        building_base.workers.push_back(Worker::Idle(beaver));
+    } else {
+        panic!();
+    }
+
+    let maybe_house = commands::build(
+        &mut world,
+        Buildings::BeaverHouse,
+        Pos::new(12, 11),
+        BuildMethod::SpawnExisting,
+    );
+    assert!(maybe_house.is_some());
+
+    world.next_tick(1.0 / 30.0);
+
+    let beaver = world.get_worker_resolve("Beaver").unwrap();
+    assert!(
+        beaver
+            .factors
+            .iter()
+            .any(|(f, _)| *f == ResolveFactor::PreferredHousing)
+    );
+
+    let housed_humans = (0..4)
+        .filter(|i| {
+            let resolve = world.get_worker_resolve(&format!("Human {}", i)).unwrap();
+            resolve.factors.iter().any(|(f, _)| *f == ResolveFactor::Housed)
+        })
+        .count();
+    assert_eq!(housed_humans, 3);
+}
+
+pub fn test_hearth_resilience() {
+    let conditions = SettlementConditions {
+        hearth_burning: false,
+        hearth_out: true,
+        housing_capacity: Vec::new(),
+        decorations: Vec::new(),
+    };
+
+    let penalty = |species: Species| {
+        let mut resolve = Resolve::new();
+        resolve.recalculate(false, Housing::Homeless, species, &conditions);
+        resolve
+            .factors
+            .iter()
+            .find(|(f, _)| *f == ResolveFactor::HearthOut)
+            .map(|(_, v)| *v)
+            .unwrap_or(0.0)
+    };
+
+    //harpies are the most resilient, lizards feel the cold the most
+    assert!(penalty(Species::Lizard) < penalty(Species::Human));
+    assert!(penalty(Species::Human) < penalty(Species::Harpy));
+    assert!(penalty(Species::Harpy) < 0.0);
+}
+
+fn configure_world(world: &mut World) {
+    world.settlement = Settlement::new(SettlementConfig {
+        species: vec![(Species::Beaver, 1)],
+    });
+
+    let hearth_pos = Pos::new(world.map.width() / 2, world.map.height() / 2);
+    let tender = world.spawn_worker("Beaver Tender".to_string(), hearth_pos);
+    let maybe_hearth = commands::build(world, Buildings::MainHearth, hearth_pos, BuildMethod::SpawnExisting);
+
+    if let Some(Building { building_base, .. }) = maybe_hearth {
+        building_base.workers.push_front(tender)
+    };
+
+    let maybe_store = commands::build(world, Buildings::MainStore, Pos::new(4, 3), BuildMethod::SpawnExisting);
+    if let Some(Building { building_base, .. }) = maybe_store {
+        building_base.output.add(&InventoryItems::Wood, 40.0);
+        building_base.output.add(&InventoryItems::Vegetables, 10.0);
+        building_base.output.add(&InventoryItems::Berries, 10.0);
+    }
+
+    //the shelter is built first - the beaver should still pick their own lodge
+    let maybe_shelter = commands::build(world, Buildings::Shelter, Pos::new(12, 3), BuildMethod::SpawnExisting);
+    assert!(maybe_shelter.is_some());
+
+    let maybe_house = commands::build(
+        world,
+        Buildings::BeaverHouse,
+        Pos::new(12, 11),
+        BuildMethod::SpawnExisting,
+    );
+    assert!(maybe_house.is_some());
+}
@@ -0,0 +1,86 @@
+use rusty_rain::{
+    config::{
+        buildings::Buildings,
+        inventory::{InventoryItems, ItemTag},
+    },
+    math::Pos,
+    world::{World, events::WorldEvent, inventory::Inventory},
+    world_interaction::commands::{self, BuildMethod},
+};
+
+const DELTA: f32 = 1.0 / 30.0;
+
+pub fn test_inventory() {
+    let mut inventory = Inventory::new();
+    inventory.add(&InventoryItems::Meat, 10.0);
+    inventory.add(&InventoryItems::Jerky, 10.0);
+
+    //ten pieces of meat lose one piece every 30 seconds
+    let mut spoiled = Vec::new();
+    let mut seconds = 0.0;
+    while seconds < 31.0 {
+        spoiled.append(&mut inventory.spoil(DELTA, &[]));
+        seconds += DELTA;
+    }
+
+    assert_eq!(spoiled, vec![(InventoryItems::Meat, 1.0)]);
+    assert_eq!(inventory.get(&InventoryItems::Meat), 9.0);
+    assert_eq!(inventory.get(&InventoryItems::Jerky), 10.0);
+
+    //kept in the cellar, nothing spoils
+    let mut seconds = 0.0;
+    while seconds < 60.0 {
+        assert!(inventory.spoil(DELTA, &[ItemTag::Food]).is_empty());
+        seconds += DELTA;
+    }
+    assert_eq!(inventory.get(&InventoryItems::Meat), 9.0);
+}
+
+pub fn test(mut world: World) {
+    configure_world(&mut world);
+
+    let mut seconds = 0.0;
+    while seconds < 2.0 * 60.0 {
+        world.next_tick(DELTA);
+        seconds += DELTA;
+    }
+
+    let main_store = world
+        .get_stores()
+        .find(|s| s.0.building == Buildings::MainStore)
+        .unwrap();
+    let granary = world.get_stores().find(|s| s.0.building == Buildings::Granary).unwrap();
+
+    //berries in the main store rot, granary keeps them fresh
+    assert_eq!(main_store.0.output.get(&InventoryItems::Berries), 9.0);
+    assert_eq!(granary.0.output.get(&InventoryItems::Berries), 10.0);
+
+    //there is nothing to preserve the meat left lying around
+    let pile = world.get_item_piles().next().unwrap();
+    assert!(pile.items.get(&InventoryItems::Meat) < 5.0);
+
+    let spoiled = world.get_spoiled_items();
+    assert_eq!(spoiled[0], (InventoryItems::Berries, 1.0));
+    assert_eq!(spoiled[1].0, InventoryItems::Meat);
+
+    let events = world.take_events();
+    assert!(
+        events
+            .iter()
+            .any(|e| matches!(e, WorldEvent::ItemsSpoiled { pos, .. } if *pos == Pos::new(4, 3)))
+    );
+}
+
+fn configure_world(world: &mut World) {
+    let maybe_store = commands::build(world, Buildings::MainStore, Pos::new(4, 3), BuildMethod::SpawnExisting);
+    if let Some(store) = maybe_store {
+        store.building_base.output.add(&InventoryItems::Berries, 10.0);
+    }
+
+    let maybe_granary = commands::build(world, Buildings::Granary, Pos::new(9, 2), BuildMethod::SpawnExisting);
+    if let Some(granary) = maybe_granary {
+        granary.building_base.output.add(&InventoryItems::Berries, 10.0);
+    }
+
+    world.drop_items(Pos::new(10, 11), [(InventoryItems::Meat, 5.0)]);
+}
@@ -0,0 +1,81 @@
+use rusty_rain::{
+    config::{buildings::Buildings, inventory::InventoryItems},
+    math::Pos,
+    world::{World, building::Building, statistics::Statistics},
+    world_interaction::commands::{self, BuildMethod},
+};
+
+const DELTA: f32 = 1.0 / 30.0;
+
+pub fn test_rates() {
+    let mut statistics = Statistics::new();
+    statistics.record_produced([(InventoryItems::Wood, 10.0)]);
+    statistics.record_consumed([(InventoryItems::Wood, 5.0), (InventoryItems::Berries, 1.0)]);
+    statistics.time = 30.0;
+
+    let rates = statistics.rates(&InventoryItems::Wood, 60.0);
+    assert_eq!(rates.produced, 20.0);
+    assert_eq!(rates.consumed, 10.0);
+    assert_eq!(rates.net(), 10.0);
+    assert_eq!(statistics.rates(&InventoryItems::Berries, 60.0).consumed, 2.0);
+}
+
+pub fn test(mut world: World) {
+    configure_world(&mut world);
+
+    let mut seconds = 0.0;
+    while seconds < 61.0 {
+        world.next_tick(DELTA);
+        seconds += DELTA;
+    }
+
+    //all the wood is somewhere - in the store, in the hearth or in the tender's hands
+    let stock = world.get_stock();
+    let total = world.get_item_total(&InventoryItems::Wood);
+    assert_eq!(
+        stock.stores.get(&InventoryItems::Wood)
+            + stock.inputs.get(&InventoryItems::Wood)
+            + stock.carried.get(&InventoryItems::Wood),
+        total
+    );
+    assert!(stock.inputs.get(&InventoryItems::Wood) > 0.0);
+
+    //and whatever is missing, was burned
+    let history = world.get_item_history(&InventoryItems::Wood);
+    assert_eq!(history.len(), 6);
+    let burned: f32 =
+        history.iter().map(|p| p.consumed).sum::<f32>() + world.statistics.current.consumed.get(&InventoryItems::Wood);
+    assert!(burned > 0.0);
+    assert_eq!(total + burned, 40.0);
+    assert_eq!(history.last().unwrap().time, 60.0);
+
+    let rates = world.get_item_rates(&InventoryItems::Wood, 60.0);
+    assert_eq!(rates.produced, 0.0);
+    assert!(rates.consumed > 0.0);
+}
+
+/// A single tick spanning several buckets closes all of them
+pub fn test_long_tick(mut world: World) {
+    configure_world(&mut world);
+
+    world.next_tick(35.0);
+    assert_eq!(world.statistics.history.len(), 3);
+    assert_eq!(world.statistics.current.start, 30.0);
+
+    world.next_tick(DELTA);
+    assert_eq!(world.statistics.history.len(), 3);
+}
+
+fn configure_world(world: &mut World) {
+    let hearth_pos = Pos::new(world.map.width() / 2, world.map.height() / 2);
+    let tender = world.spawn_worker("Hearth Tender".to_string(), hearth_pos);
+    let maybe_hearth = commands::build(world, Buildings::MainHearth, hearth_pos, BuildMethod::SpawnExisting);
+    if let Some(Building { building_base, .. }) = maybe_hearth {
+        building_base.workers.push_back(tender);
+    }
+
+    let maybe_store = commands::build(world, Buildings::MainStore, Pos::new(4, 3), BuildMethod::SpawnExisting);
+    if let Some(Building { building_base, .. }) = maybe_store {
+        building_base.output.add(&InventoryItems::Wood, 40.0);
+    }
+}
@@ -1,9 +1,28 @@
 pub mod build_supplying_test;
+pub mod build_zone_test;
+pub mod carry_test;
+pub mod decoration_test;
+pub mod entrance_test;
+pub mod flow_field_test;
 pub mod gathering_test;
+pub mod ground_items_test;
 pub mod hearth_test;
 pub mod helpers;
+pub mod inventory_test;
+pub mod jobs_test;
 pub mod lost_test;
+pub mod newcomers_test;
+pub mod pathfinding_test;
 pub mod production_test;
+pub mod reservation_test;
+pub mod resolve_test;
+pub mod rotation_test;
+pub mod skills_test;
+pub mod species_test;
+pub mod spoilage_test;
+pub mod statistics_test;
+pub mod tools_test;
+pub mod traffic_test;
 
 #[cfg(test)]
 #[test]
@@ -19,6 +38,27 @@ pub fn hearth() {
     hearth_test::test(world);
 }
 
+#[cfg(test)]
+#[test]
+pub fn hearth_any_fuel() {
+    let world = helpers::new_test_world(16, 16);
+    hearth_test::test_any_fuel(world);
+}
+
+#[cfg(test)]
+#[test]
+pub fn hearth_last_fuel() {
+    let world = helpers::new_test_world(16, 16);
+    hearth_test::test_last_fuel(world);
+}
+
+#[cfg(test)]
+#[test]
+pub fn hearth_out() {
+    let world = helpers::new_test_world(16, 16);
+    hearth_test::test_out(world);
+}
+
 #[cfg(test)]
 #[test]
 pub fn gathering() {
@@ -26,6 +66,19 @@ pub fn gathering() {
     gathering_test::test(world);
 }
 
+#[cfg(test)]
+#[test]
+pub fn gathering_node_charge() {
+    gathering_test::test_node_charge();
+}
+
+#[cfg(test)]
+#[test]
+pub fn gathering_forager() {
+    let world = helpers::new_test_world(16, 16);
+    gathering_test::test_forager(world);
+}
+
 #[cfg(test)]
 #[test]
 pub fn worker_lost() {
@@ -39,3 +92,394 @@ pub fn build_supply() {
     let world = helpers::new_test_world(16, 16);
     build_supplying_test::test(world);
 }
+
+#[cfg(test)]
+#[test]
+pub fn build_supply_trip_cost() {
+    let world = helpers::new_test_world(32, 32);
+    build_supplying_test::test_trip_cost(world);
+}
+
+#[cfg(test)]
+#[test]
+pub fn build_supply_abandoned_trip() {
+    let world = helpers::new_test_world(32, 32);
+    build_supplying_test::test_abandoned_trip(world);
+}
+
+#[cfg(test)]
+#[test]
+pub fn build_supply_blocked_trip() {
+    let world = helpers::new_test_world(32, 32);
+    build_supplying_test::test_blocked_trip(world);
+}
+
+#[cfg(test)]
+#[test]
+pub fn build_supply_multiple_sources() {
+    let world = helpers::new_test_world(32, 32);
+    build_supplying_test::test_multiple_sources(world);
+}
+
+#[cfg(test)]
+#[test]
+pub fn resolve() {
+    let world = helpers::new_test_world(16, 16);
+    resolve_test::test(world);
+}
+
+#[cfg(test)]
+#[test]
+pub fn resolve_leaving() {
+    let world = helpers::new_test_world(16, 16);
+    resolve_test::test_leaving(world);
+}
+
+#[cfg(test)]
+#[test]
+pub fn decorations() {
+    let world = helpers::new_test_world(16, 16);
+    decoration_test::test(world);
+}
+
+#[cfg(test)]
+#[test]
+pub fn decorations_long_tick() {
+    let world = helpers::new_test_world(32, 32);
+    decoration_test::test_long_tick(world);
+}
+
+#[cfg(test)]
+#[test]
+pub fn species() {
+    let world = helpers::new_test_world(16, 16);
+    species_test::test(world);
+}
+
+#[cfg(test)]
+#[test]
+pub fn species_housing() {
+    let world = helpers::new_test_world(16, 16);
+    species_test::test_housing(world);
+}
+
+#[cfg(test)]
+#[test]
+pub fn species_spawning() {
+    let world = helpers::new_test_world(16, 16);
+    species_test::test_spawning(world);
+}
+
+#[cfg(test)]
+#[test]
+pub fn species_hearth_resilience() {
+    species_test::test_hearth_resilience();
+}
+
+#[cfg(test)]
+#[test]
+pub fn newcomers() {
+    let world = helpers::new_test_world(16, 16);
+    newcomers_test::test(world);
+}
+
+#[cfg(test)]
+#[test]
+pub fn newcomers_no_fuel() {
+    let world = helpers::new_test_world(16, 16);
+    newcomers_test::test_no_fuel(world);
+}
+
+#[cfg(test)]
+#[test]
+pub fn item_configs() {
+    inventory_test::test_item_configs();
+}
+
+#[cfg(test)]
+#[test]
+pub fn reservation_ledger() {
+    reservation_test::test_ledger();
+}
+
+#[cfg(test)]
+#[test]
+pub fn reservation_hearth_supplying() {
+    let world = helpers::new_test_world(16, 16);
+    reservation_test::test_hearth_supplying(world);
+}
+
+#[cfg(test)]
+#[test]
+pub fn reservation_build_supplying() {
+    let world = helpers::new_test_world(16, 16);
+    reservation_test::test_build_supplying(world);
+}
+
+#[cfg(test)]
+#[test]
+pub fn reservation_release() {
+    let world = helpers::new_test_world(16, 16);
+    reservation_test::test_release(world);
+}
+
+#[cfg(test)]
+#[test]
+pub fn reservation_release_on_give_up() {
+    let world = helpers::new_test_world(32, 32);
+    reservation_test::test_release_on_give_up(world);
+}
+
+#[cfg(test)]
+#[test]
+pub fn reservation_reserved_output() {
+    let world = helpers::new_test_world(16, 16);
+    reservation_test::test_reserved_output(world);
+}
+
+#[cfg(test)]
+#[test]
+pub fn ground_items() {
+    let world = helpers::new_test_world(16, 16);
+    ground_items_test::test(world);
+}
+
+#[cfg(test)]
+#[test]
+pub fn carry_weight() {
+    carry_test::test_weight();
+}
+
+#[cfg(test)]
+#[test]
+pub fn carry_upgrades() {
+    let world = helpers::new_test_world(16, 16);
+    carry_test::test_upgrades(world);
+}
+
+#[cfg(test)]
+#[test]
+pub fn carry_multi_item_load() {
+    let world = helpers::new_test_world(16, 16);
+    carry_test::test_multi_item_load(world);
+}
+
+#[cfg(test)]
+#[test]
+pub fn spoilage_inventory() {
+    spoilage_test::test_inventory();
+}
+
+#[cfg(test)]
+#[test]
+pub fn spoilage() {
+    let world = helpers::new_test_world(16, 16);
+    spoilage_test::test(world);
+}
+
+#[cfg(test)]
+#[test]
+pub fn statistics_rates() {
+    statistics_test::test_rates();
+}
+
+#[cfg(test)]
+#[test]
+pub fn statistics() {
+    let world = helpers::new_test_world(16, 16);
+    statistics_test::test(world);
+}
+
+#[cfg(test)]
+#[test]
+pub fn statistics_long_tick() {
+    let world = helpers::new_test_world(16, 16);
+    statistics_test::test_long_tick(world);
+}
+
+#[cfg(test)]
+#[test]
+pub fn pathfinding_closest_by_cost() {
+    let map = helpers::new_test_map(16, 16);
+    pathfinding_test::test_closest_by_cost(map);
+}
+
+#[cfg(test)]
+#[test]
+pub fn pathfinding_pricing() {
+    let map = helpers::new_test_map(16, 16);
+    pathfinding_test::test_pricing(map);
+}
+
+#[cfg(test)]
+#[test]
+pub fn pathfinding_cache() {
+    let map = helpers::new_test_map(16, 16);
+    pathfinding_test::test_cache(map);
+}
+
+#[cfg(test)]
+#[test]
+pub fn pathfinding_repath() {
+    let world = helpers::new_test_world(16, 16);
+    pathfinding_test::test_repath(world);
+}
+
+#[cfg(test)]
+#[test]
+pub fn pathfinding_hierarchical() {
+    pathfinding_test::test_hierarchical();
+}
+
+#[cfg(test)]
+#[test]
+pub fn pathfinding_hierarchical_a_star() {
+    pathfinding_test::test_hierarchical_a_star();
+}
+
+#[cfg(test)]
+#[test]
+pub fn pathfinding_diagonal() {
+    let map = helpers::new_test_map(16, 16);
+    pathfinding_test::test_diagonal(map);
+}
+
+#[cfg(test)]
+#[test]
+pub fn pathfinding_diagonal_transit() {
+    let map = helpers::new_test_map(16, 16);
+    pathfinding_test::test_diagonal_transit(map);
+}
+
+#[cfg(test)]
+#[test]
+pub fn pathfinding_diagonal_hierarchical() {
+    pathfinding_test::test_diagonal_hierarchical();
+}
+
+#[cfg(test)]
+#[test]
+pub fn pathfinding_big_world() {
+    let world = helpers::new_test_world(64, 64);
+    pathfinding_test::test_big_world(world);
+}
+
+#[cfg(test)]
+#[test]
+pub fn entrance_paths() {
+    let world = helpers::new_test_world(16, 16);
+    entrance_test::test_paths(world);
+}
+
+#[cfg(test)]
+#[test]
+pub fn entrance_can_build() {
+    let world = helpers::new_test_world(16, 16);
+    entrance_test::test_can_build(world);
+}
+
+#[cfg(test)]
+#[test]
+pub fn rotation_footprint() {
+    let world = helpers::new_test_world(16, 16);
+    rotation_test::test_footprint(world);
+}
+
+#[cfg(test)]
+#[test]
+pub fn rotation_tight_glade() {
+    let world = helpers::new_test_world(16, 16);
+    rotation_test::test_tight_glade(world);
+}
+
+#[cfg(test)]
+#[test]
+pub fn flow_field_paths() {
+    let world = helpers::new_test_world(16, 16);
+    flow_field_test::test_paths(world);
+}
+
+#[cfg(test)]
+#[test]
+pub fn flow_field_incremental() {
+    flow_field_test::test_incremental();
+}
+
+#[cfg(test)]
+#[test]
+pub fn traffic_crowd() {
+    let world = helpers::new_test_world(16, 16);
+    let other_world = helpers::new_test_world(16, 16);
+    traffic_test::test_crowd(world, other_world);
+}
+
+#[cfg(test)]
+#[test]
+pub fn traffic_detour() {
+    let world = helpers::new_test_world(16, 16);
+    traffic_test::test_detour(world);
+}
+
+#[cfg(test)]
+#[test]
+pub fn jobs_board() {
+    jobs_test::test_board();
+}
+
+#[cfg(test)]
+#[test]
+pub fn jobs_build_order() {
+    let world = helpers::new_test_world(16, 16);
+    jobs_test::test_build_order(world);
+}
+
+#[cfg(test)]
+#[test]
+pub fn jobs_helping_out() {
+    let world = helpers::new_test_world(16, 16);
+    jobs_test::test_helping_out(world);
+}
+
+#[cfg(test)]
+#[test]
+pub fn build_zone_shared() {
+    let world = helpers::new_test_world(16, 16);
+    let other_world = helpers::new_test_world(16, 16);
+    build_zone_test::test_shared(world, other_world);
+}
+
+#[cfg(test)]
+#[test]
+pub fn build_zone_builder_leaves() {
+    let world = helpers::new_test_world(16, 16);
+    build_zone_test::test_builder_leaves(world);
+}
+
+#[cfg(test)]
+#[test]
+pub fn skills_levels() {
+    skills_test::test_levels();
+}
+
+#[cfg(test)]
+#[test]
+pub fn skills_woodcutting() {
+    let world = helpers::new_test_world(16, 16);
+    let veteran_world = helpers::new_test_world(16, 16);
+    skills_test::test_woodcutting(world, veteran_world);
+}
+
+#[cfg(test)]
+#[test]
+pub fn tools_woodcutter() {
+    let world = helpers::new_test_world(16, 16);
+    let world_with_tools = helpers::new_test_world(16, 16);
+    tools_test::test_woodcutter(world, world_with_tools);
+}
+
+#[cfg(test)]
+#[test]
+pub fn tools_toolshop() {
+    let world = helpers::new_test_world(16, 16);
+    tools_test::test_toolshop(world);
+}
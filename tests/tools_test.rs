@@ -0,0 +1,177 @@
+use std::io::Write;
+
+use rusty_rain::{
+    config::{buildings::Buildings, inventory::InventoryItems, species::Species},
+    math::Pos,
+    world::{
+        World,
+        actions::BasicAction,
+        building::Building,
+        inventory::Inventory,
+        worker::{Idle, Worker, resolve::Resolve, skills::Skills, worker_states::WorkerWithAction},
+    },
+    world_interaction::commands::{self, BuildMethod},
+};
+
+use std::sync::atomic::Ordering;
+
+use rusty_rain::FRAME_NUM;
+
+const DELTA: f32 = 1.0 / 30.0;
+
+/// The woodcutter fetches the tools from the store, cuts more wood with them and wears them out
+pub fn test_woodcutter(
+    mut world: World,
+    mut world_with_tools: World,
+) {
+    let _ = env_logger::builder()
+        .format(|buf, record| {
+            let tick_num = FRAME_NUM.load(Ordering::Relaxed);
+            writeln!(buf, "@{}\t{}", tick_num, record.args())?;
+            Ok(())
+        })
+        .try_init();
+
+    configure_woodcutter(&mut world, 0.0);
+    configure_woodcutter(&mut world_with_tools, 2.0);
+
+    let mut seconds = 0.0;
+    let mut max_tools: f32 = 0.0;
+    while seconds < 4.0 * 60.0 {
+        world.next_tick(DELTA);
+        world_with_tools.next_tick(DELTA);
+        seconds += DELTA;
+
+        max_tools = max_tools.max(woodcutter_tools(&world_with_tools));
+    }
+
+    //both tools were brought in one go, and are wearing out since
+    assert_eq!(max_tools, 2.0);
+    assert!(woodcutter_tools(&world_with_tools) < 2.0);
+    let store = world_with_tools.get_stores().next().unwrap();
+    assert_eq!(store.0.output.get(&InventoryItems::Tools), 0.0);
+
+    //the tools are not gone yet - the woodcutter can still carry more
+    assert_eq!(woodcutter_worker(&world).carry_capacity(), 5.0);
+    assert_eq!(woodcutter_worker(&world_with_tools).carry_capacity(), 7.0);
+
+    //the worn out part is used up - the rest is still there
+    let history = world_with_tools.get_item_history(&InventoryItems::Tools);
+    let worn = history.iter().map(|p| p.consumed).sum::<f32>()
+        + world_with_tools.statistics.current.consumed.get(&InventoryItems::Tools);
+    assert!(worn > 0.0);
+    let total = world_with_tools.get_item_total(&InventoryItems::Tools);
+    assert_eq!(total, woodcutter_tools(&world_with_tools));
+    assert!((total + worn - 2.0).abs() < 0.001);
+
+    let wood = world.get_stores().next().unwrap().0.output.get(&InventoryItems::Wood);
+    let wood_with_tools = store.0.output.get(&InventoryItems::Wood);
+    assert!(wood > 0.0);
+    assert!(wood_with_tools > wood);
+}
+
+/// Toolshop makes the tools out of the planks and copper, and brings them to the store
+pub fn test_toolshop(mut world: World) {
+    let maybe_store = commands::build(
+        &mut world,
+        Buildings::MainStore,
+        Pos::new(4, 3),
+        BuildMethod::SpawnExisting,
+    );
+    if let Some(Building { building_base, .. }) = maybe_store {
+        building_base.output.add(&InventoryItems::Plank, 4.0);
+        building_base.output.add(&InventoryItems::CopperBars, 4.0);
+    } else {
+        panic!();
+    }
+
+    let maybe_toolshop = commands::build(
+        &mut world,
+        Buildings::Toolshop,
+        Pos::new(11, 5),
+        BuildMethod::SpawnExisting,
+    );
+    if let Some(Building { building_base, .. }) = maybe_toolshop {
+        building_base.workers.push_back(new_worker("Tinker", building_base.pos));
+    } else {
+        panic!();
+    }
+
+    let mut seconds = 0.0;
+    while seconds < 3.0 * 60.0 {
+        world.next_tick(DELTA);
+        seconds += DELTA;
+    }
+
+    let store = world.get_stores().next().unwrap();
+    assert_eq!(store.0.output.get(&InventoryItems::Tools), 2.0);
+    assert_eq!(store.0.output.get(&InventoryItems::Plank), 0.0);
+    assert_eq!(store.0.output.get(&InventoryItems::CopperBars), 0.0);
+}
+
+fn configure_woodcutter(
+    world: &mut World,
+    tools: f32,
+) {
+    let maybe_store = commands::build(world, Buildings::MainStore, Pos::new(4, 3), BuildMethod::SpawnExisting);
+    if let Some(Building { building_base, .. }) = maybe_store {
+        building_base.output.add(&InventoryItems::Tools, tools);
+    } else {
+        panic!();
+    }
+
+    let maybe_woodcutter = commands::build(
+        world,
+        Buildings::Woodcutter,
+        Pos::new(11, 5),
+        BuildMethod::SpawnExisting,
+    );
+    if let Some(Building { building_base, .. }) = maybe_woodcutter {
+        building_base
+            .workers
+            .push_back(new_worker("Woodchuck Chuck", building_base.pos));
+    } else {
+        panic!();
+    }
+}
+
+fn woodcutter_tools(world: &World) -> f32 {
+    world
+        .shops
+        .iter()
+        .find(|s| s.building_base.building == Buildings::Woodcutter)
+        .unwrap()
+        .building_base
+        .tools
+        .amount
+}
+
+fn woodcutter_worker(world: &World) -> &Worker {
+    world
+        .shops
+        .iter()
+        .find(|s| s.building_base.building == Buildings::Woodcutter)
+        .unwrap()
+        .building_base
+        .workers
+        .front()
+        .unwrap()
+}
+
+fn new_worker(
+    name: &str,
+    pos: Pos,
+) -> Worker {
+    Worker::Idle(WorkerWithAction::<Idle> {
+        name: name.to_string(),
+        species: Species::Human,
+        inventory: Inventory::limited(5.0),
+        carry_upgrades: Vec::new(),
+        pos,
+        break_progress: BasicAction::new(600.0),
+        exhausted: false,
+        resolve: Resolve::new(),
+        skills: Skills::new(),
+        action_data: Idle(),
+    })
+}
@@ -0,0 +1,157 @@
+use rusty_rain::{
+    ai::pathfinding,
+    config::{buildings::Buildings, species::Species},
+    math::Pos,
+    world::{
+        World,
+        actions::{BasicAction, TransitAction},
+        inventory::Inventory,
+        worker::{
+            LostAction, ReturningAction, Worker, resolve::Resolve, skills::Skills, worker_states::WorkerWithAction,
+        },
+    },
+    world_interaction::commands::{self, BuildMethod},
+};
+
+const DELTA: f32 = 1.0 / 30.0;
+
+/// A crowd walking back to the hearth along the same way gets in its own way - but only with the
+/// traffic enabled
+pub fn test_crowd(
+    world: World,
+    other_world: World,
+) {
+    let (free_ticks, _) = walk_home(other_world, false);
+    let (crowded_ticks, world) = walk_home(world, true);
+    assert!(crowded_ticks as f32 > free_ticks as f32 * 1.3);
+
+    //everybody is home, so nobody is standing anywhere
+    assert_eq!(world.get_tile_occupancy(&Pos::new(12, 3)), 0);
+
+    let bottlenecks = world.get_bottlenecks(60.0, 3);
+    assert_eq!(bottlenecks.len(), 3);
+    assert!(bottlenecks[0].1 >= bottlenecks[1].1 && bottlenecks[1].1 >= bottlenecks[2].1);
+    assert!(bottlenecks[0].1 > 0.0);
+}
+
+/// The new paths go around a crowd, rather than squeezing through it
+pub fn test_detour(mut world: World) {
+    let start = Pos::new(4, 10);
+    let goal = Pos::new(12, 10);
+    let crowded = [Pos::new(7, 10), Pos::new(8, 10), Pos::new(9, 10)];
+    let straight = pathfinding::a_star(&world.map, start, goal).unwrap();
+    assert!(crowded.iter().all(|p| straight.contains(p)));
+
+    //lost workers stand still for a while
+    commands::build(
+        &mut world,
+        Buildings::MainStore,
+        Pos::new(4, 3),
+        BuildMethod::SpawnExisting,
+    )
+    .unwrap();
+    let store = world.shops.back_mut().unwrap();
+    for (i, pos) in crowded.iter().flat_map(|p| [*p; 4]).enumerate() {
+        store
+            .building_base
+            .workers
+            .push_back(Worker::Lost(WorkerWithAction::<LostAction> {
+                name: format!("Bystander {}", i),
+                species: Species::Human,
+                inventory: Inventory::limited(5.0),
+                carry_upgrades: Vec::new(),
+                pos,
+                break_progress: BasicAction::new(Worker::TIME_TO_BREAK),
+                exhausted: false,
+                resolve: Resolve::new(),
+                skills: Skills::new(),
+                action_data: LostAction::new(),
+            }));
+    }
+
+    let store_pos = Pos::new(4, 3);
+    let updated_tiles = world.map.flow_field(&store_pos).unwrap().updated_tiles;
+    let (revision, congestion_revision) = (world.map.revision, world.map.congestion_revision);
+
+    world.traffic.enabled = true;
+    world.next_tick(DELTA);
+    assert_eq!(world.get_tile_occupancy(&crowded[1]), 4);
+
+    let detour = pathfinding::a_star(&world.map, start, goal).unwrap();
+    assert!(crowded.iter().all(|p| !detour.contains(p)));
+
+    //the crowd is only a soft cost - the map did not change, and the flow fields stay as they are
+    assert_eq!(world.map.revision, revision);
+    assert!(world.map.congestion_revision > congestion_revision);
+    assert_eq!(world.map.flow_field(&store_pos).unwrap().updated_tiles, updated_tiles);
+
+    //one of the bystanders does not get in their own way
+    let (from, tile) = (Pos::new(8, 9), crowded[1]);
+    assert!(world.map.route_step_cost(&from, &tile, &tile) < world.map.route_step_cost(&from, &tile, &from));
+
+    //the crowd does not matter with the traffic off
+    world.traffic.enabled = false;
+    world.next_tick(DELTA);
+    let path = pathfinding::a_star(&world.map, start, goal).unwrap();
+    assert!(path == straight);
+}
+
+/// Number of ticks until all the workers got back into the hearth
+fn walk_home(
+    mut world: World,
+    traffic: bool,
+) -> (usize, World) {
+    world.traffic.enabled = traffic;
+
+    let hearth_pos = Pos::new(8, 8);
+    let start = Pos::new(12, 3);
+    commands::build(
+        &mut world,
+        Buildings::MainHearth,
+        hearth_pos,
+        BuildMethod::SpawnExisting,
+    )
+    .unwrap();
+    let path = pathfinding::path_into_building(&world.map, start, hearth_pos).unwrap();
+
+    let transits: Vec<TransitAction> = (0..4).map(|_| TransitAction::new(path.clone(), &world.map)).collect();
+    let hearth = world.shops.back_mut().unwrap();
+    for (i, transit) in transits.into_iter().enumerate() {
+        hearth
+            .building_base
+            .workers
+            .push_back(Worker::Returning(WorkerWithAction::<ReturningAction> {
+                name: format!("Commuter {}", i),
+                species: Species::Human,
+                inventory: Inventory::limited(5.0),
+                carry_upgrades: Vec::new(),
+                pos: start,
+                break_progress: BasicAction::new(0.0),
+                exhausted: false,
+                resolve: Resolve::new(),
+                skills: Skills::new(),
+                action_data: ReturningAction(transit),
+            }));
+    }
+
+    world.next_tick(DELTA);
+    if traffic {
+        assert_eq!(world.get_tile_occupancy(&start), 4);
+    }
+
+    let mut ticks = 1;
+    while world
+        .get_hearths()
+        .next()
+        .unwrap()
+        .0
+        .workers
+        .iter()
+        .any(|w| matches!(w, Worker::Returning(_)))
+    {
+        world.next_tick(DELTA);
+        ticks += 1;
+        assert!(ticks < 30 * 60, "the workers should have been home long ago");
+    }
+    (ticks, world)
+}